@@ -0,0 +1,14 @@
+-- 回滚说明：
+-- 1. 删除 transcript_url 与 transcript_type 列
+-- 2. 会丢失已解析的字幕信息
+-- 3. 重新爬取 feed 即可恢复
+
+-- 开始事务
+BEGIN;
+
+ALTER TABLE episodes
+    DROP COLUMN IF EXISTS transcript_type,
+    DROP COLUMN IF EXISTS transcript_url;
+
+-- 提交事务
+COMMIT;
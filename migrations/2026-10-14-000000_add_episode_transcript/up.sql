@@ -0,0 +1,15 @@
+-- 迁移说明：
+-- 1. 为 episodes 增加 Podcasting 2.0 <podcast:transcript> 的字幕地址与类型
+-- 2. 修改 episodes 表
+-- 3. 无数据迁移，新列允许为空
+-- 4. 仅新增可空列，无需重写表
+
+-- 开始事务
+BEGIN;
+
+ALTER TABLE episodes
+    ADD COLUMN transcript_url VARCHAR(1024),
+    ADD COLUMN transcript_type VARCHAR(100);
+
+-- 提交事务
+COMMIT;
@@ -0,0 +1,11 @@
+-- 回滚说明：
+-- 1. 删除 podcasts.last_build_date 索引
+-- 2. 不影响数据
+
+-- 开始事务
+BEGIN;
+
+DROP INDEX IF EXISTS idx_podcasts_last_build_date;
+
+-- 提交事务
+COMMIT;
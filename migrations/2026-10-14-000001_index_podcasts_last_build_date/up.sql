@@ -0,0 +1,14 @@
+-- 迁移说明：
+-- 1. 为 podcasts.last_build_date 建立索引，支持按最后更新时间查找过期 feed
+-- 2. 修改 podcasts 表
+-- 3. 无数据迁移
+-- 4. NULL 视为过期，索引按 NULLS FIRST 排序与查询顺序一致
+
+-- 开始事务
+BEGIN;
+
+CREATE INDEX IF NOT EXISTS idx_podcasts_last_build_date
+    ON podcasts (last_build_date ASC NULLS FIRST);
+
+-- 提交事务
+COMMIT;
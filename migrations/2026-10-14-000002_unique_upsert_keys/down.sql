@@ -0,0 +1,12 @@
+-- 回滚说明：
+-- 1. 删除 upsert 冲突键的唯一索引
+-- 2. 不影响数据
+
+-- 开始事务
+BEGIN;
+
+DROP INDEX IF EXISTS uq_episodes_podcast_id_guid;
+DROP INDEX IF EXISTS uq_podcasts_rss_feed_url;
+
+-- 提交事务
+COMMIT;
@@ -0,0 +1,43 @@
+-- 迁移说明：
+-- 1. 统一 upsert 的冲突键：podcasts 按 rss_feed_url，episodes 按 (podcast_id, guid)
+-- 2. 修改 podcasts、episodes 表
+-- 3. 数据迁移：rss_feed_url 重复的播客保留 podcast_id 最小的一条，其余播客的剧集先改挂到保留的播客上再删除；
+--    随后删除 (podcast_id, guid) 重复的剧集，保留 episode_id 最小的一条
+-- 4. guid 为 NULL 的剧集不参与唯一约束
+
+-- 开始事务
+BEGIN;
+
+UPDATE episodes e
+SET podcast_id = keep.podcast_id
+FROM podcasts dup
+JOIN podcasts keep
+  ON keep.rss_feed_url = dup.rss_feed_url
+ AND keep.podcast_id < dup.podcast_id
+WHERE e.podcast_id = dup.podcast_id
+  AND NOT EXISTS (
+      SELECT 1
+      FROM podcasts older
+      WHERE older.rss_feed_url = keep.rss_feed_url
+        AND older.podcast_id < keep.podcast_id
+  );
+
+DELETE FROM podcasts p
+USING podcasts keep
+WHERE p.rss_feed_url = keep.rss_feed_url
+  AND p.podcast_id > keep.podcast_id;
+
+DELETE FROM episodes e
+USING episodes keep
+WHERE e.podcast_id = keep.podcast_id
+  AND e.guid = keep.guid
+  AND e.episode_id > keep.episode_id;
+
+CREATE UNIQUE INDEX IF NOT EXISTS uq_podcasts_rss_feed_url
+    ON podcasts (rss_feed_url);
+
+CREATE UNIQUE INDEX IF NOT EXISTS uq_episodes_podcast_id_guid
+    ON episodes (podcast_id, guid);
+
+-- 提交事务
+COMMIT;
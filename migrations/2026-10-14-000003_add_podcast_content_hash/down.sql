@@ -0,0 +1,11 @@
+-- 回滚说明：
+-- 1. 删除 podcasts.content_hash 字段
+-- 2. 已保存的内容哈希会丢失，下次爬取会重新解析所有 feed
+
+-- 开始事务
+BEGIN;
+
+ALTER TABLE podcasts DROP COLUMN IF EXISTS content_hash;
+
+-- 提交事务
+COMMIT;
@@ -0,0 +1,13 @@
+-- 迁移说明：
+-- 1. 为 podcasts 表添加 content_hash 字段，保存最近一次抓取内容的 SHA-256
+-- 2. 修改 podcasts 表
+-- 3. 无数据迁移，已有记录为 NULL，下次爬取时写入
+-- 4. 内容哈希未变化时跳过解析和写库
+
+-- 开始事务
+BEGIN;
+
+ALTER TABLE podcasts ADD COLUMN IF NOT EXISTS content_hash VARCHAR(64);
+
+-- 提交事务
+COMMIT;
@@ -0,0 +1,11 @@
+-- 回滚说明：
+-- 1. 删除 episodes.feed_position 字段
+-- 2. 已保存的 feed 顺序会丢失
+
+-- 开始事务
+BEGIN;
+
+ALTER TABLE episodes DROP COLUMN IF EXISTS feed_position;
+
+-- 提交事务
+COMMIT;
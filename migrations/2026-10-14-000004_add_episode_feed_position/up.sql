@@ -0,0 +1,13 @@
+-- 迁移说明：
+-- 1. 为 episodes 增加 feed_position 字段，记录剧集在 feed 中的顺序(从 0 开始)
+-- 2. 修改 episodes 表
+-- 3. 无数据迁移，已有记录为 NULL，下次爬取时写入
+-- 4. pub_date 缺失或相同时可按 feed_position 还原 feed 原始顺序
+
+-- 开始事务
+BEGIN;
+
+ALTER TABLE episodes ADD COLUMN IF NOT EXISTS feed_position INTEGER;
+
+-- 提交事务
+COMMIT;
@@ -0,0 +1,14 @@
+-- 回滚说明：
+-- 1. 删除 podcasts.funding_url、funding_text、persons 字段
+-- 2. 已保存的赞助信息与人员信息会丢失
+
+-- 开始事务
+BEGIN;
+
+ALTER TABLE podcasts
+    DROP COLUMN IF EXISTS funding_url,
+    DROP COLUMN IF EXISTS funding_text,
+    DROP COLUMN IF EXISTS persons;
+
+-- 提交事务
+COMMIT;
@@ -0,0 +1,16 @@
+-- 迁移说明：
+-- 1. 为 podcasts 增加 Podcasting 2.0 <podcast:funding> 的赞助地址与文字
+-- 2. 为 podcasts 增加 persons 字段，以 JSONB 数组保存 <podcast:person>(主持人、嘉宾等)
+-- 3. 修改 podcasts 表
+-- 4. 无数据迁移，新列允许为空，下次爬取时写入
+
+-- 开始事务
+BEGIN;
+
+ALTER TABLE podcasts
+    ADD COLUMN IF NOT EXISTS funding_url VARCHAR(1024),
+    ADD COLUMN IF NOT EXISTS funding_text TEXT,
+    ADD COLUMN IF NOT EXISTS persons JSONB;
+
+-- 提交事务
+COMMIT;
@@ -0,0 +1,11 @@
+-- 回滚说明：
+-- 1. 删除无 guid 剧集的 (podcast_id, enclosure_url) 唯一索引
+-- 2. 已删除的重复剧集不会恢复
+
+-- 开始事务
+BEGIN;
+
+DROP INDEX IF EXISTS uq_episodes_podcast_id_enclosure_url;
+
+-- 提交事务
+COMMIT;
@@ -0,0 +1,23 @@
+-- 迁移说明：
+-- 1. 没有 guid 的剧集按 (podcast_id, enclosure_url) 去重，作为 upsert 的后备冲突键
+-- 2. 修改 episodes 表
+-- 3. 数据迁移：删除 guid 为 NULL 且 (podcast_id, enclosure_url) 重复的剧集，保留 episode_id 最小的一条
+-- 4. 有 guid 的剧集仍按 (podcast_id, guid) 去重，不受此约束影响；enclosure_url 为 NULL 的剧集不参与唯一约束
+
+-- 开始事务
+BEGIN;
+
+DELETE FROM episodes e
+USING episodes keep
+WHERE e.guid IS NULL
+  AND keep.guid IS NULL
+  AND e.podcast_id = keep.podcast_id
+  AND e.enclosure_url = keep.enclosure_url
+  AND e.episode_id > keep.episode_id;
+
+CREATE UNIQUE INDEX IF NOT EXISTS uq_episodes_podcast_id_enclosure_url
+    ON episodes (podcast_id, enclosure_url)
+    WHERE guid IS NULL;
+
+-- 提交事务
+COMMIT;
@@ -0,0 +1,15 @@
+-- 回滚说明：
+-- 1. 删除 podcasts.extra 与 episodes.extra 字段
+-- 2. 已保存的未知命名空间元素会丢失
+
+-- 开始事务
+BEGIN;
+
+ALTER TABLE podcasts
+    DROP COLUMN IF EXISTS extra;
+
+ALTER TABLE episodes
+    DROP COLUMN IF EXISTS extra;
+
+-- 提交事务
+COMMIT;
@@ -0,0 +1,17 @@
+-- 迁移说明：
+-- 1. 为 podcasts 与 episodes 增加 extra 字段，以 JSONB 对象保存解析器未处理的命名空间元素
+--    (如 spotify:、acast:、googleplay:)，键为完整标签名
+-- 2. 修改 podcasts、episodes 表
+-- 3. 无数据迁移，新列允许为空，开启 CRAWLER_CAPTURE_UNKNOWN_TAGS 后下次爬取时写入
+
+-- 开始事务
+BEGIN;
+
+ALTER TABLE podcasts
+    ADD COLUMN IF NOT EXISTS extra JSONB;
+
+ALTER TABLE episodes
+    ADD COLUMN IF NOT EXISTS extra JSONB;
+
+-- 提交事务
+COMMIT;
@@ -0,0 +1,11 @@
+-- 回滚说明：
+-- 1. 删除 podcasts.last_fetched_at 字段
+-- 2. 已记录的抓取时间会丢失
+
+-- 开始事务
+BEGIN;
+
+ALTER TABLE podcasts DROP COLUMN IF EXISTS last_fetched_at;
+
+-- 提交事务
+COMMIT;
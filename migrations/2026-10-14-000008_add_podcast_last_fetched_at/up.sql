@@ -0,0 +1,13 @@
+-- 迁移说明：
+-- 1. 为 podcasts 表添加 last_fetched_at 字段，记录最近一次成功抓取并写库的时间
+--    (区别于 feed 自己声明的 last_build_date)
+-- 2. 修改 podcasts 表
+-- 3. 无数据迁移，已有记录为 NULL，下次爬取时写入
+
+-- 开始事务
+BEGIN;
+
+ALTER TABLE podcasts ADD COLUMN IF NOT EXISTS last_fetched_at TIMESTAMPTZ;
+
+-- 提交事务
+COMMIT;
@@ -0,0 +1,11 @@
+-- 回滚说明：
+-- 1. 删除 failed_feeds 表
+-- 2. 已记录的失败 feed 会丢失
+
+-- 开始事务
+BEGIN;
+
+DROP TABLE IF EXISTS failed_feeds;
+
+-- 提交事务
+COMMIT;
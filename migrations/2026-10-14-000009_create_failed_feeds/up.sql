@@ -0,0 +1,21 @@
+-- 迁移说明：
+-- 1. 新建 failed_feeds 表，记录用尽重试次数后仍抓取失败的 feed，便于排查和手动重试
+-- 2. 每个 url 一行，再次失败时更新 last_error、attempts 与 failed_at
+-- 3. 无数据迁移
+
+-- 开始事务
+BEGIN;
+
+CREATE TABLE IF NOT EXISTS failed_feeds (
+    id SERIAL PRIMARY KEY,
+    url VARCHAR(1024) NOT NULL UNIQUE,
+    last_error TEXT,
+    attempts INT4 NOT NULL,
+    failed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_failed_feeds_failed_at
+    ON failed_feeds (failed_at DESC);
+
+-- 提交事务
+COMMIT;
@@ -0,0 +1,11 @@
+-- 回滚说明：
+-- 1. 删除剧集全文搜索的 GIN 表达式索引
+-- 2. 全文搜索仍可执行但需全表扫描，原有字段不受影响
+
+-- 开始事务
+BEGIN;
+
+DROP INDEX IF EXISTS idx_episodes_search;
+
+-- 提交事务
+COMMIT;
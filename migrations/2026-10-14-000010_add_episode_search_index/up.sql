@@ -0,0 +1,19 @@
+-- 迁移说明：
+-- 1. 为 episodes 的 title、description、summary(权重依次为 A、B、C)建立全文搜索的 GIN 表达式索引，
+--    不新增列，表结构和 schema.rs 保持不变，写入路径无需改动
+-- 2. 使用 simple 文本搜索配置：feed 语言不一，不做词干提取，查询需使用相同配置
+-- 3. 查询必须使用与索引完全相同的表达式(见 EpisodeRepository::search)才能命中索引
+-- 4. 建索引期间 episodes 表的写入会被阻塞，数据量大时请在低峰期执行
+
+-- 开始事务
+BEGIN;
+
+CREATE INDEX IF NOT EXISTS idx_episodes_search
+    ON episodes USING GIN ((
+        setweight(to_tsvector('simple', coalesce(title, '')), 'A') ||
+        setweight(to_tsvector('simple', coalesce(description, '')), 'B') ||
+        setweight(to_tsvector('simple', coalesce(summary, '')), 'C')
+    ));
+
+-- 提交事务
+COMMIT;
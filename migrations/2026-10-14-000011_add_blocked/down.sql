@@ -0,0 +1,13 @@
+-- 回滚说明：
+-- 1. 删除 podcasts、episodes 的 blocked 字段
+-- 2. 已记录的 <itunes:block> 值会丢失，列表接口不再排除被 block 的播客和剧集
+
+-- 开始事务
+BEGIN;
+
+ALTER TABLE podcasts DROP COLUMN IF EXISTS blocked;
+
+ALTER TABLE episodes DROP COLUMN IF EXISTS blocked;
+
+-- 提交事务
+COMMIT;
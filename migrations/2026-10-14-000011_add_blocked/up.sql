@@ -0,0 +1,17 @@
+-- 迁移说明：
+-- 1. 为 podcasts 与 episodes 增加 blocked 字段，记录 <itunes:block> 的值
+-- 2. 修改 podcasts、episodes 表
+-- 3. 无数据迁移，已有记录为 NULL(视为未 block)，下次爬取时写入
+-- 4. 公开列表接口默认排除 blocked 为 true 的播客和剧集
+
+-- 开始事务
+BEGIN;
+
+ALTER TABLE podcasts
+    ADD COLUMN IF NOT EXISTS blocked BOOLEAN;
+
+ALTER TABLE episodes
+    ADD COLUMN IF NOT EXISTS blocked BOOLEAN;
+
+-- 提交事务
+COMMIT;
@@ -0,0 +1,11 @@
+-- 回滚说明：
+-- 1. 删除 podcasts.last_fetched_at 索引
+-- 2. 不影响数据
+
+-- 开始事务
+BEGIN;
+
+DROP INDEX IF EXISTS idx_podcasts_last_fetched_at;
+
+-- 提交事务
+COMMIT;
@@ -0,0 +1,14 @@
+-- 迁移说明：
+-- 1. 为 podcasts.last_fetched_at 建立索引，支持调度器按上次抓取时间查找需要重新爬取的 feed
+-- 2. 修改 podcasts 表
+-- 3. 无数据迁移
+-- 4. NULL 视为从未抓取，索引按 NULLS FIRST 排序与查询顺序一致
+
+-- 开始事务
+BEGIN;
+
+CREATE INDEX IF NOT EXISTS idx_podcasts_last_fetched_at
+    ON podcasts (last_fetched_at ASC NULLS FIRST);
+
+-- 提交事务
+COMMIT;
@@ -77,12 +77,19 @@ where
     T: Send + 'static + Clone,
     P: Crawler<T> + Clone + Send + Sync + 'static,
 {
-    run_batch_processor_with_inserter(crawler, urls, 1, |_: Vec<T>| Ok(())).await
+    let chunk_size = urls.len();
+    run_batch_processor_with_inserter(crawler, urls, chunk_size, 1, |_: Vec<T>| Ok(())).await
 }
 
+/// Crawl `urls` in chunks of `chunk_size` and insert the parsed results in groups of `insert_batch`
+///
+/// The two sizes are independent: parsed results are buffered across chunks,
+/// so small chunks can feed large inserts (and large chunks are split into
+/// several inserts). The last, partial group is inserted once every chunk is done.
 pub(crate) async fn run_batch_processor_with_inserter<T, P, F>(
     crawler: &P,
     urls: Vec<String>,
+    chunk_size: usize,
     insert_batch: usize,
     insert_fn: F,
 ) -> Result<Vec<TaskResult<T>>, AppError>
@@ -95,19 +102,31 @@ where
         return Ok(vec![]);
     }
 
+    let insert_batch = insert_batch.max(1);
     let mut results = vec![];
-    let distributed_urls = url_utils::distribute_urls(&urls, insert_batch)?;
+    let mut pending = Vec::new();
+    let distributed_urls = url_utils::distribute_urls_in_chunks(&urls, chunk_size)?;
 
     for (batch_index, batch_urls) in distributed_urls.iter().enumerate() {
+        // Inserts are grouped here rather than per chunk
         let batch_results = process_batch(
             crawler.clone(),
             batch_urls,
             insert_batch,
             batch_index,
             distributed_urls.len(),
-            insert_fn.clone(),
+            |_: Vec<T>| Ok(()),
         )
         .await?;
+        pending.extend(
+            batch_results
+                .iter()
+                .filter_map(|r| r.parsed_data().cloned()),
+        );
+        while pending.len() >= insert_batch {
+            let rest = pending.split_off(insert_batch);
+            insert_fn(std::mem::replace(&mut pending, rest))?;
+        }
         results.extend(
             batch_results
                 .into_iter()
@@ -115,6 +134,61 @@ where
                 .collect::<Vec<_>>(),
         );
     }
+    if !pending.is_empty() {
+        insert_fn(pending)?;
+    }
 
     Ok(results)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone)]
+    struct EchoCrawler;
+
+    #[async_trait]
+    impl Crawler<String> for EchoCrawler {
+        async fn fetch(&self, url: &str) -> Result<Vec<u8>, AppError> {
+            Ok(url.as_bytes().to_vec())
+        }
+
+        async fn parse(&self, content: Vec<u8>, _url: &str) -> Result<String, AppError> {
+            Ok(String::from_utf8(content).unwrap())
+        }
+
+        fn max_concurrent(&self) -> usize {
+            4
+        }
+    }
+
+    #[tokio::test]
+    async fn test_chunk_size_and_insert_batch_are_decoupled() {
+        let urls: Vec<String> = (0..10)
+            .map(|i| format!("https://example{}.com/feed", i))
+            .collect();
+        let inserts = Arc::new(Mutex::new(Vec::new()));
+        let recorded = inserts.clone();
+
+        // 10 URLs crawled 3 at a time, inserted 4 at a time
+        let results = run_batch_processor_with_inserter(
+            &EchoCrawler,
+            urls.clone(),
+            3,
+            4,
+            move |batch: Vec<String>| {
+                recorded.lock().unwrap().push(batch.len());
+                Ok(())
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(results.len(), urls.len());
+        assert!(results.iter().all(|result| result.success));
+        assert_eq!(*inserts.lock().unwrap(), vec![4, 4, 2]);
+    }
+}
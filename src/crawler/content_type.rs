@@ -0,0 +1,150 @@
+//! Early rejection of responses that are not feeds
+//!
+//! A feed URL whose domain has been parked, or that now answers with an HTML
+//! error page, is still downloaded with status 200. Parsing such a page fails
+//! with a confusing `InvalidXml` error, so responses labelled as HTML are
+//! rejected right after the download unless their body looks like a feed.
+
+use crate::infrastructure::config::CrawlerConfig;
+use crate::infrastructure::error::{AppResult, ParseError, ParseErrorKind};
+use url::Url;
+
+// Only the start of the body is inspected, enough to find the root element
+const SNIFF_BYTES: usize = 1024;
+
+const HTML_CONTENT_TYPES: [&str; 2] = ["text/html", "application/xhtml+xml"];
+
+/// Rejects HTML responses before they reach the feed parser
+///
+/// Hosts listed in `CrawlerConfig.lenient_content_type_hosts` (and their
+/// subdomains) are never checked, for servers whose feeds are mislabelled.
+#[derive(Debug, Clone, Default)]
+pub struct ContentTypeCheck {
+    lenient_hosts: Vec<String>,
+}
+
+impl ContentTypeCheck {
+    pub fn from_config(config: &CrawlerConfig) -> Self {
+        Self {
+            lenient_hosts: config
+                .lenient_content_type_hosts
+                .split(',')
+                .map(|host| host.trim().trim_start_matches('.').to_ascii_lowercase())
+                .filter(|host| !host.is_empty())
+                .collect(),
+        }
+    }
+
+    /// Fails with `ParseErrorKind::InvalidFormat` when `content_type` is HTML
+    /// and the body does not start like an RSS, Atom or JSON feed
+    ///
+    /// Responses without a `Content-Type` and any non-HTML type are accepted.
+    pub fn check(&self, url: &str, content_type: Option<&str>, content: &[u8]) -> AppResult<()> {
+        let Some(content_type) = content_type else {
+            return Ok(());
+        };
+        let mime = content_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+        if !HTML_CONTENT_TYPES.contains(&mime.as_str())
+            || looks_like_feed(content)
+            || self.is_lenient(url)
+        {
+            return Ok(());
+        }
+        Err(ParseError::new(
+            ParseErrorKind::InvalidFormat,
+            format!("expected feed, got {}", mime),
+            url,
+            None,
+        )
+        .into())
+    }
+
+    fn is_lenient(&self, url: &str) -> bool {
+        let Some(host) = Url::parse(url)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_ascii_lowercase))
+        else {
+            return false;
+        };
+        self.lenient_hosts.iter().any(|lenient| {
+            host == *lenient
+                || host
+                    .strip_suffix(lenient.as_str())
+                    .is_some_and(|prefix| prefix.ends_with('.'))
+        })
+    }
+}
+
+// Some servers label real feeds as text/html, so check the root element too
+fn looks_like_feed(content: &[u8]) -> bool {
+    let head = &content[..content.len().min(SNIFF_BYTES)];
+    let head = head.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(head);
+    if head.iter().find(|b| !b.is_ascii_whitespace()) == Some(&b'{') {
+        return true;
+    }
+    [b"<rss".as_slice(), b"<feed", b"<rdf:rdf"]
+        .iter()
+        .any(|needle| {
+            head.windows(needle.len())
+                .any(|window| window.eq_ignore_ascii_case(needle))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::error::AppError;
+
+    const URL: &str = "https://example.com/feed.xml";
+
+    #[test]
+    fn test_html_page_is_rejected() {
+        let page = b"<!DOCTYPE html><html><body>This domain is for sale</body></html>";
+        let check = ContentTypeCheck::default();
+        match check.check(URL, Some("text/html; charset=utf-8"), page) {
+            Err(AppError::Parse(e)) => {
+                assert_eq!(e.kind, ParseErrorKind::InvalidFormat);
+                assert!(e.to_string().contains("expected feed, got text/html"));
+            }
+            other => panic!("expected InvalidFormat, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_feeds_are_accepted() {
+        let rss = b"<?xml version=\"1.0\"?><rss version=\"2.0\"><channel/></rss>";
+        let check = ContentTypeCheck::default();
+        assert!(check.check(URL, Some("application/rss+xml"), rss).is_ok());
+        assert!(check.check(URL, Some("text/plain"), rss).is_ok());
+        assert!(check.check(URL, None, b"<html/>").is_ok());
+        // Feeds mislabelled as HTML are recognised by their root element
+        assert!(check.check(URL, Some("text/html"), rss).is_ok());
+        assert!(check
+            .check(URL, Some("text/html"), b" {\"items\": []}")
+            .is_ok());
+    }
+
+    #[test]
+    fn test_lenient_hosts_are_not_checked() {
+        let check = ContentTypeCheck::from_config(&CrawlerConfig {
+            lenient_content_type_hosts: "example.com, Feeds.Example.org".to_string(),
+            ..CrawlerConfig::default()
+        });
+        let page = b"<html><body>Not a feed</body></html>";
+        assert!(check.check(URL, Some("text/html"), page).is_ok());
+        assert!(check
+            .check("https://cdn.example.com/feed", Some("text/html"), page)
+            .is_ok());
+        assert!(check
+            .check("https://feeds.example.org/feed", Some("text/html"), page)
+            .is_ok());
+        assert!(check
+            .check("https://notexample.com/feed", Some("text/html"), page)
+            .is_err());
+    }
+}
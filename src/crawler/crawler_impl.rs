@@ -1,5 +1,7 @@
 use crate::crawler::batch_processor;
+use crate::crawler::content_type::ContentTypeCheck;
 use crate::crawler::traits::Crawler;
+use crate::infrastructure::config::CrawlerConfig;
 use crate::{
     infrastructure::error::{
         AppError, AppResult, ExternalErrorKind, NetworkError, NetworkErrorKind,
@@ -31,6 +33,8 @@ where
     failed_tasks: Arc<AtomicUsize>,
     successful_tasks: Arc<AtomicUsize>,
     max_retries: usize,
+    max_feed_bytes: u64,
+    content_type_check: ContentTypeCheck,
     total_time: Arc<Mutex<Duration>>,
     failure_reasons: Arc<Mutex<Vec<String>>>,
     total_tasks: Arc<AtomicUsize>,
@@ -52,6 +56,8 @@ where
             failed_tasks: Arc::clone(&self.failed_tasks),
             successful_tasks: Arc::clone(&self.successful_tasks),
             max_retries: self.max_retries,
+            max_feed_bytes: self.max_feed_bytes,
+            content_type_check: self.content_type_check.clone(),
             total_time: Arc::clone(&self.total_time),
             failure_reasons: Arc::clone(&self.failure_reasons),
             total_tasks: Arc::clone(&self.total_tasks),
@@ -65,13 +71,40 @@ where
     T: Send + Sync + 'static + Clone,
 {
     pub fn new(parser: P, max_concurrent: usize) -> Self {
-        let client = reqwest::Client::builder()
-            .timeout(Duration::from_secs(30))
+        let config = CrawlerConfig {
+            max_concurrent_tasks: max_concurrent,
+            ..CrawlerConfig::default()
+        };
+        Self::with_config(parser, &config)
+    }
+
+    /// Creates a crawler whose concurrency and HTTP timeouts come from `config`
+    pub fn with_config(parser: P, config: &CrawlerConfig) -> Self {
+        let max_concurrent = config.max_concurrent_tasks;
+        let mut headers = reqwest::header::HeaderMap::new();
+        if !config.accept_language.is_empty() {
+            if let Ok(language) = reqwest::header::HeaderValue::from_str(&config.accept_language) {
+                headers.insert(reqwest::header::ACCEPT_LANGUAGE, language);
+            }
+        }
+        let builder = reqwest::Client::builder()
+            .default_headers(headers)
+            .timeout(Duration::from_secs(config.request_timeout_seconds))
+            .connect_timeout(Duration::from_secs(config.connect_timeout_seconds))
+            // reqwest 的 limited(n) 把起始 URL 也计入，加一后恰好允许 max_redirects 次重定向
+            .redirect(reqwest::redirect::Policy::limited(
+                config.max_redirects.saturating_add(1),
+            ))
             .tcp_nodelay(true) // 禁用 Nagle 算法，减少延迟
-            .pool_max_idle_per_host(0) // 避免连接池闲置阻塞
-            .no_proxy() // 禁用代理
-            .build()
-            .expect("Failed to create HTTP client");
+            // 复用到同一主机（如 CDN）的连接，省去每次请求的 TCP/TLS 握手
+            .pool_max_idle_per_host(config.pool_max_idle_per_host)
+            .pool_idle_timeout(Duration::from_secs(config.pool_idle_timeout_seconds));
+        // 未配置代理时直连
+        let builder = match config.proxy().expect("Invalid crawler proxy URL") {
+            Some(proxy) => builder.proxy(proxy),
+            None => builder.no_proxy(),
+        };
+        let client = builder.build().expect("Failed to create HTTP client");
 
         Self {
             client,
@@ -79,6 +112,8 @@ where
             concurrent_limit: Arc::new(Semaphore::new(max_concurrent)),
             max_concurrent,
             max_retries: 3,
+            max_feed_bytes: config.max_feed_bytes,
+            content_type_check: ContentTypeCheck::from_config(config),
             retry_delay: Duration::from_secs(1),
             _marker: std::marker::PhantomData,
             failed_tasks: Arc::new(AtomicUsize::new(0)),
@@ -107,9 +142,10 @@ where
                     )
                 })?;
 
+                let started = Instant::now();
                 let response = self.client.get(url).send().await.map_err(|e| {
                     NetworkError::new(
-                        NetworkErrorKind::Connection,
+                        send_error_kind(&e),
                         format!("Failed to send request to {}: {}", url, e),
                         Some(self.retry_delay),
                         Some(Box::new(e)),
@@ -125,14 +161,12 @@ where
                     )));
                 }
 
-                response.bytes().await.map(|b| b.to_vec()).map_err(|e| {
-                    NetworkError::new(
-                        NetworkErrorKind::Connection,
-                        format!("Failed to read response body from {}: {}", url, e),
-                        Some(self.retry_delay),
-                        Some(Box::new(e)),
-                    )
-                })
+                let content_type = content_type_header(&response);
+                let bytes = read_body_limited(response, self.max_feed_bytes).await?;
+                crate::metrics::record_feed_fetch(url, bytes.len(), started.elapsed());
+                self.content_type_check
+                    .check(url, content_type.as_deref(), &bytes)?;
+                Ok::<_, AppError>(bytes)
             },
             max_attempts = 3,
             context = "Failed to fetch URL"
@@ -140,9 +174,10 @@ where
     }
 
     async fn fetch_once(&self, url: &str) -> Result<Vec<u8>, AppError> {
+        let started = Instant::now();
         let response = self.client.get(url).send().await.map_err(|e| {
             NetworkError::new(
-                NetworkErrorKind::Connection,
+                send_error_kind(&e),
                 format!("Failed to send request: {}", e),
                 Some(self.retry_delay),
                 Some(Box::new(e)),
@@ -158,23 +193,24 @@ where
             )));
         }
 
-        response.bytes().await.map(|b| b.to_vec()).map_err(|e| {
-            AppError::Network(NetworkError::new(
-                NetworkErrorKind::Connection,
-                format!("Failed to read response body: {}", e),
-                Some(self.retry_delay),
-                Some(Box::new(e)),
-            ))
-        })
+        let content_type = content_type_header(&response);
+        let bytes = read_body_limited(response, self.max_feed_bytes).await?;
+        crate::metrics::record_feed_fetch(url, bytes.len(), started.elapsed());
+        self.content_type_check
+            .check(url, content_type.as_deref(), &bytes)?;
+        Ok(bytes)
     }
 
     pub async fn crawl_batch(&self, urls: Vec<String>) -> Result<Vec<TaskResult<T>>, AppError> {
         batch_processor::run_batch_processor(self, urls).await
     }
 
+    /// Crawl `urls` `chunk_size` at a time, passing parsed results to `insert_fn`
+    /// in groups of `insert_batch` regardless of how the URLs were chunked
     pub async fn crawl_batch_with_inserter<F, D>(
         &mut self,
         urls: Vec<String>,
+        chunk_size: usize,
         insert_batch: usize,
         insert_fn: F,
     ) -> Result<Vec<TaskResult<T>>, AppError>
@@ -186,6 +222,7 @@ where
         batch_processor::run_batch_processor_with_inserter(
             self,
             urls,
+            chunk_size,
             insert_batch,
             move |batch: Vec<T>| {
                 let converted_batch: Vec<D> = batch.into_iter().map(|item| item.into()).collect();
@@ -252,7 +289,14 @@ where
     T: Send + Sync + 'static + Clone,
 {
     async fn fetch(&self, url: &str) -> Result<Vec<u8>, AppError> {
+        self.fetch_with_final_url(url)
+            .await
+            .map(|(content, _final_url)| content)
+    }
+
+    async fn fetch_with_final_url(&self, url: &str) -> Result<(Vec<u8>, String), AppError> {
         info!("Attempting to fetch URL: {}", url);
+        let started = Instant::now();
         let response = self
             .client
             .get(url)
@@ -262,17 +306,17 @@ where
             .await
             .map_err(|e| {
                 println!("Connection error: {}", e);
-                NetworkError::new(
-                    NetworkErrorKind::Connection,
-                    e.to_string(),
-                    None,
-                    Some(Box::new(e)),
-                )
+                NetworkError::new(send_error_kind(&e), e.to_string(), None, Some(Box::new(e)))
             })?;
 
         info!("Response status: {}", response.status());
         info!("Response headers: {:?}", response.headers());
 
+        let final_url = response.url().to_string();
+        if final_url != url {
+            info!("Feed {} redirected to {}", url, final_url);
+        }
+
         if !response.status().is_success() {
             let status = response.status();
             let headers = response.headers().clone();
@@ -292,22 +336,14 @@ where
             )));
         }
 
-        let bytes = response
-            .bytes()
-            .await
-            .map_err(|e| {
-                println!("Bytes read error: {}", e);
-                NetworkError::new(
-                    NetworkErrorKind::Connection,
-                    e.to_string(),
-                    None,
-                    Some(Box::new(e)),
-                )
-            })?
-            .to_vec();
+        let content_type = content_type_header(&response);
+        let bytes = read_body_limited(response, self.max_feed_bytes).await?;
+        crate::metrics::record_feed_fetch(url, bytes.len(), started.elapsed());
+        self.content_type_check
+            .check(&final_url, content_type.as_deref(), &bytes)?;
 
         info!("Bytes read successfully: {} bytes", bytes.len());
-        Ok(bytes)
+        Ok((bytes, final_url))
     }
 
     async fn parse(&self, content: Vec<u8>, url: &str) -> Result<T, AppError> {
@@ -331,3 +367,65 @@ where
         self.max_concurrent
     }
 }
+
+/// Classifies a reqwest send error, surfacing redirect loops as `TooManyRedirects`
+fn send_error_kind(error: &reqwest::Error) -> NetworkErrorKind {
+    if error.is_redirect() {
+        NetworkErrorKind::TooManyRedirects
+    } else {
+        NetworkErrorKind::Connection
+    }
+}
+
+/// Returns the response `Content-Type` header, if it is valid text
+fn content_type_header(response: &reqwest::Response) -> Option<String> {
+    response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+}
+
+/// Reads the response body, aborting once it grows beyond `max_bytes`
+///
+/// `Content-Length` is checked up front when present; the limit is then
+/// enforced chunk by chunk so servers that lie about (or omit) it are still capped.
+pub(crate) async fn read_body_limited(
+    mut response: reqwest::Response,
+    max_bytes: u64,
+) -> Result<Vec<u8>, AppError> {
+    let too_large = |size: u64| {
+        AppError::Network(NetworkError::new(
+            NetworkErrorKind::InvalidResponse,
+            format!(
+                "Feed body of {} bytes exceeds limit of {} bytes",
+                size, max_bytes
+            ),
+            None,
+            None,
+        ))
+    };
+
+    if let Some(length) = response.content_length() {
+        if length > max_bytes {
+            return Err(too_large(length));
+        }
+    }
+
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await.map_err(|e| {
+        NetworkError::new(
+            NetworkErrorKind::Connection,
+            format!("Failed to read response body: {}", e),
+            None,
+            Some(Box::new(e)),
+        )
+    })? {
+        let size = (body.len() + chunk.len()) as u64;
+        if size > max_bytes {
+            return Err(too_large(size));
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(body)
+}
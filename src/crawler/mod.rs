@@ -24,6 +24,7 @@
 //! ```
 
 mod batch_processor;
+pub mod content_type;
 mod crawler_impl;
 pub mod rate_limiter;
 pub mod rss;
@@ -35,6 +36,7 @@ use std::time::Duration;
 
 use crate::infrastructure::error::{AppError, AppResult, DomainError, DomainErrorKind};
 
+pub(crate) use crawler_impl::read_body_limited;
 pub use crawler_impl::HttpCrawler;
 pub use traits::{Crawler, FeedParser};
 
@@ -1,5 +1,3 @@
-use std::io::BufRead;
-
 use crate::crawler::traits::FeedParser;
 use crate::infrastructure::error::{
     parse::{ParseError, ParseErrorKind},
@@ -8,8 +6,10 @@ use crate::infrastructure::error::{
 use crate::infrastructure::persistence::models::{episode::NewEpisode, podcast::NewPodcast};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
+use encoding_rs::{Encoding, UTF_8};
 use quick_xml::events::{BytesEnd, BytesStart, BytesText, Event};
 use quick_xml::Reader;
+use std::borrow::Cow;
 use tracing::{debug, warn};
 
 /// Debugging macro for parser events.
@@ -146,6 +146,10 @@ pub struct ParserConfig {
     allow_empty_required: bool,
     /// 严格模式
     strict_mode: bool,
+    /// feed 未声明语言时，是否根据标题和描述检测语言
+    detect_language: bool,
+    /// 是否要求 enclosure 为音频（type 为 audio/*，或无 type 时 URL 为音频扩展名）
+    require_audio_enclosure: bool,
 }
 
 impl Default for ParserConfig {
@@ -155,10 +159,32 @@ impl Default for ParserConfig {
             validate_urls: true,
             allow_empty_required: false,
             strict_mode: true,
+            detect_language: false,
+            require_audio_enclosure: false,
         }
     }
 }
 
+impl ParserConfig {
+    /// 开启或关闭语言检测（仅在 feed 未声明 `<language>` 时生效）
+    pub fn with_language_detection(mut self, enabled: bool) -> Self {
+        self.detect_language = enabled;
+        self
+    }
+
+    /// 开启或关闭严格模式（严格模式下字段错误会使解析失败）
+    pub fn with_strict_mode(mut self, enabled: bool) -> Self {
+        self.strict_mode = enabled;
+        self
+    }
+
+    /// 开启或关闭 enclosure 音频校验
+    pub fn with_audio_enclosure_check(mut self, enabled: bool) -> Self {
+        self.require_audio_enclosure = enabled;
+        self
+    }
+}
+
 impl RssFeedParser {
     pub fn new() -> Self {
         Self {
@@ -170,12 +196,13 @@ impl RssFeedParser {
         Self { config }
     }
 
-    async fn parse_internal<R: BufRead>(
+    async fn parse_internal(
         &self,
-        content: R,
+        content: &[u8],
         url: &str,
     ) -> AppResult<(NewPodcast, Vec<NewEpisode>)> {
-        let mut reader = Reader::from_reader(content);
+        let content = transcode_to_utf8(strip_leading_noise(content));
+        let mut reader = Reader::from_reader(content.as_ref());
         // reader.trim_text(true);
         reader.expand_empty_elements(true); // 展开空标签
 
@@ -247,7 +274,18 @@ impl RssFeedParser {
         })?;
 
         state.validate_podcast(podcast).map_err(AppError::from)?;
-        let podcast = state.podcast.unwrap();
+        let mut podcast = state.podcast.unwrap();
+
+        // feed 声明的语言优先，仅在缺失时检测
+        let has_language = podcast.language.as_deref().is_some_and(|l| !l.is_empty());
+        if self.config.detect_language && !has_language {
+            let text = format!(
+                "{} {}",
+                podcast.title,
+                podcast.description.as_deref().unwrap_or_default()
+            );
+            podcast.language = detect_language(&text);
+        }
 
         debug!("Successfully parsed RSS feed:");
         debug!("- Podcast: {:#?}", podcast);
@@ -453,6 +491,9 @@ impl RssFeedParser {
             )
         })?;
 
+        let mut url = None;
+        let mut mime_type = None;
+        let mut length = None;
         let mut found_url = ",url not found";
         let mut error_msg = String::new();
         for (key, value) in attributes {
@@ -468,17 +509,17 @@ impl RssFeedParser {
                     };
                     self.check_url(&normalized_url, &state.context.url)?;
                     debug!("Found enclosure URL: {}", normalized_url);
-                    update_field_option(&mut episode.enclosure_url, &normalized_url);
+                    url = Some(normalized_url);
                     found_url = "";
                 }
                 "type" => {
                     debug!("Found enclosure type: {}", value);
-                    update_field_option(&mut episode.enclosure_type, &value);
+                    mime_type = Some(value);
                 }
                 "length" => {
-                    if let Ok(length) = value.parse() {
-                        debug!("Found enclosure length: {}", length);
-                        episode.enclosure_length = Some(length);
+                    if let Ok(parsed) = value.parse() {
+                        debug!("Found enclosure length: {}", parsed);
+                        length = Some(parsed);
                     } else {
                         debug!("Failed to parse enclosure length: {}", value);
                         if self.config.strict_mode {
@@ -501,6 +542,46 @@ impl RssFeedParser {
             )));
         }
 
+        if let Some(enclosure_url) = &url {
+            if self.config.require_audio_enclosure
+                && !is_audio_enclosure(mime_type.as_deref(), enclosure_url)
+            {
+                let message = format!(
+                    "Enclosure is not audio: {} (type: {})",
+                    enclosure_url,
+                    mime_type.as_deref().unwrap_or("none")
+                );
+                if self.config.strict_mode {
+                    return Err(AppError::from(ParseError::new(
+                        ParseErrorKind::InvalidFormat,
+                        message,
+                        &state.context.url,
+                        None,
+                    )));
+                }
+                // 非严格模式下忽略该 enclosure
+                warn!("{}", message);
+                return Ok(());
+            }
+        }
+
+        // 同一 item 可能有多个 enclosure（mp3 + m4a、视频 + 音频），只保留最合适的一个
+        let replace = match (&url, episode.enclosure_url.as_deref()) {
+            (Some(url), Some(current)) => {
+                enclosure_rank(mime_type.as_deref(), url)
+                    > enclosure_rank(episode.enclosure_type.as_deref(), current)
+            }
+            (Some(_), None) => true,
+            (None, current) => current.is_none(),
+        };
+        if replace {
+            if url.is_some() {
+                episode.enclosure_url = url;
+            }
+            episode.enclosure_type = mime_type;
+            episode.enclosure_length = length;
+        }
+
         Ok(())
     }
 
@@ -572,6 +653,19 @@ impl RssFeedParser {
                     update_field_option(&mut episode.episode_image_url, &url);
                 }
             }
+            "podcast:transcript" => {
+                if let Some(url) = get_attribute_value(&attributes, "url") {
+                    self.check_url(&url, feed_url)?;
+                    let transcript_type = get_attribute_value(&attributes, "type");
+                    // 优先使用 WebVTT 字幕，否则保留第一个
+                    let is_vtt = transcript_type.as_deref() == Some("text/vtt");
+                    let has_vtt = episode.transcript_type.as_deref() == Some("text/vtt");
+                    if episode.transcript_url.is_none() || (is_vtt && !has_vtt) {
+                        episode.transcript_url = Some(url);
+                        episode.transcript_type = transcript_type;
+                    }
+                }
+            }
             _ => {}
         }
         Ok(())
@@ -616,8 +710,7 @@ fn get_context_as_mut(
 #[async_trait]
 impl FeedParser<(NewPodcast, Vec<NewEpisode>)> for RssFeedParser {
     async fn parse(&self, content: &[u8], url: &str) -> AppResult<(NewPodcast, Vec<NewEpisode>)> {
-        let cursor = std::io::Cursor::new(content);
-        self.parse_internal(cursor, url).await
+        self.parse_internal(content, url).await
     }
 }
 
@@ -629,6 +722,90 @@ fn make_invalid_url_error(
     ParseError::new(ParseErrorKind::InvalidFormat, error_message, url, error).into()
 }
 
+const AUDIO_EXTENSIONS: &[&str] = &[
+    ".mp3", ".m4a", ".aac", ".ogg", ".oga", ".opus", ".wav", ".flac",
+];
+
+// type 以 audio/ 开头；没有 type 时根据 URL 扩展名判断
+fn is_audio_enclosure(mime_type: Option<&str>, url: &str) -> bool {
+    match mime_type.map(str::trim).filter(|t| !t.is_empty()) {
+        Some(mime_type) => mime_type.to_ascii_lowercase().starts_with("audio/"),
+        None => {
+            let path = url
+                .split(['?', '#'])
+                .next()
+                .unwrap_or(url)
+                .to_ascii_lowercase();
+            AUDIO_EXTENSIONS.iter().any(|ext| path.ends_with(ext))
+        }
+    }
+}
+
+// enclosure 优先级：mp3/m4a 音频 > 其他音频 > 未知类型 > 视频
+fn enclosure_rank(mime_type: Option<&str>, url: &str) -> u8 {
+    let mime_type = mime_type.unwrap_or_default().trim().to_ascii_lowercase();
+    let path = url
+        .split(['?', '#'])
+        .next()
+        .unwrap_or(url)
+        .to_ascii_lowercase();
+    match mime_type.as_str() {
+        "audio/mpeg" | "audio/mp3" | "audio/mp4" | "audio/m4a" | "audio/x-m4a" => 4,
+        t if t.starts_with("audio/") => 3,
+        t if t.starts_with("video/") => 1,
+        _ if path.ends_with(".mp3") || path.ends_with(".m4a") => 4,
+        _ => 2,
+    }
+}
+
+// 去掉 feed 开头的 UTF-8 BOM 和空白，避免 XML 声明前的杂质导致解析失败
+fn strip_leading_noise(content: &[u8]) -> &[u8] {
+    let trim_start = |bytes: &[u8]| -> usize {
+        bytes
+            .iter()
+            .position(|b| !b.is_ascii_whitespace())
+            .unwrap_or(bytes.len())
+    };
+    let content = &content[trim_start(content)..];
+    let content = content.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(content);
+    &content[trim_start(content)..]
+}
+
+// 读取 XML 声明中的 encoding，非 UTF-8 时转码为 UTF-8；没有声明时按 UTF-8 处理
+fn transcode_to_utf8(content: &[u8]) -> Cow<'_, [u8]> {
+    let Some(label) = declared_encoding(content) else {
+        return Cow::Borrowed(content);
+    };
+    match Encoding::for_label(label.as_bytes()) {
+        Some(encoding) if encoding != UTF_8 => {
+            let (decoded, _, had_errors) = encoding.decode(content);
+            if had_errors {
+                debug!("Feed contains bytes invalid for encoding {}", label);
+            }
+            Cow::Owned(decoded.into_owned().into_bytes())
+        }
+        Some(_) => Cow::Borrowed(content),
+        None => {
+            warn!("Unknown feed encoding {}, assuming UTF-8", label);
+            Cow::Borrowed(content)
+        }
+    }
+}
+
+// 从 `<?xml ... encoding="..."?>` 中取出编码名
+fn declared_encoding(content: &[u8]) -> Option<String> {
+    if !content.starts_with(b"<?xml") {
+        return None;
+    }
+    let end = content.windows(2).position(|w| w == b"?>")?;
+    let declaration = std::str::from_utf8(&content[..end]).ok()?;
+    let (_, rest) = declaration.split_once("encoding")?;
+    let rest = rest.trim_start().strip_prefix('=')?.trim_start();
+    let quote = rest.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+    let value = &rest[1..];
+    value.find(quote).map(|end| value[..end].to_string())
+}
+
 fn make_invalid_scope_error(url: &str, error_message: &str) -> AppError {
     ParseError::new(ParseErrorKind::Other, error_message, url, None).into()
 }
@@ -642,6 +819,17 @@ pub fn parse_bool(value: &str) -> Option<bool> {
     }
 }
 
+/// Detect the ISO 639-1 language code of a text, `None` if detection is unreliable
+pub fn detect_language(text: &str) -> Option<String> {
+    let info = whatlang::detect(text)?;
+    if !info.is_reliable() {
+        return None;
+    }
+    isolang::Language::from_639_3(info.lang().code())?
+        .to_639_1()
+        .map(str::to_string)
+}
+
 /// Clean HTML content
 pub fn clean_html(content: &str) -> String {
     use ammonia::clean;
@@ -9,13 +9,19 @@ where
     /// 抓取单个URL的内容
     async fn fetch(&self, url: &str) -> Result<Vec<u8>, AppError>;
 
+    /// 抓取单个URL的内容，并返回重定向后的最终URL
+    async fn fetch_with_final_url(&self, url: &str) -> Result<(Vec<u8>, String), AppError> {
+        let content = self.fetch(url).await?;
+        Ok((content, url.to_string()))
+    }
+
     /// 解析内容
     async fn parse(&self, content: Vec<u8>, url: &str) -> Result<T, AppError>;
 
-    /// 抓取并解析内容
+    /// 抓取并解析内容（使用重定向后的最终URL解析）
     async fn fetch_and_parse(&self, url: &str) -> Result<T, AppError> {
-        let content = self.fetch(url).await?;
-        self.parse(content, url).await
+        let (content, final_url) = self.fetch_with_final_url(url).await?;
+        self.parse(content, &final_url).await
     }
 
     /// 获取最大并发数
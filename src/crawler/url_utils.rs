@@ -95,6 +95,43 @@ pub fn distribute_urls(urls: &[String], num_threads: usize) -> Result<Vec<Vec<St
     Ok(thread_urls)
 }
 
+/// Distribute URLs into groups of at most `chunk_size` URLs
+///
+/// Same spreading as [`distribute_urls`], sized by URLs per group instead of
+/// number of groups. A `chunk_size` of 0 is treated as 1.
+pub fn distribute_urls_in_chunks(
+    urls: &[String],
+    chunk_size: usize,
+) -> Result<Vec<Vec<String>>, AppError> {
+    distribute_urls(urls, urls.len().div_ceil(chunk_size.max(1)))
+}
+
+/// Reduce a URL to its registrable host (e.g. `cdn.feeds.example.co.uk` -> `example.co.uk`)
+///
+/// Used as a bounded metric label; unparsable URLs map to `None`.
+pub fn registrable_host(url: &str) -> Option<String> {
+    let parsed = Url::parse(url).ok()?;
+    let host = parsed
+        .host_str()?
+        .trim_end_matches('.')
+        .to_ascii_lowercase();
+    if parsed.domain().is_none() {
+        // IP 地址没有可注册域名，原样返回
+        return Some(host);
+    }
+
+    let labels: Vec<&str> = host.split('.').collect();
+    // 形如 co.uk / com.cn 的二级后缀需要多保留一段
+    let is_second_level_suffix = labels.len() >= 3
+        && labels[labels.len() - 1].len() == 2
+        && matches!(
+            labels[labels.len() - 2],
+            "co" | "com" | "net" | "org" | "gov" | "edu" | "ac"
+        );
+    let keep = if is_second_level_suffix { 3 } else { 2 };
+    Some(labels[labels.len().saturating_sub(keep)..].join("."))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -125,4 +162,34 @@ mod tests {
             urls.len()
         );
     }
+
+    #[test]
+    fn test_url_distribution_in_chunks() {
+        let urls: Vec<String> = (0..10)
+            .map(|i| format!("https://example{}.com/feed", i))
+            .collect();
+
+        let chunks = distribute_urls_in_chunks(&urls, 3).unwrap();
+        assert_eq!(chunks.len(), 4);
+        assert!(chunks.iter().all(|chunk| chunk.len() <= 3));
+        assert_eq!(chunks.iter().map(Vec::len).sum::<usize>(), urls.len());
+        assert_eq!(distribute_urls_in_chunks(&urls, 50).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_registrable_host() {
+        assert_eq!(
+            registrable_host("https://cdn.feeds.example.com/a/feed.xml").as_deref(),
+            Some("example.com")
+        );
+        assert_eq!(
+            registrable_host("https://podcasts.example.co.uk/rss").as_deref(),
+            Some("example.co.uk")
+        );
+        assert_eq!(
+            registrable_host("http://127.0.0.1:8080/feed").as_deref(),
+            Some("127.0.0.1")
+        );
+        assert_eq!(registrable_host("not a url"), None);
+    }
 }
@@ -1,50 +1,69 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use futures::future::join_all;
 use rand::Rng;
 use serde_json::json;
-use tokio::sync::broadcast;
+use tokio_util::sync::CancellationToken;
 
-use super::{task::Task, task_management_system::TaskWorkerMaps, worker::Worker};
+use super::{
+    priority_queue::PriorityQueue,
+    task::{Task, TaskOptions, PRIORITY_NORMAL},
+    task_management_system::TaskWorkerMaps,
+    worker::Worker,
+};
+use crate::infrastructure::config::DispatchStrategy;
+use crate::infrastructure::error::{AppResult, DomainError, DomainErrorKind};
 
 /// Internal Distributor structure
 pub(crate) struct Distributor {
     task_id_counter: u64,
-    task_tx: broadcast::Sender<Task>,
+    priority_queue: Arc<PriorityQueue>,
     task_worker_maps: Arc<TaskWorkerMaps>,
     current_index: usize,
+    default_max_retries: u32,
+    dispatch_strategy: DispatchStrategy,
+    cancellation_token: CancellationToken,
 }
 
 impl Distributor {
     pub(crate) fn new(
-        task_tx: broadcast::Sender<Task>,
+        priority_queue: Arc<PriorityQueue>,
         task_worker_maps: Arc<TaskWorkerMaps>,
+        default_max_retries: u32,
+        dispatch_strategy: DispatchStrategy,
+        cancellation_token: CancellationToken,
     ) -> Self {
         tracing::info!("🏭 Distributor: Creating new instance");
         Self {
             task_id_counter: 0,
-            task_tx,
+            priority_queue,
             task_worker_maps,
             current_index: 0,
+            default_max_retries,
+            dispatch_strategy,
+            cancellation_token,
         }
     }
 
-    // 选择工作线程 ID，使用轮询方式
-    fn select_worker(&mut self, workers: &[Worker]) -> usize {
-        // 获取当前工作线程的 ID
-        let worker_id = workers[self.current_index].id;
-
-        // 更新索引到下一个工作线程，循环到第一个
-        self.current_index = (self.current_index + 1) % workers.len();
-
-        worker_id
+    // 按配置的分发策略选择工作线程 ID
+    async fn select_worker(&mut self, workers: &[Worker], url: &str) -> usize {
+        let worker_ids: Vec<usize> = workers.iter().map(|worker| worker.id).collect();
+        match self.dispatch_strategy {
+            DispatchStrategy::RoundRobin => round_robin(&mut self.current_index, &worker_ids),
+            DispatchStrategy::LeastLoaded => {
+                let loads = self.task_worker_maps.count_in_flight_tasks_by_worker();
+                least_loaded(&worker_ids, &loads)
+            }
+            DispatchStrategy::Similarity => workers[self.find_best_worker(workers, url).await].id,
+        }
     }
 
     async fn find_best_worker(&self, workers: &[Worker], url: &str) -> usize {
         tracing::info!("🔍 Distributor: Finding best worker for URL '{}'", url);
         // 设置探索概率 ε
         let epsilon = 0.4; // 10% 的概率进行随机选择
-        let mut rng = rand::thread_rng();
+
         // Get both similarity scores and queue lengths
         let worker_metrics: Vec<(f64, usize)> = join_all(workers.iter().map(|worker| async move {
             let similarity = worker.calculate_similarity(url).await;
@@ -56,6 +75,8 @@ impl Distributor {
             (similarity, queue_length)
         }))
         .await;
+        // ThreadRng 不是 Send，在 await 之后再创建
+        let mut rng = rand::thread_rng();
         // 生成随机数以决定是否进行随机选择
         let random_number: f64 = rng.gen();
         // 生成 0 到 1 之间的随机数
@@ -91,15 +112,42 @@ impl Distributor {
         best_worker_index
     }
 
-    pub async fn create_task(&mut self, url: &str, workers: &mut [Worker]) -> Result<(), String> {
+    pub async fn create_task(
+        &mut self,
+        url: &str,
+        options: TaskOptions,
+        workers: &mut [Worker],
+    ) -> AppResult<u64> {
         tracing::info!("📦 Distributor: Creating task for URL '{}'", url);
 
+        // Reject URLs already being crawled to avoid racing upserts
+        if let Some(existing_id) = self.task_worker_maps.find_in_flight_task(url).await {
+            tracing::warn!(
+                "♻️ Distributor: URL '{}' is already in flight as task {}",
+                url,
+                existing_id
+            );
+            return Err(DomainError::new(
+                DomainErrorKind::Duplicate,
+                format!("Feed is already being crawled by task {}", existing_id),
+                Some(url.to_string()),
+                None,
+            )
+            .into());
+        }
+
         // Create a new task
         self.task_id_counter += 1;
-        let mut new_task = Task::new(self.task_id_counter, url.to_string(), 0);
+        let max_retries = options.max_retries.unwrap_or(self.default_max_retries);
+        let mut new_task = Task::new(self.task_id_counter, url.to_string(), max_retries);
+        new_task.priority = options.priority.unwrap_or(PRIORITY_NORMAL);
+        new_task.auth = options.auth;
+        new_task.visited_feed_urls = options.visited_feed_urls;
+        new_task.span = tracing::info_span!("task", task_id = new_task.id, url = %url);
+        // Child of the system token, so shutdown also cancels the task
+        new_task.cancellation_token = self.cancellation_token.child_token();
         new_task.add_stage("distribution");
-        // let best_worker_id = self.find_best_worker(workers, url).await;
-        let best_worker_id = self.select_worker(workers);
+        let best_worker_id = self.select_worker(workers, url).await;
 
         // Only complete stage if still in progress
         if new_task.get_task_status() == super::task::StageStatus::InProgress {
@@ -113,21 +161,57 @@ impl Distributor {
             .await;
 
         tracing::info!(
-            "🎯 Distributor: Assigned task {} to worker {}",
+            "🎯 Distributor: Assigned task {} to worker {} (priority {})",
             new_task.get_id(),
-            best_worker_id
+            best_worker_id,
+            new_task.priority
         );
 
-        match self.task_tx.send(new_task.clone()) {
-            Ok(_) => Ok(()),
-            Err(e) => {
-                new_task.fail_stage(e.to_string());
-                self.task_worker_maps
-                    .insert_task(new_task.get_id(), new_task)
-                    .await;
-                tracing::error!("❌ Distributor: Failed to send task: {}", e);
-                Err(e.to_string())
-            }
+        // 先进入优先级队列，由调度器在 worker 空闲时按优先级转发
+        let task_id = new_task.get_id();
+        self.priority_queue.push(new_task);
+        Ok(task_id)
+    }
+}
+
+// 轮询：返回 index 处的工作线程，并把 index 移到下一个，循环到第一个
+fn round_robin(index: &mut usize, worker_ids: &[usize]) -> usize {
+    let worker_id = worker_ids[*index % worker_ids.len()];
+    *index = (*index + 1) % worker_ids.len();
+    worker_id
+}
+
+// 进行中任务最少的工作线程，数量相同时取靠前的
+fn least_loaded(worker_ids: &[usize], loads: &HashMap<usize, usize>) -> usize {
+    worker_ids
+        .iter()
+        .copied()
+        .min_by_key(|id| loads.get(id).copied().unwrap_or(0))
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_robin_balances_workers() {
+        let worker_ids = [0, 1, 2];
+        let mut index = 0;
+        let mut counts = HashMap::new();
+        for _ in 0..30 {
+            *counts
+                .entry(round_robin(&mut index, &worker_ids))
+                .or_insert(0) += 1;
         }
+        assert_eq!(counts, HashMap::from([(0, 10), (1, 10), (2, 10)]));
+    }
+
+    #[test]
+    fn test_least_loaded_picks_fewest_in_flight() {
+        let loads = HashMap::from([(0, 3), (1, 1), (2, 1)]);
+        assert_eq!(least_loaded(&[0, 1, 2], &loads), 1);
+        // 没有进行中任务的工作线程负载为 0
+        assert_eq!(least_loaded(&[0, 1, 2, 3], &loads), 3);
     }
 }
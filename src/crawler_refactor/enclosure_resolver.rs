@@ -0,0 +1,249 @@
+use crate::infrastructure::config::CrawlerConfig;
+use crate::infrastructure::persistence::models::NewEpisode;
+use futures::stream::{self, StreamExt};
+use reqwest::header::{CONTENT_LENGTH, CONTENT_RANGE, RANGE};
+use reqwest::{Client, Response, StatusCode};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Semaphore;
+use tracing::debug;
+
+/// Fills missing `enclosure_length` values from the enclosure's HTTP headers
+///
+/// Sends a HEAD request to each enclosure and falls back to a ranged GET
+/// (`Range: bytes=0-0`) for hosts that reject HEAD or omit `Content-Length`.
+/// Clones share one limit of `CrawlerConfig.enclosure_resolve_concurrency`
+/// requests in flight, independent of the feed fetch limit, so a feed with
+/// thousands of episodes cannot hold up feed crawling.
+#[derive(Clone, Debug)]
+pub struct EnclosureLengthResolver {
+    client: Client,
+    concurrency: usize,
+    // 所有 feed 共用，限制同时进行的探测请求数量
+    permits: Arc<Semaphore>,
+}
+
+impl EnclosureLengthResolver {
+    /// Creates a resolver from the crawler config, `None` unless `resolve_enclosure_length` is set
+    pub fn from_config(config: &CrawlerConfig) -> Option<Self> {
+        if !config.resolve_enclosure_length {
+            return None;
+        }
+        let builder = Client::builder()
+            .timeout(Duration::from_secs(config.request_timeout_seconds))
+            .connect_timeout(Duration::from_secs(config.connect_timeout_seconds))
+            .user_agent(config.user_agent.clone());
+        let builder = match config.proxy().expect("Invalid crawler proxy URL") {
+            Some(proxy) => builder.proxy(proxy),
+            None => builder.no_proxy(),
+        };
+        let client = builder
+            .build()
+            .expect("Failed to create enclosure HTTP client");
+        Some(Self {
+            client,
+            concurrency: config.enclosure_resolve_concurrency,
+            permits: Arc::new(Semaphore::new(config.enclosure_resolve_concurrency)),
+        })
+    }
+
+    /// Resolves the length of every enclosure that has a URL but no length
+    ///
+    /// Enclosures that cannot be resolved keep their `None` length. Returns the
+    /// number of episodes that were filled in.
+    pub async fn resolve(&self, episodes: &mut [NewEpisode]) -> usize {
+        let pending: Vec<(usize, String)> = episodes
+            .iter()
+            .enumerate()
+            .filter(|(_, episode)| episode.enclosure_length.is_none())
+            .filter_map(|(index, episode)| episode.enclosure_url.clone().map(|url| (index, url)))
+            .collect();
+
+        let resolved: Vec<(usize, Option<i64>)> = stream::iter(pending)
+            .map(|(index, url)| async move { (index, self.content_length(&url).await) })
+            .buffer_unordered(self.concurrency)
+            .collect()
+            .await;
+
+        let mut filled = 0;
+        for (index, length) in resolved {
+            if let Some(length) = length {
+                episodes[index].enclosure_length = Some(length);
+                filled += 1;
+            }
+        }
+        filled
+    }
+
+    async fn content_length(&self, url: &str) -> Option<i64> {
+        // 信号量不会被关闭
+        let _permit = self.permits.acquire().await.ok()?;
+        match self.client.head(url).send().await {
+            Ok(response) if response.status().is_success() => {
+                if let Some(length) = header_length(&response) {
+                    return Some(length);
+                }
+            }
+            Ok(response) => debug!("HEAD {} returned {}", url, response.status()),
+            Err(e) => debug!("HEAD {} failed: {}", url, e),
+        }
+
+        // 不支持 HEAD 的服务器：只请求第一个字节，从 Content-Range 读取总大小
+        let response = match self.client.get(url).header(RANGE, "bytes=0-0").send().await {
+            Ok(response) => response,
+            Err(e) => {
+                debug!("Ranged GET {} failed: {}", url, e);
+                return None;
+            }
+        };
+        match response.status() {
+            StatusCode::PARTIAL_CONTENT => response
+                .headers()
+                .get(CONTENT_RANGE)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.rsplit('/').next())
+                .and_then(|total| total.trim().parse().ok()),
+            status if status.is_success() => header_length(&response),
+            status => {
+                debug!("Ranged GET {} returned {}", url, status);
+                None
+            }
+        }
+    }
+}
+
+// 读取 Content-Length 头（HEAD 响应没有 body，不能用 Response::content_length）
+fn header_length(response: &Response) -> Option<i64> {
+    response
+        .headers()
+        .get(CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse().ok())
+        .filter(|length| *length > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn episode(url: Option<String>, length: Option<i64>) -> NewEpisode {
+        NewEpisode {
+            title: "Episode".to_string(),
+            enclosure_url: url,
+            enclosure_length: length,
+            ..NewEpisode::default()
+        }
+    }
+
+    fn resolver() -> EnclosureLengthResolver {
+        resolver_with_concurrency(CrawlerConfig::default().enclosure_resolve_concurrency)
+    }
+
+    fn resolver_with_concurrency(concurrency: usize) -> EnclosureLengthResolver {
+        EnclosureLengthResolver::from_config(&CrawlerConfig {
+            resolve_enclosure_length: true,
+            enclosure_resolve_concurrency: concurrency,
+            ..CrawlerConfig::default()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_disabled_by_default() {
+        assert!(EnclosureLengthResolver::from_config(&CrawlerConfig::default()).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_resolve_missing_lengths() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/head.mp3"))
+            .respond_with(ResponseTemplate::new(200).append_header("Content-Length", "12345"))
+            .mount(&mock_server)
+            .await;
+        // 拒绝 HEAD 的服务器，回退到 Range 请求
+        Mock::given(method("HEAD"))
+            .and(path("/ranged.mp3"))
+            .respond_with(ResponseTemplate::new(405))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/ranged.mp3"))
+            .and(header("Range", "bytes=0-0"))
+            .respond_with(
+                ResponseTemplate::new(206)
+                    .append_header("Content-Range", "bytes 0-0/67890")
+                    .set_body_bytes(vec![0u8]),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let mut episodes = vec![
+            episode(Some(format!("{}/head.mp3", mock_server.uri())), None),
+            episode(Some(format!("{}/ranged.mp3", mock_server.uri())), None),
+            episode(Some(format!("{}/missing.mp3", mock_server.uri())), None),
+            episode(Some(format!("{}/known.mp3", mock_server.uri())), Some(42)),
+            episode(None, None),
+        ];
+
+        assert_eq!(resolver().resolve(&mut episodes).await, 2);
+        let lengths: Vec<Option<i64>> = episodes.iter().map(|e| e.enclosure_length).collect();
+        assert_eq!(
+            lengths,
+            vec![Some(12345), Some(67890), None, Some(42), None]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_concurrency_shared_across_feeds() {
+        let mock_server = MockServer::start().await;
+        let delay = Duration::from_millis(100);
+        Mock::given(method("HEAD"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .append_header("Content-Length", "1000")
+                    .set_delay(delay),
+            )
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("HEAD"))
+            .and(path("/broken.mp3"))
+            .respond_with(ResponseTemplate::new(500))
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+        let feed = |name: &str| {
+            (0..2)
+                .map(|n| {
+                    episode(
+                        Some(format!("{}/{}-{}.mp3", mock_server.uri(), name, n)),
+                        None,
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        let (mut first, mut second) = (feed("first"), feed("second"));
+        second.push(episode(
+            Some(format!("{}/broken.mp3", mock_server.uri())),
+            None,
+        ));
+
+        // 两个 feed 共用一个许可，四个请求只能依次进行
+        let resolver = resolver_with_concurrency(1);
+        let other = resolver.clone();
+        let started = std::time::Instant::now();
+        let (filled_first, filled_second) =
+            tokio::join!(resolver.resolve(&mut first), other.resolve(&mut second));
+        assert!(started.elapsed() >= delay * 4);
+
+        // 无法获取长度的 enclosure 保持为空，不影响其他剧集
+        assert_eq!((filled_first, filled_second), (2, 2));
+        assert_eq!(second[2].enclosure_length, None);
+        assert!(first
+            .iter()
+            .chain(&second[..2])
+            .all(|episode| episode.enclosure_length == Some(1000)));
+    }
+}
@@ -10,6 +10,60 @@ use tokio::time::timeout;
 use tracing::{error, info, warn};
 
 use super::task::Task;
+use crate::infrastructure::config::CrawlerConfig;
+
+/// Batch sizing and concurrency settings for [`BatchInserter`]
+///
+/// The collector starts at `target_batch_size` and adapts between
+/// `min_batch_size` and `max_batch_size`: it doubles after collecting a full
+/// batch and halves when a batch comes back less than half full.
+/// `batch_timeout` still bounds how long a partial batch waits before flushing.
+#[derive(Clone, Debug)]
+pub struct BatchInserterConfig {
+    pub min_batch_size: usize,
+    pub max_batch_size: usize,
+    pub target_batch_size: usize,
+    pub max_concurrent_inserts: usize,
+    pub batch_timeout: Duration,
+}
+
+impl Default for BatchInserterConfig {
+    fn default() -> Self {
+        Self {
+            min_batch_size: 1,
+            max_batch_size: 50,
+            target_batch_size: 3,
+            max_concurrent_inserts: 10,
+            batch_timeout: Duration::from_secs(5),
+        }
+    }
+}
+
+impl BatchInserterConfig {
+    /// 从爬虫配置读取初始批次大小、并发数和超时，批次上限不小于初始大小
+    pub fn from_config(config: &CrawlerConfig) -> Self {
+        let defaults = Self::default();
+        Self {
+            target_batch_size: config.insert_batch_size,
+            max_batch_size: defaults.max_batch_size.max(config.insert_batch_size),
+            max_concurrent_inserts: config.max_concurrent_inserts,
+            batch_timeout: Duration::from_millis(config.insert_batch_timeout_ms),
+            ..defaults
+        }
+    }
+
+    // 根据上一批次的填充情况计算下一批次大小
+    fn next_batch_size(&self, current: usize, collected: usize) -> usize {
+        let next = if collected >= current {
+            current.saturating_mul(2)
+        } else if collected * 2 < current {
+            current / 2
+        } else {
+            current
+        };
+        next.clamp(self.min_batch_size.max(1), self.max_batch_size.max(1))
+    }
+}
 
 #[derive(Clone, Debug)]
 pub struct BatchInserter {
@@ -23,12 +77,7 @@ pub struct BatchInserter {
 }
 
 impl BatchInserter {
-    pub fn new<F, Fut>(
-        batch_size: usize,
-        max_concurrent_inserts: usize,
-        insert_fn: F,
-        batch_timeout: Duration,
-    ) -> Self
+    pub fn new<F, Fut>(config: BatchInserterConfig, insert_fn: F) -> Self
     where
         F: Fn(Vec<Task>) -> Fut + Send + Sync + 'static + Clone,
         Fut: Future<Output = Result<(), String>> + Send,
@@ -37,15 +86,14 @@ impl BatchInserter {
         let rx = Arc::new(Mutex::new(rx));
 
         let processed_count = Arc::new(AtomicUsize::new(0));
-        let semaphore = Arc::new(Semaphore::new(max_concurrent_inserts));
+        let semaphore = Arc::new(Semaphore::new(config.max_concurrent_inserts));
         let active_workers = Arc::new(AtomicUsize::new(0));
         let (monitor_shutdown_tx, monitor_shutdown_rx) = mpsc::channel(1);
         let monitor_shutdown = Arc::new(Mutex::new(Some(monitor_shutdown_tx)));
 
         let monitor_handle = Self::spawn_monitor(
             rx.clone(),
-            batch_size,
-            batch_timeout,
+            config,
             insert_fn,
             processed_count.clone(),
             semaphore.clone(),
@@ -66,8 +114,7 @@ impl BatchInserter {
 
     fn spawn_monitor<F, Fut>(
         rx: Arc<Mutex<mpsc::Receiver<Task>>>,
-        batch_size: usize,
-        batch_timeout: Duration,
+        config: BatchInserterConfig,
         insert_fn: F,
         processed_count: Arc<AtomicUsize>,
         semaphore: Arc<Semaphore>,
@@ -79,12 +126,15 @@ impl BatchInserter {
         Fut: Future<Output = Result<(), String>> + Send,
     {
         tokio::spawn(async move {
+            let mut batch_size = config
+                .target_batch_size
+                .clamp(config.min_batch_size.max(1), config.max_batch_size.max(1));
             loop {
                 // Check for shutdown signal
                 if monitor_shutdown_rx.try_recv().is_ok() {
-                    info!(
-                        "Monitor received shutdown signal, waiting for active workers to complete"
-                    );
+                    info!("Monitor received shutdown signal, draining pending tasks");
+                    Self::drain_pending(&rx, batch_size, &insert_fn, &processed_count).await;
+                    info!("Monitor waiting for active workers to complete");
                     while active_workers.load(Ordering::SeqCst) > 0 {
                         tokio::time::sleep(Duration::from_millis(100)).await;
                     }
@@ -92,27 +142,23 @@ impl BatchInserter {
                     return Ok(());
                 }
 
-                let batch_result =
-                    timeout(batch_timeout, Self::collect_batch(rx.clone(), batch_size)).await;
-
-                let batch = match batch_result {
-                    Ok(batch) => {
-                        info!("Batch collection completed with {} items", batch.len());
-                        batch
-                    }
-                    Err(_) => {
-                        // warn!("Batch timeout reached with no items.");
-                        continue;
-                    }
-                };
+                let batch = Self::collect_batch(rx.clone(), batch_size, config.batch_timeout).await;
 
                 if batch.is_empty() {
-                    info!("No items to process; continue waiting data.");
-                    continue; // channel is closed or shutdown signal received
+                    continue; // batch timeout reached with no items, or the channel is closed
                 }
 
                 info!("Collected batch of {} items.", batch.len());
 
+                let next_batch_size = config.next_batch_size(batch_size, batch.len());
+                if next_batch_size != batch_size {
+                    info!(
+                        "Adjusting batch size from {} to {}",
+                        batch_size, next_batch_size
+                    );
+                    batch_size = next_batch_size;
+                }
+
                 let semaphore = semaphore.clone();
                 let processed_count = processed_count.clone();
                 let active_workers = active_workers.clone();
@@ -134,12 +180,55 @@ impl BatchInserter {
         })
     }
 
-    async fn collect_batch(rx: Arc<Mutex<mpsc::Receiver<Task>>>, batch_size: usize) -> Vec<Task> {
+    // 关闭时取出通道中剩余的任务，按批次直接插入，避免已解析的数据丢失
+    async fn drain_pending<F, Fut>(
+        rx: &Arc<Mutex<mpsc::Receiver<Task>>>,
+        batch_size: usize,
+        insert_fn: &F,
+        processed_count: &Arc<AtomicUsize>,
+    ) where
+        F: Fn(Vec<Task>) -> Fut + Send + Sync + 'static + Clone,
+        Fut: Future<Output = Result<(), String>> + Send,
+    {
+        let mut pending = Vec::new();
+        {
+            let mut rx = rx.lock().await;
+            while let Ok(task) = rx.try_recv() {
+                pending.push(task);
+            }
+        }
+        if pending.is_empty() {
+            return;
+        }
+
+        info!("Flushing {} pending tasks before shutdown", pending.len());
+        while !pending.is_empty() {
+            let rest = pending.split_off(batch_size.min(pending.len()));
+            let batch = std::mem::replace(&mut pending, rest);
+            if let Err(e) = insert_fn(batch).await {
+                error!("Error processing batch during shutdown: {:?}", e);
+            } else {
+                processed_count.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    // 收集一个批次：批次满、相邻任务间隔超过 500ms 或到达 batch_timeout 时返回已收到的任务
+    async fn collect_batch(
+        rx: Arc<Mutex<mpsc::Receiver<Task>>>,
+        batch_size: usize,
+        batch_timeout: Duration,
+    ) -> Vec<Task> {
+        let deadline = tokio::time::Instant::now() + batch_timeout;
         let mut batch = Vec::with_capacity(batch_size);
         let mut rx = rx.lock().await;
 
         while batch.len() < batch_size {
-            match timeout(Duration::from_millis(500), rx.recv()).await {
+            let remaining = deadline.saturating_duration_since(tokio::time::Instant::now());
+            if remaining.is_zero() {
+                break; // flush the partial batch once the batch timeout is reached
+            }
+            match timeout(Duration::from_millis(500).min(remaining), rx.recv()).await {
                 Ok(Some(item)) => {
                     info!(
                         "Received task id: {:?},batch len: {:?},batch size: {:?}",
@@ -178,7 +267,10 @@ impl BatchInserter {
         result
     }
 
-    pub async fn finish(self) -> Result<usize, String> {
+    /// Signals the monitor to flush every queued task and waits for all inserts to finish
+    ///
+    /// Returns the number of processed batches.
+    pub async fn finish(&self) -> Result<usize, String> {
         // Signal shutdown to the monitor thread
         let monitor_shutdown_tx = self.monitor_shutdown.lock().await.take();
         if let Some(tx) = monitor_shutdown_tx {
@@ -187,7 +279,6 @@ impl BatchInserter {
             }
         }
 
-        drop(self.tx); // close the channel
         let handle = self.monitor_handle.lock().await.take();
 
         if let Some(handle) = handle {
@@ -213,3 +304,150 @@ impl BatchInserter {
         Ok(self.processed_count.load(Ordering::SeqCst))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> BatchInserterConfig {
+        BatchInserterConfig {
+            min_batch_size: 2,
+            max_batch_size: 16,
+            target_batch_size: 4,
+            max_concurrent_inserts: 1,
+            batch_timeout: Duration::from_secs(5),
+        }
+    }
+
+    #[test]
+    fn test_from_config() {
+        let config = BatchInserterConfig::from_config(&CrawlerConfig {
+            insert_batch_size: 100,
+            max_concurrent_inserts: 2,
+            insert_batch_timeout_ms: 250,
+            ..CrawlerConfig::default()
+        });
+        assert_eq!(config.target_batch_size, 100);
+        // 上限随初始大小提高，避免首批就被截断
+        assert_eq!(config.max_batch_size, 100);
+        assert_eq!(config.max_concurrent_inserts, 2);
+        assert_eq!(config.batch_timeout, Duration::from_millis(250));
+
+        let config = BatchInserterConfig::from_config(&CrawlerConfig::default());
+        assert_eq!(config.target_batch_size, 3);
+        assert_eq!(config.max_batch_size, 50);
+    }
+
+    #[test]
+    fn test_batch_size_grows_when_full() {
+        let config = config();
+        assert_eq!(config.next_batch_size(4, 4), 8);
+        assert_eq!(config.next_batch_size(8, 8), 16);
+        assert_eq!(config.next_batch_size(16, 16), 16);
+    }
+
+    #[test]
+    fn test_batch_size_shrinks_when_mostly_empty() {
+        let config = config();
+        assert_eq!(config.next_batch_size(16, 3), 8);
+        assert_eq!(config.next_batch_size(4, 1), 2);
+        assert_eq!(config.next_batch_size(2, 0), 2);
+    }
+
+    #[test]
+    fn test_batch_size_stable_when_partially_filled() {
+        let config = config();
+        assert_eq!(config.next_batch_size(8, 5), 8);
+    }
+
+    #[tokio::test]
+    async fn test_inserter_flushes_all_tasks() {
+        let inserted = Arc::new(AtomicUsize::new(0));
+        let counter = inserted.clone();
+        let inserter = BatchInserter::new(config(), move |batch: Vec<Task>| {
+            let counter = counter.clone();
+            async move {
+                counter.fetch_add(batch.len(), Ordering::SeqCst);
+                Ok(())
+            }
+        });
+
+        for id in 0..10 {
+            inserter
+                .insert(Task::new(id, format!("http://example.com/{}", id), 0))
+                .await
+                .unwrap();
+        }
+
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while inserted.load(Ordering::SeqCst) < 10 {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+        })
+        .await
+        .expect("all tasks should be flushed");
+    }
+
+    #[tokio::test]
+    async fn test_finish_drains_queued_tasks() {
+        let inserted = Arc::new(AtomicUsize::new(0));
+        let counter = inserted.clone();
+        let config = BatchInserterConfig {
+            min_batch_size: 2,
+            max_batch_size: 2,
+            target_batch_size: 2,
+            ..config()
+        };
+        let inserter = BatchInserter::new(config, move |batch: Vec<Task>| {
+            let counter = counter.clone();
+            async move {
+                counter.fetch_add(batch.len(), Ordering::SeqCst);
+                Ok(())
+            }
+        });
+
+        for id in 0..10 {
+            inserter
+                .insert(Task::new(id, format!("http://example.com/{}", id), 0))
+                .await
+                .unwrap();
+        }
+
+        inserter.finish().await.unwrap();
+        assert_eq!(inserted.load(Ordering::SeqCst), 10);
+    }
+
+    #[tokio::test]
+    async fn test_slow_arrivals_are_not_lost() {
+        let inserted = Arc::new(AtomicUsize::new(0));
+        let counter = inserted.clone();
+        let config = BatchInserterConfig {
+            batch_timeout: Duration::from_millis(100),
+            ..config()
+        };
+        let inserter = BatchInserter::new(config, move |batch: Vec<Task>| {
+            let counter = counter.clone();
+            async move {
+                counter.fetch_add(batch.len(), Ordering::SeqCst);
+                Ok(())
+            }
+        });
+
+        // 任务到达的间隔比 batch_timeout 还长，每批都在超时时未满
+        for id in 0..5 {
+            tokio::time::sleep(Duration::from_millis(150)).await;
+            inserter
+                .insert(Task::new(id, format!("http://example.com/{}", id), 0))
+                .await
+                .unwrap();
+        }
+
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while inserted.load(Ordering::SeqCst) < 5 {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+        })
+        .await
+        .expect("every task should be inserted");
+    }
+}
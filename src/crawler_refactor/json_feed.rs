@@ -0,0 +1,286 @@
+use crate::crawler_refactor::pipeline::Parser;
+use crate::infrastructure::error::{AppResult, ParseError, ParseErrorKind};
+use crate::infrastructure::persistence::models::{episode::NewEpisode, podcast::NewPodcast};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use tracing::debug;
+
+/// JSON Feed 1.1 (https://jsonfeed.org/version/1.1) parser
+#[derive(Clone, Debug, Default)]
+pub struct JsonFeedParser;
+
+#[derive(Debug, Deserialize)]
+struct JsonFeed {
+    title: Option<String>,
+    home_page_url: Option<String>,
+    description: Option<String>,
+    icon: Option<String>,
+    language: Option<String>,
+    #[serde(default)]
+    authors: Vec<JsonFeedAuthor>,
+    // JSON Feed 1.0 只有单个 author
+    author: Option<JsonFeedAuthor>,
+    #[serde(default)]
+    items: Vec<JsonFeedItem>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonFeedAuthor {
+    name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonFeedItem {
+    id: Option<serde_json::Value>,
+    url: Option<String>,
+    title: Option<String>,
+    content_html: Option<String>,
+    content_text: Option<String>,
+    summary: Option<String>,
+    image: Option<String>,
+    date_published: Option<String>,
+    #[serde(default)]
+    authors: Vec<JsonFeedAuthor>,
+    author: Option<JsonFeedAuthor>,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    attachments: Vec<JsonFeedAttachment>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonFeedAttachment {
+    url: String,
+    mime_type: Option<String>,
+    size_in_bytes: Option<i64>,
+    duration_in_seconds: Option<f64>,
+}
+
+impl JsonFeedParser {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn parse_internal(
+        &self,
+        content: &[u8],
+        url: &str,
+    ) -> AppResult<(NewPodcast, Vec<NewEpisode>)> {
+        let feed: JsonFeed = serde_json::from_slice(content).map_err(|e| {
+            ParseError::new(
+                ParseErrorKind::InvalidFormat,
+                format!("Invalid JSON Feed: {}", e),
+                url,
+                Some(Box::new(e)),
+            )
+        })?;
+
+        let title = non_empty(feed.title).ok_or_else(|| {
+            ParseError::new(
+                ParseErrorKind::MissingField,
+                "Podcast title is required",
+                url,
+                None,
+            )
+        })?;
+
+        let podcast = NewPodcast {
+            title,
+            description: non_empty(feed.description),
+            link: non_empty(feed.home_page_url),
+            language: non_empty(feed.language),
+            image_url: non_empty(feed.icon),
+            rss_feed_url: Some(url.to_string()),
+            author: first_author(feed.authors, feed.author),
+            ..Default::default()
+        };
+
+        let mut episodes = Vec::with_capacity(feed.items.len());
+        for (position, item) in feed.items.into_iter().enumerate() {
+            // 没有标题的条目（如纯文本短消息）不是剧集
+            let Some(title) = non_empty(item.title.clone()) else {
+                debug!("Skipping JSON Feed item without title in {}", url);
+                continue;
+            };
+            let mut episode = item_to_episode(item, title);
+            episode.feed_position = Some(position as i32);
+            episodes.push(episode);
+        }
+
+        debug!(
+            "Successfully parsed JSON Feed {} with {} episodes",
+            url,
+            episodes.len()
+        );
+        Ok((podcast, episodes))
+    }
+}
+
+fn item_to_episode(item: JsonFeedItem, title: String) -> NewEpisode {
+    // 第一个 attachment 作为 enclosure
+    let attachment = item.attachments.into_iter().next();
+    let guid = item.id.map(|id| match id {
+        serde_json::Value::String(id) => id,
+        other => other.to_string(),
+    });
+
+    NewEpisode {
+        title,
+        description: non_empty(item.content_text).or_else(|| non_empty(item.content_html)),
+        summary: non_empty(item.summary),
+        link: non_empty(item.url),
+        guid: non_empty(guid),
+        episode_image_url: non_empty(item.image),
+        pub_date: item.date_published.as_deref().and_then(parse_rfc3339),
+        author: first_author(item.authors, item.author),
+        keywords: (!item.tags.is_empty()).then(|| item.tags.into_iter().map(Some).collect()),
+        duration: attachment
+            .as_ref()
+            .and_then(|a| a.duration_in_seconds)
+            .map(|seconds| (seconds.round() as i64).to_string()),
+        enclosure_type: attachment.as_ref().and_then(|a| a.mime_type.clone()),
+        enclosure_length: attachment.as_ref().and_then(|a| a.size_in_bytes),
+        enclosure_url: attachment.map(|a| a.url),
+        ..Default::default()
+    }
+}
+
+fn first_author(authors: Vec<JsonFeedAuthor>, author: Option<JsonFeedAuthor>) -> Option<String> {
+    authors
+        .into_iter()
+        .chain(author)
+        .find_map(|author| non_empty(author.name))
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+fn parse_rfc3339(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value.trim())
+        .ok()
+        .map(|date| date.with_timezone(&Utc))
+}
+
+#[async_trait]
+impl Parser<(NewPodcast, Vec<NewEpisode>)> for JsonFeedParser {
+    async fn parse(&self, content: &[u8], url: &str) -> AppResult<(NewPodcast, Vec<NewEpisode>)> {
+        self.parse_internal(content, url)
+    }
+
+    async fn parse_with_task(
+        &self,
+        task: &mut crate::crawler_refactor::task::Task,
+    ) -> AppResult<(NewPodcast, Vec<NewEpisode>)> {
+        let url = task.get_feed_url().to_string();
+        task.add_stage("parsing");
+        let result = self.parse_internal(&task.content, &url);
+        match &result {
+            Ok((podcast, episodes)) => {
+                task.complete_stage(serde_json::json!({
+                    "podcast": podcast,
+                    "episodes": episodes
+                }));
+            }
+            Err(e) => {
+                task.fail_stage(e.to_string());
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crawler_refactor::task::Task;
+
+    const FEED_URL: &str = "https://example.com/podcast/feed.json";
+
+    #[tokio::test]
+    async fn test_parse_json_feed() {
+        let content = include_bytes!("../../tests/data/json_feed.json");
+        let (podcast, episodes) = JsonFeedParser::new()
+            .parse(content, FEED_URL)
+            .await
+            .unwrap();
+
+        assert_eq!(podcast.title, "JSON Feed Podcast");
+        assert_eq!(podcast.link.as_deref(), Some("https://example.com/podcast"));
+        assert_eq!(
+            podcast.image_url.as_deref(),
+            Some("https://example.com/podcast/cover.jpg")
+        );
+        assert_eq!(podcast.author.as_deref(), Some("Jane Host"));
+        assert_eq!(podcast.rss_feed_url.as_deref(), Some(FEED_URL));
+
+        // 没有标题的条目被跳过
+        assert_eq!(episodes.len(), 2);
+        let first = &episodes[0];
+        assert_eq!(first.guid.as_deref(), Some("episode-2"));
+        assert_eq!(
+            first.enclosure_url.as_deref(),
+            Some("https://example.com/podcast/2.mp3")
+        );
+        assert_eq!(first.enclosure_type.as_deref(), Some("audio/mpeg"));
+        assert_eq!(first.enclosure_length, Some(24000000));
+        assert_eq!(first.duration.as_deref(), Some("1800"));
+        assert_eq!(
+            first.keywords,
+            Some(vec![
+                Some("rust".to_string()),
+                Some("podcasting".to_string())
+            ])
+        );
+        assert_eq!(
+            first.pub_date.unwrap().to_rfc3339(),
+            "2024-02-01T08:00:00+00:00"
+        );
+
+        let second = &episodes[1];
+        assert_eq!(
+            second.description.as_deref(),
+            Some("<p>The first episode.</p>")
+        );
+        assert_eq!(
+            second.pub_date.unwrap().to_rfc3339(),
+            "2024-01-01T00:00:00+00:00"
+        );
+        assert_eq!(first.feed_position, Some(0));
+        assert_eq!(second.feed_position, Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_parse_with_task_round_trip() {
+        let mut task = Task::new(1, FEED_URL.to_string(), 0);
+        task.content = include_bytes!("../../tests/data/json_feed.json").to_vec();
+
+        let (podcast, episodes) = JsonFeedParser::new()
+            .parse_with_task(&mut task)
+            .await
+            .unwrap();
+
+        // 阶段结果可以还原为同样的数据，供插入阶段使用
+        let result = task.get_stage_result_data_by_name("parsing").unwrap();
+        let stored: NewPodcast = serde_json::from_value(result["podcast"].clone()).unwrap();
+        let stored_episodes: Vec<NewEpisode> =
+            serde_json::from_value(result["episodes"].clone()).unwrap();
+        assert_eq!(stored.title, podcast.title);
+        assert_eq!(stored_episodes.len(), episodes.len());
+        assert_eq!(stored_episodes[0].enclosure_url, episodes[0].enclosure_url);
+    }
+
+    #[tokio::test]
+    async fn test_missing_title_is_rejected() {
+        let result = JsonFeedParser::new()
+            .parse(
+                br#"{"version": "https://jsonfeed.org/version/1.1", "items": []}"#,
+                FEED_URL,
+            )
+            .await;
+        assert!(result.is_err());
+    }
+}
@@ -1,11 +1,18 @@
 pub mod distributor;
+mod enclosure_resolver;
 pub mod inserter_refactored;
+mod json_feed;
+mod parser_registry;
 mod pipeline;
-mod rss;
+pub mod priority_queue;
+mod robots;
+pub mod rss;
 pub mod rss_crawler;
 mod rss_fetcher;
+pub mod scheduler;
 pub mod task;
 pub mod task_management_system;
 pub mod thread_manager;
 pub mod timer_queue;
+pub mod webhook;
 pub mod worker;
@@ -0,0 +1,314 @@
+use super::json_feed::JsonFeedParser;
+use super::pipeline::Parser;
+use super::rss::{RssFeedParser, TextLimits};
+use crate::infrastructure::config::CrawlerConfig;
+use crate::infrastructure::error::{
+    parse::{ParseError, ParseErrorKind},
+    AppError,
+};
+use crate::infrastructure::persistence::models::{NewEpisode, NewPodcast};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+pub type PodcastParser = Arc<dyn Parser<(NewPodcast, Vec<NewEpisode>)> + Send + Sync>;
+
+/// 支持的 feed 格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FeedFormat {
+    Rss,
+    Atom,
+    JsonFeed,
+    /// 不是可识别的 feed，如 HTML 页面或空内容
+    Unknown,
+}
+
+// 只检查内容开头，足以找到根元素
+const SNIFF_BYTES: usize = 1024;
+
+impl FeedFormat {
+    /// 根据 `Content-Type` 判断格式，类型不明确时检查内容开头
+    pub fn detect(content_type: Option<&str>, content: &[u8]) -> Self {
+        let mime = content_type
+            .and_then(|value| value.split(';').next())
+            .map(|value| value.trim().to_ascii_lowercase())
+            .unwrap_or_default();
+        match mime.as_str() {
+            "application/feed+json" | "application/json" => return FeedFormat::JsonFeed,
+            "application/atom+xml" => return FeedFormat::Atom,
+            "application/rss+xml" => return FeedFormat::Rss,
+            _ => {}
+        }
+
+        // text/xml、application/xml 以及未声明类型时按内容判断
+        detect_feed_format(content)
+    }
+}
+
+/// 根据内容开头判断 feed 格式
+///
+/// 跳过 BOM、空白、XML 声明、注释和 DOCTYPE 后，`{` 为 JSON Feed，根元素为 `<rss>`、
+/// `<rdf:RDF>`（或缺少外层的 `<channel>`）为 RSS，`<feed>` 为 Atom，其他内容（如 HTML 页面）为 `Unknown`。
+/// 开头过长、找不到根元素时按 RSS 处理，由解析器报告具体错误。
+pub fn detect_feed_format(content: &[u8]) -> FeedFormat {
+    let head = &content[..content.len().min(SNIFF_BYTES)];
+    let mut rest = head.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(head);
+    loop {
+        rest = trim_start(rest);
+        // 跳过 `<?xml ...?>`、`<!-- ... -->` 与 `<!DOCTYPE ...>`
+        let end_marker: &[u8] = if rest.starts_with(b"<?") {
+            b"?>"
+        } else if rest.starts_with(b"<!--") {
+            b"-->"
+        } else if rest.starts_with(b"<!") {
+            b">"
+        } else {
+            break;
+        };
+        match find(rest, end_marker) {
+            Some(position) => rest = &rest[position + end_marker.len()..],
+            None => return FeedFormat::Rss,
+        }
+    }
+
+    match rest.first() {
+        Some(b'{') => FeedFormat::JsonFeed,
+        Some(b'<') => {
+            let name_end = rest[1..]
+                .iter()
+                .position(|b| b.is_ascii_whitespace() || matches!(b, b'>' | b'/'))
+                .map_or(rest.len(), |position| position + 1);
+            match rest[1..name_end].to_ascii_lowercase().as_slice() {
+                b"rss" | b"rdf:rdf" | b"channel" => FeedFormat::Rss,
+                b"feed" => FeedFormat::Atom,
+                _ if name_end == rest.len() => FeedFormat::Rss,
+                _ => FeedFormat::Unknown,
+            }
+        }
+        _ => FeedFormat::Unknown,
+    }
+}
+
+/// 内容不是可识别的 feed 时的错误，附上内容开头便于排查
+pub fn unrecognized_format_error(url: &str, content: &[u8]) -> AppError {
+    let head = String::from_utf8_lossy(&content[..content.len().min(64)]);
+    let head = head.trim();
+    let message = if head.is_empty() {
+        "Unrecognized feed format: content is empty".to_string()
+    } else {
+        format!(
+            "Unrecognized feed format: expected <rss>, <feed> or a JSON Feed, content starts with {:?}",
+            head
+        )
+    };
+    ParseError::new(ParseErrorKind::InvalidFormat, message, url, None).into()
+}
+
+fn trim_start(bytes: &[u8]) -> &[u8] {
+    let start = bytes
+        .iter()
+        .position(|b| !b.is_ascii_whitespace())
+        .unwrap_or(bytes.len());
+    &bytes[start..]
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+/// 按 feed 格式注册解析器，worker 根据抓取结果选择
+#[derive(Debug, Clone, Default)]
+pub struct ParserRegistry {
+    parsers: HashMap<FeedFormat, PodcastParser>,
+}
+
+impl ParserRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 按爬虫配置注册内置的解析器
+    pub fn from_config(config: &CrawlerConfig) -> Self {
+        Self::new()
+            .register(
+                FeedFormat::Rss,
+                Arc::new(
+                    RssFeedParser::new()
+                        .with_language_detection(config.detect_language)
+                        .with_unknown_tag_capture(config.capture_unknown_tags)
+                        .with_media_rss(config.parse_media_rss)
+                        .with_text_limits(TextLimits::from_config(config))
+                        .with_skip_invalid_items(config.skip_invalid_items)
+                        .with_max_episodes((config.max_episodes > 0).then_some(config.max_episodes))
+                        .with_audio_enclosure_check(config.require_audio_enclosure)
+                        .with_stripped_tracking_params(
+                            config
+                                .stripped_tracking_params
+                                .split(',')
+                                .map(|param| param.trim().to_string())
+                                .filter(|param| !param.is_empty())
+                                .collect(),
+                        ),
+                ),
+            )
+            .register(FeedFormat::JsonFeed, Arc::new(JsonFeedParser::new()))
+    }
+
+    /// 注册某种格式的解析器，已有的会被替换
+    pub fn register(mut self, format: FeedFormat, parser: PodcastParser) -> Self {
+        self.parsers.insert(format, parser);
+        self
+    }
+
+    /// 选择解析器；检测到的格式没有注册时回退到 RSS 解析器，无法识别的内容返回 `None`
+    pub fn select(&self, content_type: Option<&str>, content: &[u8]) -> Option<PodcastParser> {
+        let format = FeedFormat::detect(content_type, content);
+        if format == FeedFormat::Unknown {
+            return None;
+        }
+        self.parsers
+            .get(&format)
+            .or_else(|| self.parsers.get(&FeedFormat::Rss))
+            .cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_format() {
+        let rss = b"<?xml version=\"1.0\"?><rss version=\"2.0\"><channel/></rss>";
+        let atom = b"<?xml version=\"1.0\"?><feed xmlns=\"http://www.w3.org/2005/Atom\"/>";
+        let json = b"\xEF\xBB\xBF  {\"version\": \"https://jsonfeed.org/version/1.1\"}";
+
+        assert_eq!(FeedFormat::detect(None, rss), FeedFormat::Rss);
+        assert_eq!(FeedFormat::detect(Some("text/xml"), atom), FeedFormat::Atom);
+        assert_eq!(FeedFormat::detect(None, json), FeedFormat::JsonFeed);
+        // 明确的 Content-Type 优先于内容
+        assert_eq!(
+            FeedFormat::detect(Some("application/feed+json; charset=utf-8"), rss),
+            FeedFormat::JsonFeed
+        );
+    }
+
+    #[test]
+    fn test_detect_feed_format() {
+        let rss =
+            b"<?xml version=\"1.0\"?>\n<!-- generated -->\n<rss version=\"2.0\"><channel/></rss>";
+        let rdf = b"<?xml version=\"1.0\"?><rdf:RDF xmlns:rdf=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\">";
+        let atom = b"\xEF\xBB\xBF<feed xmlns=\"http://www.w3.org/2005/Atom\"></feed>";
+        let json = b"  {\"version\": \"https://jsonfeed.org/version/1.1\"}";
+        let html = b"<!DOCTYPE html>\n<html><head><title>Not found</title></head></html>";
+
+        assert_eq!(detect_feed_format(rss), FeedFormat::Rss);
+        assert_eq!(detect_feed_format(rdf), FeedFormat::Rss);
+        assert_eq!(detect_feed_format(atom), FeedFormat::Atom);
+        assert_eq!(detect_feed_format(json), FeedFormat::JsonFeed);
+        assert_eq!(detect_feed_format(html), FeedFormat::Unknown);
+        assert_eq!(
+            detect_feed_format(b"Service Unavailable"),
+            FeedFormat::Unknown
+        );
+        assert_eq!(detect_feed_format(b"  \n"), FeedFormat::Unknown);
+
+        // HTML 页面不选择解析器，错误中带上内容开头
+        let registry = ParserRegistry::from_config(&CrawlerConfig::default());
+        assert!(registry.select(Some("text/html"), html).is_none());
+        let error = unrecognized_format_error("https://example.com/feed", html);
+        assert!(error.to_string().contains("<!DOCTYPE html>"));
+    }
+
+    #[test]
+    fn test_select_falls_back_to_rss() {
+        let registry = ParserRegistry::new();
+        assert!(registry.select(None, b"<rss/>").is_none());
+
+        let registry = registry.register(FeedFormat::Rss, Arc::new(RssFeedParser::new()));
+        assert!(registry.select(None, b"<rss/>").is_some());
+        assert!(registry.select(Some("application/atom+xml"), b"").is_some());
+    }
+
+    #[test]
+    fn test_from_config_selects_json_feed_parser() {
+        let registry = ParserRegistry::from_config(&CrawlerConfig::default());
+        let parser = registry
+            .select(Some("application/feed+json"), b"{}")
+            .unwrap();
+        assert!(format!("{:?}", parser).contains("JsonFeedParser"));
+        let parser = registry
+            .select(Some("application/rss+xml"), b"<rss/>")
+            .unwrap();
+        assert!(format!("{:?}", parser).contains("RssFeedParser"));
+    }
+
+    #[tokio::test]
+    async fn test_from_config_skips_invalid_items() {
+        let rss = br#"<rss version="2.0"><channel><title>Partly Broken</title>
+            <item><title>Good</title></item>
+            <item><description>No title</description></item>
+        </channel></rss>"#;
+        let url = "https://example.com/broken.xml";
+        let parser = |skip_invalid_items| {
+            ParserRegistry::from_config(&CrawlerConfig {
+                skip_invalid_items,
+                ..CrawlerConfig::default()
+            })
+            .select(Some("application/rss+xml"), rss)
+            .unwrap()
+        };
+
+        assert!(parser(false).parse(rss, url).await.is_err());
+        let (_podcast, episodes) = parser(true).parse(rss, url).await.unwrap();
+        assert_eq!(episodes.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_from_config_caps_episodes() {
+        let rss = br#"<rss version="2.0"><channel><title>Long Feed</title>
+            <item><title>Episode 3</title></item>
+            <item><title>Episode 2</title></item>
+            <item><title>Episode 1</title></item>
+        </channel></rss>"#;
+        let url = "https://example.com/long.xml";
+        let episodes = |max_episodes| async move {
+            ParserRegistry::from_config(&CrawlerConfig {
+                max_episodes,
+                ..CrawlerConfig::default()
+            })
+            .select(Some("application/rss+xml"), rss)
+            .unwrap()
+            .parse(rss, url)
+            .await
+            .unwrap()
+            .1
+        };
+
+        assert_eq!(episodes(0).await.len(), 3);
+        let titles: Vec<String> = episodes(2).await.into_iter().map(|e| e.title).collect();
+        assert_eq!(titles, vec!["Episode 3", "Episode 2"]);
+    }
+
+    #[tokio::test]
+    async fn test_from_config_checks_audio_enclosures() {
+        let rss = br#"<rss version="2.0"><channel><title>Mixed</title>
+            <item><title>Page</title>
+                <enclosure url="https://example.com/ep1.html" type="text/html" length="1"/>
+            </item>
+        </channel></rss>"#;
+        let url = "https://example.com/mixed.xml";
+        let parser = |require_audio_enclosure| {
+            ParserRegistry::from_config(&CrawlerConfig {
+                require_audio_enclosure,
+                ..CrawlerConfig::default()
+            })
+            .select(Some("application/rss+xml"), rss)
+            .unwrap()
+        };
+
+        assert!(parser(false).parse(rss, url).await.is_ok());
+        assert!(parser(true).parse(rss, url).await.is_err());
+    }
+}
@@ -15,6 +15,11 @@ pub trait Parser<T>: std::fmt::Debug {
 pub trait Fetcher: std::fmt::Debug {
     /// 获取内容
     async fn fetch(&self, url: &str) -> Result<Vec<u8>, AppError>;
+    /// 获取内容，并返回重定向后的最终 URL
+    async fn fetch_with_final_url(&self, url: &str) -> Result<(Vec<u8>, String), AppError> {
+        let content = self.fetch(url).await?;
+        Ok((content, url.to_string()))
+    }
     async fn fetch_with_task(
         &self,
         task: &mut crate::crawler_refactor::task::Task,
@@ -0,0 +1,415 @@
+use crate::crawler_refactor::task::Task;
+use crate::crawler_refactor::task_management_system::TaskWorkerMaps;
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, BinaryHeap, HashMap};
+use std::sync::atomic::{AtomicU64, Ordering as AtomicOrdering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::{broadcast, Notify, Semaphore};
+use tokio_util::sync::CancellationToken;
+
+// 队列中的任务，按优先级从高到低出队，同优先级按入队顺序（FIFO）
+struct PrioritizedTask {
+    sequence: u64,
+    task: Task,
+}
+
+impl Ord for PrioritizedTask {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.task
+            .priority
+            .cmp(&other.task.priority)
+            .then_with(|| other.sequence.cmp(&self.sequence))
+    }
+}
+
+impl PartialOrd for PrioritizedTask {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for PrioritizedTask {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for PrioritizedTask {}
+
+// 没有 worker 接收时分发失败的任务的错误信息
+const DISPATCH_FAILED: &str = "dispatch to worker failed";
+
+/// Priority-aware queue sitting in front of the worker broadcast channel
+///
+/// The broadcast channel delivers tasks in send order, so tasks are held
+/// here and only forwarded when a worker slot is free. Workers hand their
+/// slot back with [`release_slot`](Self::release_slot) once a task is done.
+///
+/// Every dispatched task is stamped with a per-worker `dispatch_seq`, so a
+/// worker whose broadcast receiver lagged can tell from the gap how many of
+/// its own tasks were dropped. The ids of dispatched tasks are kept until
+/// the worker takes them with [`take_dispatched`](Self::take_dispatched), so
+/// the dropped ones can be failed.
+///
+/// Workers registered with [`add_worker`](Self::add_worker) each contribute a
+/// slot. A task whose target worker has been retired is handed to the next
+/// registered worker when it is dispatched.
+pub struct PriorityQueue {
+    heap: Mutex<BinaryHeap<PrioritizedTask>>,
+    sequence: AtomicU64,
+    dispatch_seqs: Mutex<HashMap<usize, u64>>,
+    // 已分发但目标 worker 尚未确认收到的任务 id，按 (worker, dispatch_seq) 索引
+    dispatched: Mutex<HashMap<(usize, u64), u64>>,
+    live_workers: Mutex<LiveWorkers>,
+    notify: Notify,
+    slots: Semaphore,
+    worker_task_tx: broadcast::Sender<Task>,
+    cancellation_token: CancellationToken,
+    // 分发失败时用于将任务标记为失败
+    task_worker_maps: Option<Arc<TaskWorkerMaps>>,
+}
+
+// 当前可接收任务的 worker，last_assigned 用于在其中轮询
+#[derive(Default)]
+struct LiveWorkers {
+    ids: BTreeSet<usize>,
+    last_assigned: usize,
+}
+
+impl LiveWorkers {
+    // 目标 worker 已退出时按轮询改派；没有登记任何 worker 时保持原目标
+    fn assign(&mut self, target: usize) -> usize {
+        if self.ids.is_empty() || self.ids.contains(&target) {
+            return target;
+        }
+        let next = self
+            .ids
+            .range(self.last_assigned + 1..)
+            .next()
+            .or_else(|| self.ids.iter().next())
+            .copied()
+            .unwrap_or(target);
+        self.last_assigned = next;
+        next
+    }
+}
+
+impl PriorityQueue {
+    pub fn new(
+        worker_task_tx: broadcast::Sender<Task>,
+        slots: usize,
+        cancellation_token: CancellationToken,
+    ) -> Self {
+        Self {
+            heap: Mutex::new(BinaryHeap::new()),
+            sequence: AtomicU64::new(0),
+            dispatch_seqs: Mutex::new(HashMap::new()),
+            dispatched: Mutex::new(HashMap::new()),
+            live_workers: Mutex::new(LiveWorkers::default()),
+            notify: Notify::new(),
+            slots: Semaphore::new(slots),
+            worker_task_tx,
+            cancellation_token,
+            task_worker_maps: None,
+        }
+    }
+
+    /// Fails tasks that could not be sent to any worker through `task_worker_maps`
+    pub fn with_task_worker_maps(mut self, task_worker_maps: Arc<TaskWorkerMaps>) -> Self {
+        self.task_worker_maps = Some(task_worker_maps);
+        self
+    }
+
+    pub fn push(&self, task: Task) {
+        let sequence = self.sequence.fetch_add(1, AtomicOrdering::SeqCst);
+        self.heap
+            .lock()
+            .unwrap()
+            .push(PrioritizedTask { sequence, task });
+        self.notify.notify_one();
+    }
+
+    pub fn pop(&self) -> Option<Task> {
+        self.heap.lock().unwrap().pop().map(|entry| entry.task)
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // 目标 worker 的下一个分发序号
+    fn next_dispatch_seq(&self, worker_id: usize) -> u64 {
+        let mut seqs = self.dispatch_seqs.lock().unwrap();
+        let seq = seqs.entry(worker_id).or_insert(0);
+        *seq += 1;
+        *seq
+    }
+
+    /// Takes the id of the task dispatched to `worker_id` with `dispatch_seq`
+    ///
+    /// Workers call this for every task they receive and for every sequence
+    /// number missing from their stream, which identifies the lost tasks.
+    pub fn take_dispatched(&self, worker_id: usize, dispatch_seq: u64) -> Option<u64> {
+        self.dispatched
+            .lock()
+            .unwrap()
+            .remove(&(worker_id, dispatch_seq))
+    }
+
+    // 任务处理完成后归还 worker 槽位
+    pub fn release_slot(&self) {
+        self.slots.add_permits(1);
+    }
+
+    pub fn available_slots(&self) -> usize {
+        self.slots.available_permits()
+    }
+
+    /// Registers a started worker and adds its slot
+    pub fn add_worker(&self, worker_id: usize) {
+        self.live_workers.lock().unwrap().ids.insert(worker_id);
+        self.release_slot();
+    }
+
+    pub fn live_worker_count(&self) -> usize {
+        self.live_workers.lock().unwrap().ids.len()
+    }
+
+    /// Unregisters an idle worker, taking its slot away
+    ///
+    /// Fails while only `min_workers` workers are left, or when every slot is
+    /// taken (a task is then on its way to some worker). Tasks already sent to
+    /// the retired worker must be handed back with [`requeue`](Self::requeue).
+    pub fn try_retire_worker(&self, worker_id: usize, min_workers: usize) -> bool {
+        let mut live = self.live_workers.lock().unwrap();
+        if live.ids.len() <= min_workers || !live.ids.contains(&worker_id) {
+            return false;
+        }
+        match self.slots.try_acquire() {
+            Ok(permit) => permit.forget(),
+            Err(_) => return false,
+        }
+        live.ids.remove(&worker_id);
+        // 重新启动的 worker 从序号 1 开始接收
+        self.dispatch_seqs.lock().unwrap().remove(&worker_id);
+        // 已发给该 worker 的任务由 requeue 交还
+        self.dispatched
+            .lock()
+            .unwrap()
+            .retain(|&(id, _), _| id != worker_id);
+        true
+    }
+
+    // 退出的 worker 归还已收到但未处理的任务及其槽位
+    pub fn requeue(&self, task: Task) {
+        self.push(task);
+        self.release_slot();
+    }
+
+    pub async fn start_dispatcher(&self) {
+        'dispatch: loop {
+            // 先等待任务入队再占用槽位，队列为空时不占用槽位，空闲的 worker 才能退出
+            while self.is_empty() {
+                tokio::select! {
+                    _ = self.cancellation_token.cancelled() => break 'dispatch,
+                    _ = self.notify.notified() => {}
+                }
+            }
+
+            // 等待空闲的 worker 槽位
+            tokio::select! {
+                _ = self.cancellation_token.cancelled() => break,
+                permit = self.slots.acquire() => match permit {
+                    Ok(permit) => permit.forget(),
+                    Err(_) => break,
+                },
+            }
+
+            // 取出优先级最高的任务
+            let Some(mut task) = self.pop() else {
+                self.release_slot();
+                continue;
+            };
+            // 持锁发送，worker 退出时不会再收到发给它的新任务
+            let sent = {
+                let mut live = self.live_workers.lock().unwrap();
+                task.target_thread_id = live.assign(task.target_thread_id);
+                task.dispatch_seq = self.next_dispatch_seq(task.target_thread_id);
+                let key = (task.target_thread_id, task.dispatch_seq);
+                self.dispatched.lock().unwrap().insert(key, task.id);
+
+                tracing::debug!(
+                    "📤 PriorityQueue: Dispatching task {} (priority {})",
+                    task.id,
+                    task.priority
+                );
+                self.worker_task_tx.send(task).map_err(|e| (key, e.0))
+            };
+            // 没有 worker 接收的任务不会再被处理，直接失败
+            if let Err((key, task)) = sent {
+                tracing::error!("❌ PriorityQueue: Failed to dispatch task {}", task.id);
+                self.take_dispatched(key.0, key.1);
+                self.release_slot();
+                crate::metrics::FAILED_TASKS.inc();
+                if let Some(task_worker_maps) = &self.task_worker_maps {
+                    task_worker_maps
+                        .fail_undelivered_task(task.id, DISPATCH_FAILED)
+                        .await;
+                }
+            }
+        }
+        tracing::info!(
+            "🏁 PriorityQueue: Dispatcher stopped, {} tasks left undispatched",
+            self.len()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crawler_refactor::task::PRIORITY_NORMAL;
+
+    fn task_with_priority(id: u64, priority: u8) -> Task {
+        let mut task = Task::new(id, format!("http://example.com/{}", id), 0);
+        task.priority = priority;
+        task
+    }
+
+    #[test]
+    fn test_pop_prefers_higher_priority() {
+        let (tx, _rx) = broadcast::channel(16);
+        let queue = PriorityQueue::new(tx, 1, CancellationToken::new());
+        queue.push(task_with_priority(1, PRIORITY_NORMAL));
+        queue.push(task_with_priority(2, 255));
+        queue.push(task_with_priority(3, 0));
+
+        assert_eq!(queue.pop().map(|t| t.id), Some(2));
+        assert_eq!(queue.pop().map(|t| t.id), Some(1));
+        assert_eq!(queue.pop().map(|t| t.id), Some(3));
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_same_priority_is_fifo() {
+        let (tx, _rx) = broadcast::channel(16);
+        let queue = PriorityQueue::new(tx, 1, CancellationToken::new());
+        for id in 1..=3 {
+            queue.push(task_with_priority(id, PRIORITY_NORMAL));
+        }
+
+        let order: Vec<u64> = std::iter::from_fn(|| queue.pop()).map(|t| t.id).collect();
+        assert_eq!(order, vec![1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_dispatcher_sends_high_priority_first_when_slot_frees() {
+        let (tx, mut rx) = broadcast::channel(16);
+        let token = CancellationToken::new();
+        let queue = Arc::new(PriorityQueue::new(tx, 1, token.clone()));
+
+        queue.push(task_with_priority(1, PRIORITY_NORMAL));
+        let dispatcher = {
+            let queue = queue.clone();
+            tokio::spawn(async move { queue.start_dispatcher().await })
+        };
+        assert_eq!(rx.recv().await.unwrap().id, 1);
+
+        // The only slot is taken, so both tasks wait and the urgent one goes first
+        queue.push(task_with_priority(2, PRIORITY_NORMAL));
+        queue.push(task_with_priority(3, 255));
+        queue.release_slot();
+        assert_eq!(rx.recv().await.unwrap().id, 3);
+
+        token.cancel();
+        dispatcher.await.unwrap();
+        assert_eq!(queue.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_seq_is_per_worker() {
+        let (tx, mut rx) = broadcast::channel(16);
+        let token = CancellationToken::new();
+        let queue = Arc::new(PriorityQueue::new(tx, 3, token.clone()));
+        for (id, worker) in [(1, 0), (2, 1), (3, 0)] {
+            let mut task = task_with_priority(id, PRIORITY_NORMAL);
+            task.target_thread_id = worker;
+            queue.push(task);
+        }
+        let dispatcher = {
+            let queue = queue.clone();
+            tokio::spawn(async move { queue.start_dispatcher().await })
+        };
+
+        let mut seqs = Vec::new();
+        for _ in 0..3 {
+            let task = rx.recv().await.unwrap();
+            seqs.push((task.id, task.target_thread_id, task.dispatch_seq));
+        }
+        assert_eq!(seqs, vec![(1, 0, 1), (2, 1, 1), (3, 0, 2)]);
+        assert_eq!(queue.take_dispatched(0, 2), Some(3));
+        assert_eq!(queue.take_dispatched(0, 2), None);
+        assert_eq!(queue.take_dispatched(1, 1), Some(2));
+
+        token.cancel();
+        dispatcher.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_tasks_for_retired_worker_are_reassigned() {
+        let (tx, mut rx) = broadcast::channel(16);
+        let token = CancellationToken::new();
+        let queue = Arc::new(PriorityQueue::new(tx, 0, token.clone()));
+        queue.add_worker(0);
+        queue.add_worker(1);
+        assert!(queue.try_retire_worker(1, 1));
+        // The last worker is kept
+        assert!(!queue.try_retire_worker(0, 1));
+        assert_eq!(queue.live_worker_count(), 1);
+
+        let mut task = task_with_priority(1, PRIORITY_NORMAL);
+        task.target_thread_id = 1;
+        queue.push(task);
+        let dispatcher = {
+            let queue = queue.clone();
+            tokio::spawn(async move { queue.start_dispatcher().await })
+        };
+        let task = rx.recv().await.unwrap();
+        assert_eq!((task.target_thread_id, task.dispatch_seq), (0, 1));
+
+        token.cancel();
+        dispatcher.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_retire_needs_a_free_slot() {
+        let (tx, mut rx) = broadcast::channel(16);
+        let token = CancellationToken::new();
+        let queue = Arc::new(PriorityQueue::new(tx, 0, token.clone()));
+        queue.add_worker(0);
+        queue.add_worker(1);
+        let dispatcher = {
+            let queue = queue.clone();
+            tokio::spawn(async move { queue.start_dispatcher().await })
+        };
+        for (id, worker) in [(1, 0), (2, 1)] {
+            let mut task = task_with_priority(id, PRIORITY_NORMAL);
+            task.target_thread_id = worker;
+            queue.push(task);
+            rx.recv().await.unwrap();
+        }
+
+        // Both workers are busy
+        assert!(!queue.try_retire_worker(1, 0));
+        queue.release_slot();
+        assert!(queue.try_retire_worker(1, 0));
+        assert_eq!(queue.available_slots(), 0);
+
+        token.cancel();
+        dispatcher.await.unwrap();
+    }
+}
@@ -0,0 +1,239 @@
+use reqwest::Client;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::debug;
+use url::Url;
+
+// 一个站点对本爬虫生效的规则：(是否允许, 路径模式)
+#[derive(Debug, Default, Clone, PartialEq)]
+struct RobotsRules {
+    rules: Vec<(bool, String)>,
+}
+
+impl RobotsRules {
+    // 使用名称 `agent_name` 匹配本爬虫的分组，没有时使用 `*` 分组；多个匹配的分组合并
+    fn parse(content: &str, agent_name: &str) -> Self {
+        let mut named = None::<Vec<(bool, String)>>;
+        let mut wildcard = None::<Vec<(bool, String)>>;
+        // 当前分组的 User-agent；遇到规则行后，下一个 User-agent 开始新的分组
+        let mut agents: Vec<String> = Vec::new();
+        let mut in_rules = false;
+
+        for line in content.lines() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let value = value.trim();
+            match key.trim().to_ascii_lowercase().as_str() {
+                "user-agent" => {
+                    if in_rules {
+                        agents.clear();
+                        in_rules = false;
+                    }
+                    let agent = value.to_ascii_lowercase();
+                    if agent == "*" {
+                        wildcard.get_or_insert_with(Vec::new);
+                    } else if is_own_agent(&agent, agent_name) {
+                        named.get_or_insert_with(Vec::new);
+                    }
+                    agents.push(agent);
+                }
+                key @ ("allow" | "disallow") => {
+                    in_rules = true;
+                    // 空的 Disallow 表示不限制
+                    if value.is_empty() {
+                        continue;
+                    }
+                    let rule = (key == "allow", value.to_string());
+                    for agent in &agents {
+                        let group = if agent == "*" {
+                            wildcard.as_mut()
+                        } else if is_own_agent(agent, agent_name) {
+                            named.as_mut()
+                        } else {
+                            None
+                        };
+                        if let Some(group) = group {
+                            group.push(rule.clone());
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        Self {
+            rules: named.or(wildcard).unwrap_or_default(),
+        }
+    }
+
+    // 最长的匹配规则生效，长度相同时 Allow 优先；没有匹配的规则时允许
+    fn is_allowed(&self, path: &str) -> bool {
+        self.rules
+            .iter()
+            .filter(|(_, pattern)| pattern_matches(pattern, path))
+            .max_by_key(|(allow, pattern)| (pattern.len(), *allow))
+            .is_none_or(|(allow, _)| *allow)
+    }
+}
+
+// User-agent 可以带版本号，如 `PodcastCrawler/1.0`
+fn is_own_agent(agent: &str, agent_name: &str) -> bool {
+    agent.split('/').next().map(str::trim) == Some(agent_name)
+}
+
+// robots.txt 中匹配的爬虫名称：User-Agent 的第一个产品名，小写，如 `PodcastCrawler/1.0` 为 `podcastcrawler`
+fn agent_name(user_agent: &str) -> String {
+    user_agent
+        .split(|c: char| c == '/' || c.is_whitespace())
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase()
+}
+
+// 路径模式支持 `*`（任意字符）和结尾的 `$`（匹配到路径末尾）
+fn pattern_matches(pattern: &str, path: &str) -> bool {
+    let (pattern, anchored) = match pattern.strip_suffix('$') {
+        Some(pattern) => (pattern, true),
+        None => (pattern, false),
+    };
+    let mut parts = pattern.split('*');
+    let Some(mut rest) = path.strip_prefix(parts.next().unwrap_or_default()) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    for (i, part) in parts.iter().enumerate() {
+        if anchored && i == parts.len() - 1 {
+            return rest.ends_with(part);
+        }
+        match rest.find(part) {
+            Some(position) => rest = &rest[position + part.len()..],
+            None => return false,
+        }
+    }
+    !anchored || rest.is_empty()
+}
+
+// 站点及其 robots.txt 的获取时间和规则
+type RobotsSites = Arc<Mutex<HashMap<String, (Instant, Arc<RobotsRules>)>>>;
+
+// 按站点（scheme、主机和端口）缓存 robots.txt 规则；克隆的 fetcher 共用同一份缓存
+#[derive(Clone, Debug)]
+pub(crate) struct RobotsCache {
+    ttl: Duration,
+    sites: RobotsSites,
+}
+
+impl RobotsCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            sites: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    // robots.txt 不存在或无法获取时不做限制
+    pub async fn is_allowed(&self, client: &Client, user_agent: &str, url: &Url) -> bool {
+        let site = url.origin().ascii_serialization();
+        let cached = self
+            .sites
+            .lock()
+            .unwrap()
+            .get(&site)
+            .filter(|(fetched_at, _)| fetched_at.elapsed() < self.ttl)
+            .map(|(_, rules)| rules.clone());
+        let rules = match cached {
+            Some(rules) => rules,
+            None => {
+                let rules = Arc::new(fetch_rules(client, user_agent, &site).await);
+                self.sites
+                    .lock()
+                    .unwrap()
+                    .insert(site, (Instant::now(), rules.clone()));
+                rules
+            }
+        };
+
+        let path = match url.query() {
+            Some(query) => format!("{}?{}", url.path(), query),
+            None => url.path().to_string(),
+        };
+        rules.is_allowed(&path)
+    }
+}
+
+async fn fetch_rules(client: &Client, user_agent: &str, site: &str) -> RobotsRules {
+    let robots_url = format!("{}/robots.txt", site);
+    let response = match client
+        .get(&robots_url)
+        .header("User-Agent", user_agent)
+        .send()
+        .await
+    {
+        Ok(response) if response.status().is_success() => response,
+        Ok(response) => {
+            debug!("No robots.txt at {} ({})", robots_url, response.status());
+            return RobotsRules::default();
+        }
+        Err(e) => {
+            debug!("Failed to fetch {}: {}", robots_url, e);
+            return RobotsRules::default();
+        }
+    };
+    match response.text().await {
+        Ok(content) => RobotsRules::parse(&content, &agent_name(user_agent)),
+        Err(e) => {
+            debug!("Failed to read {}: {}", robots_url, e);
+            RobotsRules::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_uses_named_group_over_wildcard() {
+        let content = "\
+# 示例
+User-agent: *
+Disallow: /
+
+User-agent: GoogleBot
+User-agent: PodcastCrawler
+Disallow: /private/
+Allow: /private/feeds/
+Disallow: /*.php$
+";
+        let rules = RobotsRules::parse(content, "podcastcrawler");
+        assert!(rules.is_allowed("/feed.xml"));
+        assert!(!rules.is_allowed("/private/feed.xml"));
+        assert!(rules.is_allowed("/private/feeds/show.xml"));
+        assert!(!rules.is_allowed("/rss.php"));
+        assert!(rules.is_allowed("/rss.php?id=1"));
+
+        let rules = RobotsRules::parse(
+            "User-agent: *\nDisallow: /\nUser-agent: Other\nAllow: /",
+            "podcastcrawler",
+        );
+        assert!(!rules.is_allowed("/feed.xml"));
+        assert!(
+            RobotsRules::parse("User-agent: *\nDisallow:\n", "podcastcrawler")
+                .is_allowed("/feed.xml")
+        );
+    }
+
+    #[test]
+    fn test_agent_name_follows_user_agent() {
+        assert_eq!(agent_name("PodcastCrawler/1.0"), "podcastcrawler");
+        assert_eq!(agent_name("MyBot (+https://example.com/bot)"), "mybot");
+
+        let content = "User-agent: *\nDisallow: /\n\nUser-agent: MyBot\nAllow: /\n";
+        assert!(RobotsRules::parse(content, &agent_name("MyBot/2.0")).is_allowed("/feed.xml"));
+        assert!(
+            !RobotsRules::parse(content, &agent_name("PodcastCrawler/1.0")).is_allowed("/feed.xml")
+        );
+    }
+}
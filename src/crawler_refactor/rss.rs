@@ -1,6 +1,5 @@
-use std::io::BufRead;
-
 use crate::crawler_refactor::pipeline::Parser;
+use crate::infrastructure::config::CrawlerConfig;
 use crate::infrastructure::error::{
     parse::{ParseError, ParseErrorKind},
     AppError, AppResult,
@@ -8,8 +7,11 @@ use crate::infrastructure::error::{
 use crate::infrastructure::persistence::models::{episode::NewEpisode, podcast::NewPodcast};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
+use encoding_rs::{Encoding, UTF_8};
 use quick_xml::events::{BytesEnd, BytesStart, BytesText, Event};
 use quick_xml::Reader;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use tracing::{debug, warn};
 
 /// Debugging macro for parser events.
@@ -46,9 +48,46 @@ struct RssParserState {
     podcast: Option<NewPodcast>,
     current_episode: Option<NewEpisode>,
     episodes: Vec<NewEpisode>,
+    skipped_episodes: usize,
+    // 已结束的 item 数量，即下一个 item 在 feed 中的位置
+    item_position: i32,
+    warnings: Vec<ParseWarning>,
+    // <itunes:new-feed-url> 声明的新地址，不写入数据库
+    new_feed_url: Option<String>,
+    // 正在读取的未处理命名空间元素，元素结束时写入 extra
+    pending_extra: Option<PendingExtra>,
+    // 当前剧集的 enclosure 来自 <media:content>，之后出现的 <enclosure> 会替换它
+    media_enclosure: bool,
     context: ParseContext,
 }
 
+#[derive(Debug)]
+struct PendingExtra {
+    tag: String,
+    attributes: serde_json::Map<String, serde_json::Value>,
+    text: String,
+    // 文本由已有字段处理时不再记录
+    handled: bool,
+}
+
+// 一次解析的完整结果
+struct ParsedFeed {
+    podcast: NewPodcast,
+    episodes: Vec<NewEpisode>,
+    warnings: Vec<ParseWarning>,
+    new_feed_url: Option<String>,
+}
+
+/// 解析中发现的非致命问题，feed 仍然解析成功
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParseWarning {
+    /// 受影响的字段，如 `image_url`、`pub_date`
+    pub field: String,
+    /// 发现问题时的 XML 元素路径，如 `rss/channel/item/pubDate`
+    pub path: String,
+    pub message: String,
+}
+
 /// RSS 解析上下文，用于错误处理和状态跟踪
 #[derive(Debug, Default)]
 pub struct ParseContext {
@@ -78,6 +117,15 @@ impl ParseContext {
         self.element_path.join("/")
     }
 
+    // 在当前元素路径上生成一条警告
+    fn warning(&self, field: &str, message: impl Into<String>) -> ParseWarning {
+        ParseWarning {
+            field: field.to_string(),
+            path: self.current_path(),
+            message: message.into(),
+        }
+    }
+
     fn current_depth(&self) -> usize {
         self.element_path.len()
     }
@@ -145,6 +193,48 @@ pub struct ParserConfig {
     allow_empty_required: bool,
     /// 严格模式
     strict_mode: bool,
+    /// feed 未声明语言时，是否根据标题和描述检测语言
+    detect_language: bool,
+    /// 是否要求 enclosure 为音频（type 为 audio/*，或无 type 时 URL 为音频扩展名）
+    require_audio_enclosure: bool,
+    /// 每个 feed 最多保留的剧集数量
+    max_episodes: Option<usize>,
+    /// 剧集校验失败时只跳过该剧集，而不是让整个 feed 解析失败
+    skip_invalid_items: bool,
+    /// 把未处理的命名空间元素（如 `spotify:`、`acast:`）按完整标签名记录到 `extra`
+    capture_unknown_tags: bool,
+    /// 没有 `<enclosure>` 时使用 `<media:content>`，并用 `<media:thumbnail>` 作为剧集封面
+    parse_media_rss: bool,
+    /// 文本字段的长度上限
+    text_limits: TextLimits,
+    /// 从 enclosure 和 link 地址中删除的查询参数，`utm_*` 形式按前缀匹配
+    strip_enclosure_tracking_params: Vec<String>,
+    /// 没有 `<guid>` 的剧集根据 enclosure 地址（或 link 加发布时间）生成稳定的 guid
+    synthesize_guid: bool,
+}
+
+/// 播客和剧集文本字段的最大长度（字符数），`None` 表示不限制
+///
+/// 超过上限的文本截断到上限（含结尾的 "…"），并记录一条解析警告。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TextLimits {
+    pub title: Option<usize>,
+    pub description: Option<usize>,
+    pub summary: Option<usize>,
+    pub subtitle: Option<usize>,
+}
+
+impl TextLimits {
+    /// 从爬虫配置读取，0 表示不限制
+    pub fn from_config(config: &CrawlerConfig) -> Self {
+        let limit = |length: usize| (length > 0).then_some(length);
+        Self {
+            title: limit(config.max_title_length),
+            description: limit(config.max_description_length),
+            summary: limit(config.max_summary_length),
+            subtitle: limit(config.max_subtitle_length),
+        }
+    }
 }
 
 impl Default for ParserConfig {
@@ -154,10 +244,75 @@ impl Default for ParserConfig {
             validate_urls: true,
             allow_empty_required: false,
             strict_mode: true,
+            detect_language: false,
+            require_audio_enclosure: false,
+            max_episodes: None,
+            skip_invalid_items: false,
+            capture_unknown_tags: false,
+            parse_media_rss: true,
+            text_limits: TextLimits::default(),
+            strip_enclosure_tracking_params: Vec::new(),
+            synthesize_guid: true,
         }
     }
 }
 
+impl ParserConfig {
+    /// 开启或关闭语言检测（仅在 feed 未声明 `<language>` 时生效）
+    pub fn with_language_detection(mut self, enabled: bool) -> Self {
+        self.detect_language = enabled;
+        self
+    }
+
+    /// 开启或关闭未处理命名空间元素的记录
+    pub fn with_unknown_tag_capture(mut self, enabled: bool) -> Self {
+        self.capture_unknown_tags = enabled;
+        self
+    }
+
+    /// 开启或关闭 Media RSS（`media:` 命名空间）解析
+    pub fn with_media_rss(mut self, enabled: bool) -> Self {
+        self.parse_media_rss = enabled;
+        self
+    }
+
+    /// 设置文本字段的长度上限
+    pub fn with_text_limits(mut self, text_limits: TextLimits) -> Self {
+        self.text_limits = text_limits;
+        self
+    }
+
+    /// 设置要从 enclosure 和 link 地址中删除的查询参数
+    pub fn with_stripped_tracking_params(mut self, params: Vec<String>) -> Self {
+        self.strip_enclosure_tracking_params = params;
+        self
+    }
+
+    /// 开启或关闭缺失 guid 的生成
+    pub fn with_guid_synthesis(mut self, enabled: bool) -> Self {
+        self.synthesize_guid = enabled;
+        self
+    }
+
+    /// 开启或关闭校验失败剧集的跳过
+    pub fn with_skip_invalid_items(mut self, enabled: bool) -> Self {
+        self.skip_invalid_items = enabled;
+        self
+    }
+
+    /// 限制每个 feed 最多保留的剧集数量，`None` 表示不限制
+    pub fn with_max_episodes(mut self, max_episodes: Option<usize>) -> Self {
+        self.max_episodes = max_episodes;
+        self
+    }
+
+    /// 开启或关闭 enclosure 的音频检查
+    pub fn with_audio_enclosure_check(mut self, enabled: bool) -> Self {
+        self.require_audio_enclosure = enabled;
+        self
+    }
+}
+
 impl RssFeedParser {
     pub fn new() -> Self {
         Self {
@@ -169,18 +324,103 @@ impl RssFeedParser {
         Self { config }
     }
 
-    async fn parse_internal<R: BufRead>(
+    /// 开启或关闭未声明语言时的语言检测
+    pub fn with_language_detection(mut self, enabled: bool) -> Self {
+        self.config = self.config.with_language_detection(enabled);
+        self
+    }
+
+    /// 开启或关闭未处理命名空间元素的记录，记录在播客和剧集的 `extra` 中
+    ///
+    /// 值为元素文本；带属性的元素记录为属性对象，文本保存在 `#text` 中；
+    /// 重复出现的元素记录为数组。
+    pub fn with_unknown_tag_capture(mut self, enabled: bool) -> Self {
+        self.config = self.config.with_unknown_tag_capture(enabled);
+        self
+    }
+
+    /// 开启或关闭 Media RSS 解析（默认开启）
+    ///
+    /// 开启时，没有 `<enclosure>` 的剧集使用 `<media:content>` 的 `url`、`type` 和 `fileSize`
+    /// 作为 enclosure，`<media:thumbnail>` 在没有 `<itunes:image>` 时作为剧集封面。
+    pub fn with_media_rss(mut self, enabled: bool) -> Self {
+        self.config = self.config.with_media_rss(enabled);
+        self
+    }
+
+    /// 设置标题、描述、摘要和副标题的长度上限，超长的文本会被截断
+    pub fn with_text_limits(mut self, text_limits: TextLimits) -> Self {
+        self.config = self.config.with_text_limits(text_limits);
+        self
+    }
+
+    /// 删除 enclosure 和 link 地址中的指定查询参数（如 `utm_*`、会话 id）
+    ///
+    /// 默认不删除任何参数：部分平台（如喜马拉雅）的播放地址依赖查询参数。
+    pub fn with_stripped_tracking_params(mut self, params: Vec<String>) -> Self {
+        self.config = self.config.with_stripped_tracking_params(params);
+        self
+    }
+
+    /// 开启或关闭缺失 guid 的生成（默认开启）
+    ///
+    /// 开启时，没有 `<guid>` 的剧集使用 enclosure 地址的哈希作为 guid；没有 enclosure 时
+    /// 使用 link 和发布时间。同一剧集每次解析得到相同的 guid，便于去重。
+    pub fn with_guid_synthesis(mut self, enabled: bool) -> Self {
+        self.config = self.config.with_guid_synthesis(enabled);
+        self
+    }
+
+    /// 开启或关闭校验失败剧集的跳过（默认关闭）
+    ///
+    /// 开启时，校验失败的剧集（如标题为空）只跳过该剧集并记录一条解析警告，
+    /// 其余剧集照常返回；关闭时整个 feed 解析失败。
+    pub fn with_skip_invalid_items(mut self, enabled: bool) -> Self {
+        self.config = self.config.with_skip_invalid_items(enabled);
+        self
+    }
+
+    /// 限制每个 feed 最多保留的剧集数量，`None` 表示不限制（默认）
+    ///
+    /// 只保留 feed 中最前面的 N 个剧集（feed 通常按时间倒序），其余剧集不再解析校验。
+    pub fn with_max_episodes(mut self, max_episodes: Option<usize>) -> Self {
+        self.config = self.config.with_max_episodes(max_episodes);
+        self
+    }
+
+    /// 开启或关闭 enclosure 的音频检查（默认关闭）
+    ///
+    /// 开启时，`type` 不是 `audio/*`（没有 type 时 URL 不是音频扩展名）的 enclosure
+    /// 在严格模式下让解析失败，否则记录一条解析警告；非音频的 `<media:content>` 直接忽略。
+    pub fn with_audio_enclosure_check(mut self, enabled: bool) -> Self {
+        self.config = self.config.with_audio_enclosure_check(enabled);
+        self
+    }
+
+    /// 解析 feed，并返回解析过程中收集到的非致命警告
+    ///
+    /// 警告包括缺失的封面图、无法解析的日期、被跳过的 item 和被忽略的 enclosure，
+    /// 无需开启 debug 日志即可了解 feed 哪些部分没有被完整解析。
+    pub async fn parse_with_warnings(
         &self,
-        content: R,
+        content: &[u8],
         url: &str,
-    ) -> AppResult<(NewPodcast, Vec<NewEpisode>)> {
-        let mut reader = Reader::from_reader(content);
+    ) -> AppResult<(NewPodcast, Vec<NewEpisode>, Vec<ParseWarning>)> {
+        self.parse_feed(content, url)
+            .await
+            .map(|feed| (feed.podcast, feed.episodes, feed.warnings))
+    }
+
+    async fn parse_feed(&self, content: &[u8], url: &str) -> AppResult<ParsedFeed> {
+        let content = transcode_to_utf8(strip_leading_noise(content));
+        let mut reader = Reader::from_reader(content.as_ref());
         // reader.trim_text(true);
         reader.expand_empty_elements(true); // 展开空标签
 
         let mut state = RssParserState::new(url.to_string());
         state.podcast = Some(NewPodcast {
             rss_feed_url: Some(url.to_string()),
+            blocked: Some(false),
             ..Default::default()
         });
 
@@ -219,6 +459,7 @@ impl RssFeedParser {
                 }
                 Ok(Event::Eof) => {
                     debug!("Reached end of RSS feed");
+                    self.handle_unterminated_feed(&mut state)?;
                     break;
                 }
                 Err(e) => {
@@ -246,13 +487,52 @@ impl RssFeedParser {
         })?;
 
         state.validate_podcast(podcast).map_err(AppError::from)?;
-        let podcast = state.podcast.unwrap();
+        let mut podcast = state.podcast.unwrap();
+        drop_unnamed_persons(&mut podcast);
+        if podcast.image_url.is_none() {
+            state.warnings.push(ParseWarning {
+                field: "image_url".to_string(),
+                path: "rss/channel".to_string(),
+                message: "Podcast has no itunes:image".to_string(),
+            });
+        }
+
+        // feed 声明的语言优先，仅在缺失时检测
+        let has_language = podcast.language.as_deref().is_some_and(|l| !l.is_empty());
+        if self.config.detect_language && !has_language {
+            let text = format!(
+                "{} {}",
+                podcast.title,
+                podcast.description.as_deref().unwrap_or_default()
+            );
+            podcast.language = detect_language(&text);
+        }
+
+        if state.skipped_episodes > 0 {
+            debug!(
+                "Reached max_episodes for {}, kept {} and skipped {} episodes",
+                url,
+                state.episodes.len(),
+                state.skipped_episodes
+            );
+        }
+
+        let (podcast_coverage, episode_coverage) = field_coverage(&podcast, &state.episodes);
+        crate::metrics::record_parse_field_coverage(url, "podcast", podcast_coverage);
+        if let Some(episode_coverage) = episode_coverage {
+            crate::metrics::record_parse_field_coverage(url, "episode", episode_coverage);
+        }
 
         debug!("Successfully parsed RSS feed:");
         // debug!("- Podcast: {:#?}", podcast);
         // debug!("- Episodes: {:#?}", state.episodes);
 
-        Ok((podcast, state.episodes))
+        Ok(ParsedFeed {
+            podcast,
+            episodes: state.episodes,
+            warnings: state.warnings,
+            new_feed_url: state.new_feed_url,
+        })
     }
 
     fn handle_start_event(
@@ -261,17 +541,25 @@ impl RssFeedParser {
         tag_name: String,
         attributes: Vec<(String, String)>,
     ) -> AppResult<()> {
+        // 未闭合的前一个元素（如包含子元素）在这里写入
+        self.flush_extra(state);
         match tag_name.as_str() {
             "channel" => {
                 state.current_state = ParsingState::InPodcast;
+                // 未声明 <itunes:block> 视为未屏蔽，重新爬取时会解除之前的屏蔽
                 state.podcast = Some(NewPodcast {
                     rss_feed_url: Some(state.context.url.clone()),
+                    blocked: Some(false),
                     ..Default::default()
                 });
             }
             "item" => {
                 state.current_state = ParsingState::InEpisode;
-                state.current_episode = Some(NewEpisode::default());
+                state.current_episode = Some(NewEpisode {
+                    blocked: Some(false),
+                    ..Default::default()
+                });
+                state.media_enclosure = false;
             }
             _ => {
                 self.handle_start_event_internal(state, attributes)?;
@@ -285,14 +573,62 @@ impl RssFeedParser {
         state: &mut RssParserState,
         attributes: Vec<(String, String)>,
     ) -> AppResult<()> {
-        match state.current_state {
+        let capture = self.config.capture_unknown_tags && state.current_tag.contains(':');
+        let captured_attributes = if capture {
+            attributes
+                .iter()
+                .map(|(key, value)| (key.clone(), value.as_str().into()))
+                .collect()
+        } else {
+            serde_json::Map::new()
+        };
+        let handled = match state.current_state {
             ParsingState::InPodcast => self.handle_podcast_start(state, attributes)?,
             ParsingState::InEpisode => self.handle_episode_start(state, attributes)?,
-            _ => {}
+            _ => true,
+        };
+        if capture {
+            state.pending_extra = Some(PendingExtra {
+                tag: state.current_tag.clone(),
+                attributes: captured_attributes,
+                text: String::new(),
+                handled,
+            });
         }
         Ok(())
     }
 
+    // 把结束的未处理元素写入当前播客或剧集的 extra
+    fn flush_extra(&self, state: &mut RssParserState) {
+        let Some(pending) = state.pending_extra.take() else {
+            return;
+        };
+        let text = pending.text.trim();
+        if pending.handled || (pending.attributes.is_empty() && text.is_empty()) {
+            return;
+        }
+        let value = if pending.attributes.is_empty() {
+            serde_json::Value::from(text)
+        } else {
+            let mut attributes = pending.attributes;
+            if !text.is_empty() {
+                attributes.insert("#text".to_string(), text.into());
+            }
+            serde_json::Value::Object(attributes)
+        };
+        let extra = match state.current_state {
+            ParsingState::InPodcast => state.podcast.as_mut().map(|podcast| &mut podcast.extra),
+            ParsingState::InEpisode => state
+                .current_episode
+                .as_mut()
+                .map(|episode| &mut episode.extra),
+            _ => None,
+        };
+        if let Some(extra) = extra {
+            insert_extra(extra, pending.tag, value);
+        }
+    }
+
     fn handle_text_event(&self, event: &BytesText, state: &mut RssParserState) -> AppResult<()> {
         let text = event.unescape().map_err(|e| {
             AppError::from(ParseError::new(
@@ -314,24 +650,74 @@ impl RssFeedParser {
         }
         // debug_info!("TEXT EVENT", &text, &state);
 
-        match state.current_state {
+        let handled = match state.current_state {
             // Podcast 字段
             ParsingState::InPodcast => {
-                self.handle_podcast_text(state, &text)?;
+                let text = self.limit_text_length(state, &text);
+                self.handle_podcast_text(state, &text)?
             }
             // Episode 字段
             ParsingState::InEpisode => {
-                self.handle_episode_text(state, &text)?;
+                let text = self.limit_text_length(state, &text);
+                self.handle_episode_text(state, &text)?
+            }
+            _ => true,
+        };
+        if let Some(pending) = state
+            .pending_extra
+            .as_mut()
+            .filter(|pending| pending.tag == state.current_tag)
+        {
+            if handled {
+                pending.handled = true;
+            } else {
+                pending.text.push_str(&text);
             }
-            _ => {}
         }
         Ok(())
     }
 
+    // 超过长度上限的文本字段截断到上限（含结尾的 "…"），并记录警告
+    fn limit_text_length<'a>(&self, state: &mut RssParserState, text: &'a str) -> Cow<'a, str> {
+        let limits = &self.config.text_limits;
+        let (field, limit) = match state.current_tag.as_str() {
+            "title" => ("title", limits.title),
+            "description" => ("description", limits.description),
+            "itunes:summary" => ("summary", limits.summary),
+            "itunes:subtitle" => ("subtitle", limits.subtitle),
+            _ => return Cow::Borrowed(text),
+        };
+        let Some(limit) = limit else {
+            return Cow::Borrowed(text);
+        };
+        let length = text.chars().count();
+        if length <= limit {
+            return Cow::Borrowed(text);
+        }
+        let mut truncated: String = text.chars().take(limit.saturating_sub(1)).collect();
+        truncated.push('…');
+        let warning = state.context.warning(
+            field,
+            format!(
+                "Truncated {} from {} to {} characters",
+                field, length, limit
+            ),
+        );
+        state.warnings.push(warning);
+        Cow::Owned(truncated)
+    }
+
     fn handle_end_event(&self, state: &mut RssParserState, event: &BytesEnd) -> AppResult<()> {
         let name = event.name();
         let tag_name = String::from_utf8_lossy(name.as_ref()).into_owned();
         state.context.pop_element();
+        if state
+            .pending_extra
+            .as_ref()
+            .is_some_and(|pending| pending.tag == tag_name)
+        {
+            self.flush_extra(state);
+        }
 
         match (tag_name.as_str(), &state.current_state) {
             ("channel", ParsingState::InPodcast) => {
@@ -386,14 +772,21 @@ impl RssFeedParser {
         Ok((tag_name, attributes))
     }
 
-    fn handle_podcast_text(&self, state: &mut RssParserState, text: &str) -> AppResult<()> {
+    // 返回 false 表示该元素没有对应的字段
+    fn handle_podcast_text(&self, state: &mut RssParserState, text: &str) -> AppResult<bool> {
         let (tag_name, podcast_mut, feed_url) = get_context_as_mut(state)?;
         let podcast = podcast_mut
             .downcast_mut::<NewPodcast>()
             .ok_or_else(|| make_invalid_url_error(feed_url, "Podcast not found", None))?;
+        let mut invalid_date = false;
+        let mut new_feed_url = None;
+        let mut handled = true;
         match tag_name {
             "title" => update_field(&mut podcast.title, text),
             "description" => update_field_option(&mut podcast.description, text),
+            "itunes:new-feed-url" if !text.trim().is_empty() => {
+                new_feed_url = Some(text.trim().to_string())
+            }
             "language" => update_field_option(&mut podcast.language, text),
             "copyright" => update_field_option(&mut podcast.copyright, text),
             "itunes:author" => update_field_option(&mut podcast.author, text),
@@ -402,39 +795,79 @@ impl RssFeedParser {
             "itunes:category" => add_to_vec_option(&mut podcast.category, text),
             "itunes:keywords" => add_to_vec_option(&mut podcast.keywords, text),
             "itunes:explicit" => podcast.explicit = parse_bool(text),
+            "itunes:block" => podcast.blocked = parse_bool(text),
             "itunes:summary" => update_field_option(&mut podcast.summary, text),
             "itunes:subtitle" => update_field_option(&mut podcast.subtitle, text),
-            "link" => {
-                self.check_url(text, feed_url)?;
-                update_field_option(&mut podcast.link, text);
+            // lastBuildDate 优先，频道的 pubDate 仅在没有 lastBuildDate 时使用
+            "lastBuildDate" => match parse_date(text) {
+                Some(date) => podcast.last_build_date = Some(date),
+                None => invalid_date = true,
+            },
+            "pubDate" => match parse_date(text) {
+                Some(date) if podcast.last_build_date.is_none() => {
+                    podcast.last_build_date = Some(date)
+                }
+                Some(_) => {}
+                None => invalid_date = true,
+            },
+            "podcast:funding" => update_field_option(&mut podcast.funding_text, text),
+            "podcast:person" => {
+                let person = podcast
+                    .persons
+                    .as_mut()
+                    .and_then(|persons| persons.as_array_mut())
+                    .and_then(|persons| persons.last_mut())
+                    .and_then(|person| person.as_object_mut());
+                if let Some(person) = person {
+                    person.insert("name".to_string(), text.into());
+                }
             }
-            _ => {}
+            "link" => podcast.link = Some(self.checked_link_url(text, feed_url)?),
+            _ => handled = false,
         }
-        Ok(())
+        if invalid_date {
+            let warning = state
+                .context
+                .warning("last_build_date", format!("Unparseable date: {}", text));
+            state.warnings.push(warning);
+        }
+        if new_feed_url.is_some() {
+            state.new_feed_url = new_feed_url;
+        }
+        Ok(handled)
     }
 
-    fn handle_episode_text(&self, state: &mut RssParserState, text: &str) -> AppResult<()> {
+    fn handle_episode_text(&self, state: &mut RssParserState, text: &str) -> AppResult<bool> {
         let (tag_name, episode_mut, feed_url) = get_context_as_mut(state)?;
         let episode = episode_mut
             .downcast_mut::<NewEpisode>()
             .ok_or_else(|| make_invalid_url_error(feed_url, "Episode not found", None))?;
+        let mut invalid_date = false;
+        let mut handled = true;
         match tag_name {
             "title" => update_field(&mut episode.title, text),
             "description" => update_field_option(&mut episode.description, text),
-            "pubDate" => episode.pub_date = parse_date(text),
+            "pubDate" => {
+                episode.pub_date = parse_date(text);
+                invalid_date = episode.pub_date.is_none();
+            }
             "guid" => update_field_option(&mut episode.guid, text),
             "itunes:duration" => update_field_option(&mut episode.duration, text),
             "itunes:author" => update_field_option(&mut episode.author, text),
             "itunes:subtitle" => update_field_option(&mut episode.subtitle, text),
             "itunes:summary" => update_field_option(&mut episode.summary, text),
             "itunes:explicit" => episode.explicit = parse_bool(text),
-            "link" => {
-                self.check_url(text, feed_url)?;
-                update_field_option(&mut episode.link, text);
-            }
-            _ => {}
+            "itunes:block" => episode.blocked = parse_bool(text),
+            "link" => episode.link = Some(self.checked_link_url(text, feed_url)?),
+            _ => handled = false,
         }
-        Ok(())
+        if invalid_date {
+            let warning = state
+                .context
+                .warning("pub_date", format!("Unparseable date: {}", text));
+            state.warnings.push(warning);
+        }
+        Ok(handled)
     }
 
     fn handle_enclosure(
@@ -451,36 +884,33 @@ impl RssFeedParser {
             )
         })?;
 
-        let mut found_url = ",url not found";
-        let mut error_msg = String::new();
+        let mut url = None;
+        let mut mime_type = None;
+        let mut length = None;
+        // 严格模式下汇总所有问题后一起报错
+        let mut problems = Vec::new();
         for (key, value) in attributes {
             match key.as_str() {
                 "url" => {
-                    // 先解码 XML 实体
-                    let decoded_url = value.replace("&amp;", "&");
-                    let normalized_url = if decoded_url.starts_with("http") {
-                        // 保持原始 URL 不变，因为喜马拉雅的 URL 包含特殊的查询参数
-                        decoded_url
-                    } else {
-                        decoded_url.replace("//", "/")
-                    };
-                    self.check_url(&normalized_url, &state.context.url)?;
-                    // debug!("Found enclosure URL: {}", normalized_url);
-                    update_field_option(&mut episode.enclosure_url, &normalized_url);
-                    found_url = "";
+                    url = Some(self.checked_link_url(&value, &state.context.url)?);
                 }
                 "type" => {
                     // debug!("Found enclosure type: {}", value);
-                    update_field_option(&mut episode.enclosure_type, &value);
+                    mime_type = Some(value);
                 }
                 "length" => {
-                    if let Ok(length) = value.parse() {
-                        // debug!("Found enclosure length: {}", length);
-                        episode.enclosure_length = Some(length);
+                    if let Ok(parsed) = value.parse() {
+                        // debug!("Found enclosure length: {}", parsed);
+                        length = Some(parsed);
                     } else {
                         debug!("Failed to parse enclosure length: {}", value);
                         if self.config.strict_mode {
-                            error_msg = format!("Invalid enclosure length: {}", value);
+                            problems.push(format!("Invalid enclosure length: {}", value));
+                        } else {
+                            state.warnings.push(state.context.warning(
+                                "enclosure_length",
+                                format!("Invalid enclosure length: {}", value),
+                            ));
                         }
                     }
                 }
@@ -489,28 +919,214 @@ impl RssFeedParser {
                 }
             }
         }
-        error_msg.push_str(found_url);
-        if !error_msg.is_empty() && self.config.strict_mode {
+        if url.is_none() {
+            if self.config.strict_mode {
+                problems.push("Enclosure has no url".to_string());
+            } else {
+                state.warnings.push(
+                    state
+                        .context
+                        .warning("enclosure_url", "Enclosure has no url"),
+                );
+            }
+        }
+        if !problems.is_empty() {
             return Err(AppError::from(ParseError::new(
                 ParseErrorKind::MissingField,
-                error_msg,
+                problems.join(", "),
                 &state.context.url,
                 None,
             )));
         }
 
+        if let Some(enclosure_url) = &url {
+            if self.config.require_audio_enclosure
+                && !is_audio_enclosure(mime_type.as_deref(), enclosure_url)
+            {
+                let message = format!(
+                    "Enclosure is not audio: {} (type: {})",
+                    enclosure_url,
+                    mime_type.as_deref().unwrap_or("none")
+                );
+                if self.config.strict_mode {
+                    return Err(AppError::from(ParseError::new(
+                        ParseErrorKind::InvalidFormat,
+                        message,
+                        &state.context.url,
+                        None,
+                    )));
+                }
+                // 非严格模式下忽略该 enclosure
+                warn!("{}", message);
+                state
+                    .warnings
+                    .push(state.context.warning("enclosure_url", message));
+                return Ok(());
+            }
+        }
+
+        // 同一 item 可能有多个 enclosure（mp3 + m4a、视频 + 音频），只保留最合适的一个
+        let replace = match (&url, episode.enclosure_url.as_deref()) {
+            // 真正的 <enclosure> 优先于 <media:content>
+            (Some(_), Some(_)) if state.media_enclosure => true,
+            (Some(url), Some(current)) => {
+                enclosure_rank(mime_type.as_deref(), url)
+                    > enclosure_rank(episode.enclosure_type.as_deref(), current)
+            }
+            (Some(_), None) => true,
+            (None, current) => current.is_none(),
+        };
+        if replace {
+            if url.is_some() {
+                episode.enclosure_url = url;
+                state.media_enclosure = false;
+            }
+            episode.enclosure_type = mime_type;
+            episode.enclosure_length = length;
+        }
+
+        Ok(())
+    }
+
+    // <media:content> 只在剧集没有 <enclosure> 时使用，图片等非音视频内容忽略
+    fn handle_media_content(
+        &self,
+        state: &mut RssParserState,
+        attributes: Vec<(String, String)>,
+    ) -> AppResult<()> {
+        let Some(url) = get_attribute_value(&attributes, "url") else {
+            return Ok(());
+        };
+        let mime_type = get_attribute_value(&attributes, "type");
+        let is_media = |value: &str| {
+            ["audio", "video"]
+                .iter()
+                .any(|medium| value.split('/').next() == Some(medium))
+        };
+        if !get_attribute_value(&attributes, "medium")
+            .or_else(|| mime_type.clone())
+            .is_none_or(|value| is_media(&value))
+        {
+            return Ok(());
+        }
+        let url = strip_query_params(
+            &resolve_url(&url, &state.context.url),
+            &self.config.strip_enclosure_tracking_params,
+        );
+        if self.config.require_audio_enclosure && !is_audio_enclosure(mime_type.as_deref(), &url) {
+            return Ok(());
+        }
+        self.check_url(&url, &state.context.url)?;
+
+        let Some(episode) = state.current_episode.as_mut() else {
+            return Ok(());
+        };
+        // 同一剧集有多个 <media:content>（如 media:group 中的不同格式）时保留最合适的一个
+        let replace = match episode.enclosure_url.as_deref() {
+            None => true,
+            Some(current) if state.media_enclosure => {
+                enclosure_rank(mime_type.as_deref(), &url)
+                    > enclosure_rank(episode.enclosure_type.as_deref(), current)
+            }
+            Some(_) => false,
+        };
+        if replace {
+            episode.enclosure_url = Some(url);
+            episode.enclosure_type = mime_type;
+            episode.enclosure_length =
+                get_attribute_value(&attributes, "fileSize").and_then(|value| value.parse().ok());
+            state.media_enclosure = true;
+        }
         Ok(())
     }
 
     fn handle_item_end(&self, state: &mut RssParserState) -> AppResult<()> {
-        if let Some(episode) = state.current_episode.take() {
+        if let Some(mut episode) = state.current_episode.take() {
+            // 被跳过的 item 也占用位置，保证 feed_position 与原始顺序一致
+            episode.feed_position = Some(state.item_position);
+            state.item_position += 1;
+            // 达到上限后不再收集（feed 通常按时间倒序，保留最前面的 N 个）
+            if let Some(max_episodes) = self.config.max_episodes {
+                if state.episodes.len() >= max_episodes {
+                    state.skipped_episodes += 1;
+                    return Ok(());
+                }
+            }
+            if episode.guid.is_none() && self.config.synthesize_guid {
+                episode.guid = synthesize_guid(&episode);
+            }
             // debug!("Finishing episode: {:?}", episode);
-            state.validate_episode(&episode)?;
+            if let Err(e) = state.validate_episode(&episode) {
+                if !self.config.skip_invalid_items {
+                    return Err(e);
+                }
+                warn!("Skipping invalid episode in {}: {}", state.context.url, e);
+                state.warnings.push(ParseWarning {
+                    field: "item".to_string(),
+                    path: format!("{}/item", state.context.current_path()),
+                    message: format!(
+                        "Skipped item at position {}: {}",
+                        state.item_position - 1,
+                        e
+                    ),
+                });
+                return Ok(());
+            }
             state.episodes.push(episode);
         }
         Ok(())
     }
 
+    // 截断或缺少 </channel> 的 feed：保留尚未结束的剧集，并记录警告
+    fn handle_unterminated_feed(&self, state: &mut RssParserState) -> AppResult<()> {
+        if !matches!(
+            state.current_state,
+            ParsingState::InPodcast | ParsingState::InEpisode
+        ) {
+            return Ok(());
+        }
+        let unclosed = state.context.current_path();
+        warn!(
+            "Feed {} ended inside {}, keeping what was parsed",
+            state.context.url, unclosed
+        );
+        state.warnings.push(ParseWarning {
+            field: "channel".to_string(),
+            path: unclosed,
+            message: "Feed ended before </channel>".to_string(),
+        });
+        // 回到 channel 层级，按正常结束处理最后一个 item
+        while state
+            .context
+            .element_path
+            .last()
+            .is_some_and(|name| name != "channel")
+        {
+            state.context.pop_element();
+        }
+        if state.current_state == ParsingState::InEpisode {
+            self.handle_item_end(state)?;
+        }
+        state.current_state = ParsingState::Finished;
+        Ok(())
+    }
+
+    // 相对地址先基于 feed 地址补全再校验，返回补全后的 URL
+    fn checked_url(&self, url: &str, feed_url: &str) -> AppResult<String> {
+        let url = resolve_url(url, feed_url);
+        self.check_url(&url, feed_url)?;
+        Ok(url)
+    }
+
+    // 与 checked_url 相同，并删除配置的跟踪参数，用于 enclosure 和 link
+    fn checked_link_url(&self, url: &str, feed_url: &str) -> AppResult<String> {
+        let url = self.checked_url(url, feed_url)?;
+        Ok(strip_query_params(
+            &url,
+            &self.config.strip_enclosure_tracking_params,
+        ))
+    }
+
     fn check_url(&self, text: &str, feed_url: &str) -> AppResult<()> {
         if self.config.validate_urls {
             validate_url(text).map_err(|e| {
@@ -524,7 +1140,7 @@ impl RssFeedParser {
         &self,
         state: &mut RssParserState,
         attributes: Vec<(String, String)>,
-    ) -> AppResult<()> {
+    ) -> AppResult<bool> {
         let (tag_name, podcast_mut, feed_url) = get_context_as_mut(state)?;
         let podcast = podcast_mut
             .downcast_mut::<NewPodcast>()
@@ -532,14 +1148,12 @@ impl RssFeedParser {
         match tag_name {
             "link" => {
                 if let Some(url) = get_attribute_value(&attributes, "href") {
-                    self.check_url(&url, feed_url)?;
-                    update_field_option(&mut podcast.link, &url);
+                    podcast.link = Some(self.checked_link_url(&url, feed_url)?);
                 }
             }
             "itunes:image" => {
                 if let Some(url) = get_attribute_value(&attributes, "href") {
-                    self.check_url(&url, feed_url)?;
-                    update_field_option(&mut podcast.image_url, &url);
+                    podcast.image_url = Some(self.checked_url(&url, feed_url)?);
                 }
             }
             "itunes:category" => {
@@ -547,31 +1161,94 @@ impl RssFeedParser {
                     add_to_vec_option(&mut podcast.category, &text);
                 }
             }
-            _ => {}
+            "podcast:funding" => {
+                if let Some(url) = get_attribute_value(&attributes, "url") {
+                    self.check_url(&url, feed_url)?;
+                    update_field_option(&mut podcast.funding_url, &url);
+                }
+            }
+            "podcast:person" => {
+                // 姓名在标签文本中，这里先记录属性
+                let mut person = serde_json::Map::new();
+                for key in ["role", "group", "img", "href"] {
+                    if let Some(value) = get_attribute_value(&attributes, key) {
+                        person.insert(key.to_string(), value.into());
+                    }
+                }
+                let persons = podcast
+                    .persons
+                    .get_or_insert_with(|| serde_json::Value::Array(Vec::new()));
+                if let Some(persons) = persons.as_array_mut() {
+                    persons.push(person.into());
+                }
+            }
+            _ => return Ok(false),
         }
-        Ok(())
+        Ok(true)
     }
 
     fn handle_episode_start(
         &self,
         state: &mut RssParserState,
         attributes: Vec<(String, String)>,
-    ) -> AppResult<()> {
+    ) -> AppResult<bool> {
         let (tag_name, episode_mut, feed_url) = get_context_as_mut(state)?;
         let episode = episode_mut
             .downcast_mut::<NewEpisode>()
             .ok_or_else(|| make_invalid_url_error(feed_url, "Episode not found", None))?;
         match tag_name {
             "enclosure" => self.handle_enclosure(state, attributes)?,
+            "media:content" if self.config.parse_media_rss => {
+                self.handle_media_content(state, attributes)?
+            }
+            "media:thumbnail" if self.config.parse_media_rss => {
+                // <itunes:image> 会覆盖缩略图
+                if let Some(url) = get_attribute_value(&attributes, "url") {
+                    if episode.episode_image_url.is_none() {
+                        episode.episode_image_url = Some(self.checked_url(&url, feed_url)?);
+                    }
+                }
+            }
             "itunes:image" => {
                 if let Some(url) = get_attribute_value(&attributes, "href") {
+                    episode.episode_image_url = Some(self.checked_url(&url, feed_url)?);
+                }
+            }
+            "podcast:transcript" => {
+                if let Some(url) = get_attribute_value(&attributes, "url") {
                     self.check_url(&url, feed_url)?;
-                    update_field_option(&mut episode.episode_image_url, &url);
+                    let transcript_type = get_attribute_value(&attributes, "type");
+                    // 优先使用 WebVTT 字幕，否则保留第一个
+                    let is_vtt = transcript_type.as_deref() == Some("text/vtt");
+                    let has_vtt = episode.transcript_type.as_deref() == Some("text/vtt");
+                    if episode.transcript_url.is_none() || (is_vtt && !has_vtt) {
+                        episode.transcript_url = Some(url);
+                        episode.transcript_type = transcript_type;
+                    }
                 }
             }
-            _ => {}
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+}
+
+// 重复出现的标签保存为数组
+fn insert_extra(extra: &mut Option<serde_json::Value>, tag: String, value: serde_json::Value) {
+    let serde_json::Value::Object(map) =
+        extra.get_or_insert_with(|| serde_json::Value::Object(serde_json::Map::new()))
+    else {
+        return;
+    };
+    match map.get_mut(&tag) {
+        None => {
+            map.insert(tag, value);
+        }
+        Some(serde_json::Value::Array(values)) => values.push(value),
+        Some(existing) => {
+            let first = existing.take();
+            *existing = serde_json::Value::Array(vec![first, value]);
         }
-        Ok(())
     }
 }
 
@@ -613,38 +1290,84 @@ fn get_context_as_mut(
 #[async_trait]
 impl Parser<(NewPodcast, Vec<NewEpisode>)> for RssFeedParser {
     async fn parse(&self, content: &[u8], url: &str) -> AppResult<(NewPodcast, Vec<NewEpisode>)> {
-        let cursor = std::io::Cursor::new(content);
-        self.parse_internal(cursor, url).await
+        self.parse_with_warnings(content, url)
+            .await
+            .map(|(podcast, episodes, _warnings)| (podcast, episodes))
     }
 
     async fn parse_with_task(
         &self,
         task: &mut crate::crawler_refactor::task::Task,
     ) -> AppResult<(NewPodcast, Vec<NewEpisode>)> {
-        let url = task.payload.clone();
+        let url = task.get_feed_url().to_string();
         task.add_stage("parsing");
         let content = task
             .get_content()
             .ok_or_else(|| make_invalid_url_error(&url, "Task content is empty", None))?;
-        let cursor = std::io::Cursor::new(content);
-        let result: AppResult<(NewPodcast, Vec<NewEpisode>)> =
-            self.parse_internal(cursor, &url).await;
-        match &result {
-            Ok((podcast, episodes)) => {
+        match self.parse_feed(content, &url).await {
+            Ok(ParsedFeed {
+                podcast,
+                episodes,
+                warnings,
+                new_feed_url,
+            }) => {
+                // 警告随解析结果保存在任务中，可通过任务列表查看
                 let result_data = serde_json::json!({
                     "podcast": podcast,
-                    "episodes": episodes
+                    "episodes": episodes,
+                    "warnings": warnings,
                 });
                 task.complete_stage(result_data);
+                task.new_feed_url = new_feed_url;
+                Ok((podcast, episodes))
             }
             Err(e) => {
                 task.fail_stage(e.to_string());
+                Err(e)
             }
         }
-        result
     }
 }
 
+// 已填充字段所占比例；剧集取所有剧集的平均值，没有剧集时为 None
+fn field_coverage(podcast: &NewPodcast, episodes: &[NewEpisode]) -> (f64, Option<f64>) {
+    fn ratio(fields: &[bool]) -> f64 {
+        fields.iter().filter(|present| **present).count() as f64 / fields.len() as f64
+    }
+
+    let podcast_coverage = ratio(&[
+        podcast.image_url.is_some(),
+        podcast.category.is_some(),
+        podcast.keywords.is_some(),
+        podcast.summary.is_some(),
+        podcast.subtitle.is_some(),
+    ]);
+
+    if episodes.is_empty() {
+        return (podcast_coverage, None);
+    }
+    let episode_total: f64 = episodes
+        .iter()
+        .map(|episode| {
+            ratio(&[
+                episode.description.is_some(),
+                episode.pub_date.is_some(),
+                episode.guid.is_some(),
+                episode.enclosure_url.is_some(),
+                episode.duration.is_some(),
+                episode.episode_image_url.is_some(),
+                episode.summary.is_some(),
+                episode.subtitle.is_some(),
+                episode.keywords.is_some(),
+            ])
+        })
+        .sum();
+    (
+        podcast_coverage,
+        Some(episode_total / episodes.len() as f64),
+    )
+}
+
 fn make_invalid_url_error(
     url: &str,
     error_message: &str,
@@ -653,6 +1376,110 @@ fn make_invalid_url_error(
     ParseError::new(ParseErrorKind::InvalidFormat, error_message, url, error).into()
 }
 
+const AUDIO_EXTENSIONS: &[&str] = &[
+    ".mp3", ".m4a", ".aac", ".ogg", ".oga", ".opus", ".wav", ".flac",
+];
+
+// type 以 audio/ 开头；没有 type 时根据 URL 扩展名判断
+fn is_audio_enclosure(mime_type: Option<&str>, url: &str) -> bool {
+    match mime_type.map(str::trim).filter(|t| !t.is_empty()) {
+        Some(mime_type) => mime_type.to_ascii_lowercase().starts_with("audio/"),
+        None => {
+            let path = url
+                .split(['?', '#'])
+                .next()
+                .unwrap_or(url)
+                .to_ascii_lowercase();
+            AUDIO_EXTENSIONS.iter().any(|ext| path.ends_with(ext))
+        }
+    }
+}
+
+// enclosure 优先级：mp3/m4a 音频 > 其他音频 > 未知类型 > 视频
+fn enclosure_rank(mime_type: Option<&str>, url: &str) -> u8 {
+    let mime_type = mime_type.unwrap_or_default().trim().to_ascii_lowercase();
+    let path = url
+        .split(['?', '#'])
+        .next()
+        .unwrap_or(url)
+        .to_ascii_lowercase();
+    match mime_type.as_str() {
+        "audio/mpeg" | "audio/mp3" | "audio/mp4" | "audio/m4a" | "audio/x-m4a" => 4,
+        t if t.starts_with("audio/") => 3,
+        t if t.starts_with("video/") => 1,
+        _ if path.ends_with(".mp3") || path.ends_with(".m4a") => 4,
+        _ => 2,
+    }
+}
+
+// 根据 enclosure 地址（没有时用 link 加发布时间）生成确定的 guid，两者都没有时返回 None
+fn synthesize_guid(episode: &NewEpisode) -> Option<String> {
+    let key = match (&episode.enclosure_url, &episode.link) {
+        (Some(enclosure_url), _) => format!("enclosure:{}", enclosure_url),
+        (None, Some(link)) => format!(
+            "link:{}|{}",
+            link,
+            episode
+                .pub_date
+                .map(|pub_date| pub_date.to_rfc3339())
+                .unwrap_or_default()
+        ),
+        (None, None) => return None,
+    };
+    Some(format!(
+        "urn:sha256:{}",
+        crate::crawler_refactor::task::content_hash(key.as_bytes())
+    ))
+}
+
+// 去掉 feed 开头的 UTF-8 BOM 和空白，避免 XML 声明前的杂质导致解析失败
+fn strip_leading_noise(content: &[u8]) -> &[u8] {
+    let trim_start = |bytes: &[u8]| -> usize {
+        bytes
+            .iter()
+            .position(|b| !b.is_ascii_whitespace())
+            .unwrap_or(bytes.len())
+    };
+    let content = &content[trim_start(content)..];
+    let content = content.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(content);
+    &content[trim_start(content)..]
+}
+
+// 读取 XML 声明中的 encoding，非 UTF-8 时转码为 UTF-8；没有声明时按 UTF-8 处理
+fn transcode_to_utf8(content: &[u8]) -> Cow<'_, [u8]> {
+    let Some(label) = declared_encoding(content) else {
+        return Cow::Borrowed(content);
+    };
+    match Encoding::for_label(label.as_bytes()) {
+        Some(encoding) if encoding != UTF_8 => {
+            let (decoded, _, had_errors) = encoding.decode(content);
+            if had_errors {
+                debug!("Feed contains bytes invalid for encoding {}", label);
+            }
+            Cow::Owned(decoded.into_owned().into_bytes())
+        }
+        Some(_) => Cow::Borrowed(content),
+        None => {
+            warn!("Unknown feed encoding {}, assuming UTF-8", label);
+            Cow::Borrowed(content)
+        }
+    }
+}
+
+// 从 `<?xml ... encoding="..."?>` 中取出编码名
+fn declared_encoding(content: &[u8]) -> Option<String> {
+    if !content.starts_with(b"<?xml") {
+        return None;
+    }
+    let end = content.windows(2).position(|w| w == b"?>")?;
+    let declaration = std::str::from_utf8(&content[..end]).ok()?;
+    let (_, rest) = declaration.split_once("encoding")?;
+    let rest = rest.trim_start().strip_prefix('=')?.trim_start();
+    let quote = rest.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+    let value = &rest[1..];
+    value.find(quote).map(|end| value[..end].to_string())
+}
+
 fn make_invalid_scope_error(url: &str, error_message: &str) -> AppError {
     ParseError::new(ParseErrorKind::Other, error_message, url, None).into()
 }
@@ -666,6 +1493,17 @@ pub fn parse_bool(value: &str) -> Option<bool> {
     }
 }
 
+/// Detect the ISO 639-1 language code of a text, `None` if detection is unreliable
+pub fn detect_language(text: &str) -> Option<String> {
+    let info = whatlang::detect(text)?;
+    if !info.is_reliable() {
+        return None;
+    }
+    isolang::Language::from_639_3(info.lang().code())?
+        .to_639_1()
+        .map(str::to_string)
+}
+
 /// Clean HTML content
 pub fn clean_html(content: &str) -> String {
     use ammonia::clean;
@@ -686,6 +1524,76 @@ pub fn validate_url(url: &str) -> Result<(), Box<dyn std::error::Error + Send +
     Err(Box::new(url::ParseError::EmptyHost))
 }
 
+/// Resolve a link, image or enclosure URL against the URL of its feed
+///
+/// Absolute URLs are kept verbatim, protocol-relative ones (`//host/path`) get
+/// `https:`, and anything else is joined to `feed_url`.
+pub fn resolve_url(url: &str, feed_url: &str) -> String {
+    use url::Url;
+
+    // 先解码 XML 实体
+    let url = url.trim().replace("&amp;", "&");
+    if url.starts_with("//") {
+        return format!("https:{}", url);
+    }
+    match Url::parse(&url) {
+        // 保持原始 URL 不变，因为喜马拉雅的 URL 包含特殊的查询参数
+        Ok(_) => url,
+        Err(url::ParseError::RelativeUrlWithoutBase) => Url::parse(feed_url)
+            .and_then(|base| base.join(&url))
+            .map(String::from)
+            .unwrap_or(url),
+        // 无法解析的 URL 原样返回，由 validate_urls 决定是否报错
+        Err(_) => url,
+    }
+}
+
+/// Remove the query parameters named in `params` from `url`
+///
+/// Names match case-insensitively, and a trailing `*` matches a prefix
+/// (`utm_*`). The rest of the URL is kept byte for byte.
+pub fn strip_query_params(url: &str, params: &[String]) -> String {
+    let Some((base, rest)) = url.split_once('?') else {
+        return url.to_string();
+    };
+    if params.is_empty() {
+        return url.to_string();
+    }
+    let (query, fragment) = match rest.split_once('#') {
+        Some((query, fragment)) => (query, Some(fragment)),
+        None => (rest, None),
+    };
+    let is_stripped = |pair: &str| {
+        let name = pair
+            .split('=')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        params.iter().any(|param| {
+            let param = param.to_ascii_lowercase();
+            match param.strip_suffix('*') {
+                Some(prefix) => name.starts_with(prefix),
+                None => name == param,
+            }
+        })
+    };
+    let kept: Vec<&str> = query
+        .split('&')
+        .filter(|pair| !pair.is_empty() && !is_stripped(pair))
+        .collect();
+
+    let mut stripped = base.to_string();
+    if !kept.is_empty() {
+        stripped.push('?');
+        stripped.push_str(&kept.join("&"));
+    }
+    if let Some(fragment) = fragment {
+        stripped.push('#');
+        stripped.push_str(fragment);
+    }
+    stripped
+}
+
 /// Parse date string to DateTime<Utc>
 pub fn parse_date(date_str: &str) -> Option<DateTime<Utc>> {
     use chrono::prelude::*;
@@ -770,8 +1678,868 @@ fn update_field_option(field: &mut Option<String>, text: &str) {
     *field = Some(text.to_string());
 }
 
+// 没有姓名的 <podcast:person> 没有意义，全部无效时不保存该字段
+fn drop_unnamed_persons(podcast: &mut NewPodcast) {
+    if let Some(serde_json::Value::Array(persons)) = &mut podcast.persons {
+        persons.retain(|person| person.get("name").is_some());
+        if persons.is_empty() {
+            podcast.persons = None;
+        }
+    }
+}
+
 fn add_to_vec_option(field: &mut Option<Vec<Option<String>>>, text: &str) {
     field
         .get_or_insert_with(Vec::new)
         .push(Some(text.to_string()));
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_parse_podcast_transcript() {
+        let parser = RssFeedParser::new();
+        let (_podcast, episodes) = parser
+            .parse(
+                include_bytes!("../../tests/data/transcript_feed.xml"),
+                "https://example.com/transcript_feed.xml",
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            episodes[0].transcript_url.as_deref(),
+            Some("https://example.com/transcripts/1.vtt")
+        );
+        assert_eq!(episodes[0].transcript_type.as_deref(), Some("text/vtt"));
+        assert_eq!(
+            episodes[1].transcript_url.as_deref(),
+            Some("https://example.com/transcripts/2.html")
+        );
+        assert_eq!(episodes[2].transcript_url, None);
+    }
+
+    #[tokio::test]
+    async fn test_detect_language_for_undeclared_feed() {
+        let content = include_bytes!("../../tests/data/undeclared_language_feed.xml");
+        let url = "https://example.com/garden.xml";
+
+        let (podcast, _) = RssFeedParser::new().parse(content, url).await.unwrap();
+        assert_eq!(podcast.language, None);
+
+        let parser =
+            RssFeedParser::with_config(ParserConfig::default().with_language_detection(true));
+        let (podcast, _) = parser.parse(content, url).await.unwrap();
+        assert_eq!(podcast.language.as_deref(), Some("en"));
+    }
+
+    #[tokio::test]
+    async fn test_declared_language_wins_over_detection() {
+        let rss = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <rss version="2.0">
+                <channel>
+                    <title>The Weekly Garden Hour</title>
+                    <description>Every week we walk through the garden with experienced growers, talking about soil and seasonal planting.</description>
+                    <language>fr</language>
+                </channel>
+            </rss>"#;
+
+        let parser =
+            RssFeedParser::with_config(ParserConfig::default().with_language_detection(true));
+        let (podcast, _) = parser
+            .parse(rss.as_bytes(), "https://example.com/garden.xml")
+            .await
+            .unwrap();
+        assert_eq!(podcast.language.as_deref(), Some("fr"));
+    }
+
+    #[tokio::test]
+    async fn test_prefers_audio_enclosure() {
+        let (_podcast, episodes) = RssFeedParser::new()
+            .parse(
+                include_bytes!("../../tests/data/multi_enclosure_feed.xml"),
+                "https://example.com/multi.xml",
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            episodes[0].enclosure_url.as_deref(),
+            Some("https://example.com/audio/multi-1.mp3")
+        );
+        assert_eq!(episodes[0].enclosure_type.as_deref(), Some("audio/mpeg"));
+        assert_eq!(episodes[0].enclosure_length, Some(1000));
+        assert_eq!(
+            episodes[1].enclosure_url.as_deref(),
+            Some("https://example.com/audio/multi-2.m4a")
+        );
+        assert_eq!(
+            episodes[2].enclosure_url.as_deref(),
+            Some("https://example.com/video/multi-3.mp4")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_media_rss_content_and_thumbnail() {
+        let content = include_bytes!("../../tests/data/media_rss_feed.xml");
+        let (_podcast, episodes) = RssFeedParser::new()
+            .parse(content, "https://example.com/media.xml")
+            .await
+            .unwrap();
+
+        // 只有 media:content 时作为 enclosure，缩略图作为剧集封面
+        assert_eq!(
+            episodes[0].enclosure_url.as_deref(),
+            Some("https://example.com/video/media-1.mp4")
+        );
+        assert_eq!(episodes[0].enclosure_type.as_deref(), Some("video/mp4"));
+        assert_eq!(episodes[0].enclosure_length, Some(5000));
+        assert_eq!(
+            episodes[0].episode_image_url.as_deref(),
+            Some("https://example.com/images/media-1.jpg")
+        );
+        // 真正的 enclosure 和 itunes:image 优先
+        assert_eq!(
+            episodes[1].enclosure_url.as_deref(),
+            Some("https://example.com/audio/media-2.mp3")
+        );
+        assert_eq!(episodes[1].enclosure_length, Some(2000));
+        assert_eq!(
+            episodes[1].episode_image_url.as_deref(),
+            Some("https://example.com/images/media-2.jpg")
+        );
+        // media:group 中忽略图片，优先音频
+        assert_eq!(
+            episodes[2].enclosure_url.as_deref(),
+            Some("https://example.com/audio/media-3.m4a")
+        );
+        assert_eq!(episodes[2].enclosure_length, Some(3000));
+
+        let (_podcast, episodes) = RssFeedParser::new()
+            .with_media_rss(false)
+            .parse(content, "https://example.com/media.xml")
+            .await
+            .unwrap();
+        assert_eq!(episodes[0].enclosure_url, None);
+        assert_eq!(episodes[0].episode_image_url, None);
+    }
+
+    const HTML_ENCLOSURE_FEED: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <rss version="2.0">
+            <channel>
+                <title>Broken Enclosures</title>
+                <item>
+                    <title>Landing Page</title>
+                    <enclosure url="https://example.com/episodes/1.html" type="text/html" length="100"/>
+                </item>
+                <item>
+                    <title>Untyped Audio</title>
+                    <enclosure url="https://example.com/episodes/2.mp3?ref=rss"/>
+                </item>
+            </channel>
+        </rss>"#;
+
+    #[tokio::test]
+    async fn test_html_enclosure_accepted_without_audio_check() {
+        let (_podcast, episodes) = RssFeedParser::new()
+            .parse(
+                HTML_ENCLOSURE_FEED.as_bytes(),
+                "https://example.com/feed.xml",
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            episodes[0].enclosure_url.as_deref(),
+            Some("https://example.com/episodes/1.html")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_html_enclosure_rejected_in_strict_mode() {
+        let parser = RssFeedParser::with_config(ParserConfig {
+            require_audio_enclosure: true,
+            ..ParserConfig::default()
+        });
+        let result = parser
+            .parse(
+                HTML_ENCLOSURE_FEED.as_bytes(),
+                "https://example.com/feed.xml",
+            )
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_html_enclosure_skipped_in_lenient_mode() {
+        let parser = RssFeedParser::with_config(ParserConfig {
+            require_audio_enclosure: true,
+            strict_mode: false,
+            ..ParserConfig::default()
+        });
+        let (_podcast, episodes) = parser
+            .parse(
+                HTML_ENCLOSURE_FEED.as_bytes(),
+                "https://example.com/feed.xml",
+            )
+            .await
+            .unwrap();
+        assert_eq!(episodes[0].enclosure_url, None);
+        assert_eq!(episodes[0].enclosure_type, None);
+        assert_eq!(
+            episodes[1].enclosure_url.as_deref(),
+            Some("https://example.com/episodes/2.mp3?ref=rss")
+        );
+    }
+
+    #[test]
+    fn test_resolve_url() {
+        let feed_url = "https://example.com/podcasts/feed.xml";
+        // 绝对 URL 保持不变，只解码 XML 实体
+        assert_eq!(
+            resolve_url("http://cdn.example.com/1.mp3?a=1&amp;b=2", feed_url),
+            "http://cdn.example.com/1.mp3?a=1&b=2"
+        );
+        // 协议相对 URL 补全为 https
+        assert_eq!(
+            resolve_url("//cdn.example.com/audio//1.mp3", feed_url),
+            "https://cdn.example.com/audio//1.mp3"
+        );
+        // 相对 URL 基于 feed 地址解析
+        assert_eq!(
+            resolve_url("/audio/1.mp3", feed_url),
+            "https://example.com/audio/1.mp3"
+        );
+        assert_eq!(
+            resolve_url("audio/1.mp3", feed_url),
+            "https://example.com/podcasts/audio/1.mp3"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_enclosure_url_cases() {
+        let feed = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <rss version="2.0">
+            <channel>
+                <title>Enclosure URLs</title>
+                <item>
+                    <title>Protocol Relative</title>
+                    <enclosure url="//cdn.example.com/1.mp3" type="audio/mpeg"/>
+                </item>
+                <item>
+                    <title>Relative</title>
+                    <enclosure url="episodes/2.mp3" type="audio/mpeg"/>
+                </item>
+                <item>
+                    <title>Missing</title>
+                    <enclosure type="audio/mpeg" length="abc"/>
+                </item>
+            </channel>
+        </rss>"#;
+        let url = "https://example.com/podcasts/feed.xml";
+        let lenient = RssFeedParser::with_config(ParserConfig {
+            strict_mode: false,
+            ..ParserConfig::default()
+        });
+        let (_podcast, episodes) = lenient.parse(feed.as_bytes(), url).await.unwrap();
+        let urls: Vec<_> = episodes
+            .iter()
+            .map(|e| e.enclosure_url.as_deref())
+            .collect();
+        assert_eq!(
+            urls,
+            vec![
+                Some("https://cdn.example.com/1.mp3"),
+                Some("https://example.com/podcasts/episodes/2.mp3"),
+                None,
+            ]
+        );
+
+        // 严格模式下缺少 url 的 enclosure 报告所有问题
+        let error = RssFeedParser::new()
+            .parse(feed.as_bytes(), url)
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("Invalid enclosure length: abc, Enclosure has no url"));
+    }
+
+    #[tokio::test]
+    async fn test_strip_enclosure_tracking_params() {
+        let feed = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <rss version="2.0">
+            <channel>
+                <title>Tracking</title>
+                <item>
+                    <title>Episode 1</title>
+                    <link>https://example.com/episodes/1?utm_source=rss&amp;id=1</link>
+                    <enclosure url="https://cdn.example.com/1.mp3?sign=abc&amp;UTM_Medium=feed&amp;sid=42#t=10" type="audio/mpeg"/>
+                </item>
+            </channel>
+        </rss>"#;
+        let url = "https://example.com/feed.xml";
+
+        // 默认保留所有查询参数
+        let (_podcast, episodes) = RssFeedParser::new()
+            .parse(feed.as_bytes(), url)
+            .await
+            .unwrap();
+        assert_eq!(
+            episodes[0].enclosure_url.as_deref(),
+            Some("https://cdn.example.com/1.mp3?sign=abc&UTM_Medium=feed&sid=42#t=10")
+        );
+
+        // 只删除列出的参数，播放所需的 sign 保留
+        let parser = RssFeedParser::new()
+            .with_stripped_tracking_params(vec!["utm_*".to_string(), "sid".to_string()]);
+        let (_podcast, episodes) = parser.parse(feed.as_bytes(), url).await.unwrap();
+        assert_eq!(
+            episodes[0].enclosure_url.as_deref(),
+            Some("https://cdn.example.com/1.mp3?sign=abc#t=10")
+        );
+        assert_eq!(
+            episodes[0].link.as_deref(),
+            Some("https://example.com/episodes/1?id=1")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_itunes_block() {
+        let feed = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <rss version="2.0" xmlns:itunes="http://www.itunes.com/dtds/podcast-1.0.dtd">
+            <channel>
+                <title>Blocked</title>
+                <itunes:block>Yes</itunes:block>
+                <item>
+                    <title>Episode 1</title>
+                    <itunes:block>yes</itunes:block>
+                </item>
+                <item>
+                    <title>Episode 2</title>
+                    <itunes:block>no</itunes:block>
+                </item>
+                <item>
+                    <title>Episode 3</title>
+                </item>
+            </channel>
+        </rss>"#;
+        let (podcast, episodes) = RssFeedParser::new()
+            .parse(feed.as_bytes(), "https://example.com/feed.xml")
+            .await
+            .unwrap();
+        assert_eq!(podcast.blocked, Some(true));
+        let blocked: Vec<Option<bool>> = episodes.iter().map(|episode| episode.blocked).collect();
+        // 未声明时视为未屏蔽
+        assert_eq!(blocked, vec![Some(true), Some(false), Some(false)]);
+    }
+
+    #[tokio::test]
+    async fn test_synthesized_guid_is_stable() {
+        let feed = |enclosure: &str| {
+            format!(
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+        <rss version="2.0">
+            <channel>
+                <title>No Guid</title>
+                <item>
+                    <title>Episode 1</title>
+                    <enclosure url="{}" type="audio/mpeg"/>
+                </item>
+                <item>
+                    <title>Episode 2</title>
+                    <link>https://example.com/episodes/2</link>
+                    <pubDate>Mon, 01 Jan 2024 00:00:00 +0000</pubDate>
+                </item>
+                <item>
+                    <title>Episode 3</title>
+                    <guid>original-guid</guid>
+                    <enclosure url="https://cdn.example.com/3.mp3" type="audio/mpeg"/>
+                </item>
+            </channel>
+        </rss>"#,
+                enclosure
+            )
+        };
+        let url = "https://example.com/feed.xml";
+        let parse = |content: String| async move {
+            RssFeedParser::new()
+                .parse(content.as_bytes(), url)
+                .await
+                .unwrap()
+                .1
+        };
+
+        // 多次解析同一 feed 得到相同的 guid
+        let first = parse(feed("https://cdn.example.com/1.mp3")).await;
+        let second = parse(feed("https://cdn.example.com/1.mp3")).await;
+        let guids = |episodes: &[NewEpisode]| {
+            episodes
+                .iter()
+                .map(|episode| episode.guid.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(guids(&first), guids(&second));
+        assert!(first[0]
+            .guid
+            .as_deref()
+            .is_some_and(|guid| guid.starts_with("urn:sha256:")));
+        // 没有 enclosure 时使用 link 和发布时间
+        assert!(first[1].guid.is_some());
+        assert_ne!(first[0].guid, first[1].guid);
+        // 已有的 guid 保持不变
+        assert_eq!(first[2].guid.as_deref(), Some("original-guid"));
+
+        // enclosure 不同时 guid 也不同
+        let other = parse(feed("https://cdn.example.com/other.mp3")).await;
+        assert_ne!(first[0].guid, other[0].guid);
+
+        // 关闭后保留缺失的 guid
+        let content = feed("https://cdn.example.com/1.mp3");
+        let (_podcast, episodes) = RssFeedParser::new()
+            .with_guid_synthesis(false)
+            .parse(content.as_bytes(), url)
+            .await
+            .unwrap();
+        assert_eq!(episodes[0].guid, None);
+        assert_eq!(episodes[1].guid, None);
+    }
+
+    #[tokio::test]
+    async fn test_relative_urls_resolved_against_feed() {
+        let feed = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <rss version="2.0" xmlns:itunes="http://www.itunes.com/dtds/podcast-1.0.dtd">
+            <channel>
+                <title>Relative URLs</title>
+                <link>/</link>
+                <itunes:image href="/cover.jpg"/>
+                <item>
+                    <title>Episode 1</title>
+                    <link>/episodes/1</link>
+                    <itunes:image href="images/1.jpg"/>
+                    <enclosure url="/episodes/1.mp3" type="audio/mpeg" length="100"/>
+                </item>
+            </channel>
+        </rss>"#;
+        // 严格模式下相对地址不再导致解析失败
+        let (podcast, episodes) = RssFeedParser::new()
+            .parse(feed.as_bytes(), "https://example.com/podcasts/feed.xml")
+            .await
+            .unwrap();
+        assert_eq!(podcast.link.as_deref(), Some("https://example.com/"));
+        assert_eq!(
+            podcast.image_url.as_deref(),
+            Some("https://example.com/cover.jpg")
+        );
+        assert_eq!(
+            episodes[0].link.as_deref(),
+            Some("https://example.com/episodes/1")
+        );
+        assert_eq!(
+            episodes[0].episode_image_url.as_deref(),
+            Some("https://example.com/podcasts/images/1.jpg")
+        );
+        assert_eq!(
+            episodes[0].enclosure_url.as_deref(),
+            Some("https://example.com/episodes/1.mp3")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_parse_tolerates_leading_bom_and_whitespace() {
+        let feed = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<rss version=\"2.0\"><channel><title>BOM Podcast</title></channel></rss>";
+        let parser = RssFeedParser::new();
+        for content in [
+            format!("\u{FEFF}{}", feed),
+            format!("\n  \t{}", feed),
+            format!("\u{FEFF}\r\n{}", feed),
+        ] {
+            let (podcast, _episodes) = parser
+                .parse(content.as_bytes(), "https://example.com/bom.xml")
+                .await
+                .unwrap();
+            assert_eq!(podcast.title, "BOM Podcast");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_parse_gb2312_feed() {
+        let (podcast, episodes) = RssFeedParser::new()
+            .parse(
+                include_bytes!("../../tests/data/gb2312_feed.xml"),
+                "https://example.com/gb2312.xml",
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(podcast.title, "中文播客测试");
+        assert_eq!(episodes[0].title, "第一期：你好世界");
+    }
+
+    #[test]
+    fn test_declared_encoding() {
+        assert_eq!(
+            declared_encoding(b"<?xml version='1.0' encoding='ISO-8859-1'?><rss/>").as_deref(),
+            Some("ISO-8859-1")
+        );
+        assert_eq!(declared_encoding(b"<?xml version=\"1.0\"?><rss/>"), None);
+        assert_eq!(declared_encoding(b"<rss/>"), None);
+    }
+
+    #[tokio::test]
+    async fn test_parse_latin1_feed() {
+        let mut content =
+            b"<?xml version=\"1.0\" encoding=\"ISO-8859-1\"?><rss><channel><title>Caf".to_vec();
+        content.push(0xE9); // "é" in latin-1
+        content.extend_from_slice(b"</title></channel></rss>");
+
+        let (podcast, _episodes) = RssFeedParser::new()
+            .parse(&content, "https://example.com/latin1.xml")
+            .await
+            .unwrap();
+        assert_eq!(podcast.title, "Café");
+    }
+
+    #[tokio::test]
+    async fn test_text_limits_truncate_long_fields() {
+        let summary = "长".repeat(100);
+        let rss = format!(
+            r#"<rss version="2.0" xmlns:itunes="http://www.itunes.com/dtds/podcast-1.0.dtd">
+                <channel>
+                    <title>Long Summaries</title>
+                    <itunes:summary>{summary}</itunes:summary>
+                    <item>
+                        <title>Episode With A Very Long Title</title>
+                        <itunes:summary>{summary}</itunes:summary>
+                        <itunes:subtitle>Short</itunes:subtitle>
+                    </item>
+                </channel>
+            </rss>"#
+        );
+
+        let parser = RssFeedParser::new().with_text_limits(TextLimits {
+            title: Some(12),
+            summary: Some(10),
+            subtitle: Some(10),
+            ..TextLimits::default()
+        });
+        let (podcast, episodes, warnings) = parser
+            .parse_with_warnings(rss.as_bytes(), "https://example.com/long.xml")
+            .await
+            .unwrap();
+
+        // 按字符截断，结果含 "…" 不超过上限
+        assert_eq!(podcast.title, "Long Summar…");
+        assert_eq!(podcast.summary, Some(format!("{}…", "长".repeat(9))));
+        assert_eq!(episodes[0].title, "Episode Wit…");
+        assert_eq!(episodes[0].summary, Some(format!("{}…", "长".repeat(9))));
+        assert_eq!(episodes[0].subtitle.as_deref(), Some("Short"));
+
+        let truncated: Vec<_> = warnings
+            .iter()
+            .filter(|warning| warning.message.starts_with("Truncated"))
+            .collect();
+        assert_eq!(truncated.len(), 4);
+        assert_eq!(truncated[1].field, "summary");
+        assert_eq!(truncated[1].path, "rss/channel/itunes:summary");
+        assert_eq!(
+            truncated[1].message,
+            "Truncated summary from 100 to 10 characters"
+        );
+
+        // 默认不限制
+        let (_podcast, episodes) = RssFeedParser::new()
+            .parse(rss.as_bytes(), "https://example.com/long.xml")
+            .await
+            .unwrap();
+        assert_eq!(episodes[0].summary, Some(summary));
+    }
+
+    #[tokio::test]
+    async fn test_max_episodes_keeps_first_items() {
+        let items: String = (1..=5)
+            .map(|i| format!("<item><title>Episode {}</title></item>", i))
+            .collect();
+        let rss = format!(
+            "<rss version=\"2.0\"><channel><title>Long Feed</title>{}</channel></rss>",
+            items
+        );
+
+        let parser = RssFeedParser::with_config(ParserConfig {
+            max_episodes: Some(2),
+            ..ParserConfig::default()
+        });
+        let (_podcast, episodes) = parser
+            .parse(rss.as_bytes(), "https://example.com/long.xml")
+            .await
+            .unwrap();
+        let titles: Vec<&str> = episodes.iter().map(|e| e.title.as_str()).collect();
+        assert_eq!(titles, vec!["Episode 1", "Episode 2"]);
+    }
+
+    #[tokio::test]
+    async fn test_parse_podcast_funding_and_persons() {
+        let (podcast, episodes) = RssFeedParser::new()
+            .parse(
+                include_bytes!("../../tests/data/podcasting2_feed.xml"),
+                "https://example.com/podcasting2_feed.xml",
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            podcast.funding_url.as_deref(),
+            Some("https://example.com/support")
+        );
+        assert_eq!(podcast.funding_text.as_deref(), Some("Support the show!"));
+        // 没有姓名的 person 被丢弃，item 内的 person 不计入播客
+        assert_eq!(
+            podcast.persons,
+            Some(serde_json::json!([
+                {
+                    "name": "Alice Host",
+                    "role": "host",
+                    "img": "https://example.com/images/alice.jpg",
+                    "href": "https://example.com/alice"
+                },
+                { "name": "Bob Guest", "role": "guest", "group": "cast" }
+            ]))
+        );
+        assert_eq!(episodes.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_skip_invalid_items() {
+        let rss = r#"<rss version="2.0">
+            <channel>
+                <title>Partly Broken</title>
+                <item><title>Good 1</title></item>
+                <item><description>No title</description></item>
+                <item><title>Good 2</title></item>
+            </channel>
+        </rss>"#;
+        let url = "https://example.com/broken.xml";
+
+        // 默认行为：第一个无标题的剧集让整个 feed 解析失败
+        assert!(RssFeedParser::new()
+            .parse(rss.as_bytes(), url)
+            .await
+            .is_err());
+
+        let parser = RssFeedParser::with_config(ParserConfig {
+            skip_invalid_items: true,
+            ..ParserConfig::default()
+        });
+        let (_podcast, episodes) = parser.parse(rss.as_bytes(), url).await.unwrap();
+        let titles: Vec<&str> = episodes.iter().map(|e| e.title.as_str()).collect();
+        assert_eq!(titles, vec!["Good 1", "Good 2"]);
+        let positions: Vec<Option<i32>> = episodes.iter().map(|e| e.feed_position).collect();
+        assert_eq!(positions, vec![Some(0), Some(2)]);
+    }
+
+    #[tokio::test]
+    async fn test_parse_channel_dates() {
+        let parse = |channel: &str| {
+            let rss = format!(
+                r#"<rss version="2.0"><channel><title>Dates</title>{}</channel></rss>"#,
+                channel
+            );
+            async move {
+                let (podcast, _) = RssFeedParser::new()
+                    .parse(rss.as_bytes(), "https://example.com/dates.xml")
+                    .await
+                    .unwrap();
+                podcast.last_build_date
+            }
+        };
+        let build_date = parse_date("Tue, 10 Jun 2025 04:00:00 GMT");
+        let pub_date = parse_date("Mon, 09 Jun 2025 04:00:00 GMT");
+        assert!(build_date.is_some());
+
+        assert_eq!(
+            parse("<lastBuildDate>Tue, 10 Jun 2025 04:00:00 GMT</lastBuildDate>").await,
+            build_date
+        );
+        assert_eq!(
+            parse("<pubDate>Mon, 09 Jun 2025 04:00:00 GMT</pubDate>").await,
+            pub_date
+        );
+        // 无论先后顺序，都以 lastBuildDate 为准
+        for channel in [
+            "<pubDate>Mon, 09 Jun 2025 04:00:00 GMT</pubDate>\
+             <lastBuildDate>Tue, 10 Jun 2025 04:00:00 GMT</lastBuildDate>",
+            "<lastBuildDate>Tue, 10 Jun 2025 04:00:00 GMT</lastBuildDate>\
+             <pubDate>Mon, 09 Jun 2025 04:00:00 GMT</pubDate>",
+        ] {
+            assert_eq!(parse(channel).await, build_date);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_parse_unterminated_feed() {
+        let content = include_bytes!("../../tests/data/unclosed_item_feed.xml");
+        let (podcast, episodes, warnings) = RssFeedParser::new()
+            .parse_with_warnings(content, "https://example.com/truncated.xml")
+            .await
+            .unwrap();
+        assert_eq!(podcast.title, "Truncated Podcast");
+        // 未闭合的最后一个 item 也被保留
+        let guids: Vec<Option<&str>> = episodes.iter().map(|e| e.guid.as_deref()).collect();
+        assert_eq!(guids, vec![Some("truncated-2"), Some("truncated-1")]);
+        assert_eq!(
+            episodes[1].enclosure_url.as_deref(),
+            Some("https://example.com/audio/episode-1.mp3")
+        );
+        assert_eq!(episodes[1].feed_position, Some(1));
+        let warning = warnings.iter().find(|w| w.field == "channel").unwrap();
+        assert_eq!(warning.path, "rss/channel/item");
+    }
+
+    #[tokio::test]
+    async fn test_capture_unknown_tags() {
+        let rss = r#"<rss version="2.0">
+            <channel>
+                <title>Extra</title>
+                <itunes:author>Host</itunes:author>
+                <spotify:limit recentCount="5"/>
+                <googleplay:category text="Technology"/>
+                <googleplay:category text="News"/>
+                <acast:showId>abc123</acast:showId>
+                <item>
+                    <title>Episode</title>
+                    <itunes:duration>60</itunes:duration>
+                    <acast:episodeId>ep-1</acast:episodeId>
+                    <acast:settings type="sealed">secret</acast:settings>
+                </item>
+            </channel>
+        </rss>"#;
+        let parser = RssFeedParser::new().with_unknown_tag_capture(true);
+        let (podcast, episodes) = parser
+            .parse(rss.as_bytes(), "https://example.com/extra.xml")
+            .await
+            .unwrap();
+        // 已处理的 itunes: 元素不会进入 extra
+        assert_eq!(
+            podcast.extra,
+            Some(serde_json::json!({
+                "spotify:limit": {"recentCount": "5"},
+                "googleplay:category": [{"text": "Technology"}, {"text": "News"}],
+                "acast:showId": "abc123",
+            }))
+        );
+        assert_eq!(
+            episodes[0].extra,
+            Some(serde_json::json!({
+                "acast:episodeId": "ep-1",
+                "acast:settings": {"type": "sealed", "#text": "secret"},
+            }))
+        );
+
+        // 默认不记录
+        let (podcast, episodes) = RssFeedParser::new()
+            .parse(rss.as_bytes(), "https://example.com/extra.xml")
+            .await
+            .unwrap();
+        assert_eq!(podcast.extra, None);
+        assert_eq!(episodes[0].extra, None);
+    }
+
+    #[tokio::test]
+    async fn test_parse_warnings() {
+        let rss = r#"<rss version="2.0">
+            <channel>
+                <title>Warnings</title>
+                <lastBuildDate>not a date</lastBuildDate>
+                <item><title>Good</title><pubDate>yesterday</pubDate></item>
+                <item><description>No title</description></item>
+            </channel>
+        </rss>"#;
+        let parser = RssFeedParser::with_config(ParserConfig {
+            skip_invalid_items: true,
+            ..ParserConfig::default()
+        });
+        let (_podcast, episodes, warnings) = parser
+            .parse_with_warnings(rss.as_bytes(), "https://example.com/warnings.xml")
+            .await
+            .unwrap();
+        assert_eq!(episodes.len(), 1);
+
+        let found: Vec<(&str, &str)> = warnings
+            .iter()
+            .map(|w| (w.field.as_str(), w.path.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("last_build_date", "rss/channel/lastBuildDate"),
+                ("pub_date", "rss/channel/item/pubDate"),
+                ("item", "rss/channel/item"),
+                ("image_url", "rss/channel"),
+            ]
+        );
+        assert!(warnings[0].message.contains("not a date"));
+        assert!(warnings[2].message.contains("position 1"));
+
+        // 没有问题的 feed 不产生警告
+        let rss = r#"<rss version="2.0" xmlns:itunes="http://www.itunes.com/dtds/podcast-1.0.dtd">
+            <channel>
+                <title>Clean</title>
+                <itunes:image href="https://example.com/cover.jpg"/>
+                <item><title>Good</title><pubDate>Tue, 10 Jun 2025 04:00:00 GMT</pubDate></item>
+            </channel>
+        </rss>"#;
+        let (_podcast, _episodes, warnings) = RssFeedParser::new()
+            .parse_with_warnings(rss.as_bytes(), "https://example.com/clean.xml")
+            .await
+            .unwrap();
+        assert!(warnings.is_empty(), "unexpected warnings: {:?}", warnings);
+    }
+
+    #[tokio::test]
+    async fn test_parse_new_feed_url() {
+        use crate::crawler_refactor::task::Task;
+
+        let mut task = Task::new(1, "https://example.com/moved.xml".to_string(), 0);
+        task.content = include_bytes!("../../tests/data/moved_feed.xml").to_vec();
+        let (podcast, _episodes) = RssFeedParser::new()
+            .parse_with_task(&mut task)
+            .await
+            .unwrap();
+        // 新地址只记录在任务上，播客仍以本次抓取的地址保存
+        assert_eq!(
+            task.new_feed_url.as_deref(),
+            Some("https://example.com/moved/new.xml")
+        );
+        assert_eq!(
+            podcast.rss_feed_url.as_deref(),
+            Some("https://example.com/moved.xml")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_field_coverage() {
+        let rss = r#"<rss version="2.0" xmlns:itunes="http://www.itunes.com/dtds/podcast-1.0.dtd">
+            <channel>
+                <title>Coverage</title>
+                <itunes:summary>Summary</itunes:summary>
+                <itunes:subtitle>Subtitle</itunes:subtitle>
+                <item><title>Bare</title></item>
+                <item>
+                    <title>Full</title>
+                    <description>Desc</description>
+                    <guid>full-1</guid>
+                </item>
+            </channel>
+        </rss>"#;
+
+        let (podcast, episodes) = RssFeedParser::new()
+            .parse(rss.as_bytes(), "https://example.com/coverage.xml")
+            .await
+            .unwrap();
+        let (podcast_coverage, episode_coverage) = field_coverage(&podcast, &episodes);
+        assert!((podcast_coverage - 0.4).abs() < f64::EPSILON);
+        // 第一个剧集 0/9，第二个 2/9
+        assert!((episode_coverage.unwrap() - 1.0 / 9.0).abs() < 1e-9);
+        assert_eq!(field_coverage(&podcast, &[]).1, None);
+    }
+}
@@ -5,9 +5,19 @@ use std::{
 
 use tracing::{error, info};
 
-use crate::infrastructure::AppState;
-
-use super::{task::Task, task_management_system::TaskManagementSystem};
+use crate::infrastructure::config::CrawlerConfig;
+use crate::infrastructure::persistence::models::{NewEpisode, NewPodcast};
+use crate::infrastructure::{AppResult, AppState};
+
+use super::{
+    enclosure_resolver::EnclosureLengthResolver,
+    parser_registry::{unrecognized_format_error, ParserRegistry},
+    pipeline::Fetcher,
+    rss::ParseWarning,
+    rss_fetcher::RssFetcher,
+    task::{Task, TaskOptions, TaskSummary},
+    task_management_system::TaskManagementSystem,
+};
 
 /// RSS爬虫系统入口
 pub struct RssCrawler {
@@ -25,6 +35,39 @@ impl RssCrawler {
         Self { system }
     }
 
+    /// 单次抓取并解析一个 feed，不启动任务系统也不写入数据库
+    ///
+    /// # 参数
+    /// - url: 要爬取的RSS feed URL
+    /// - config: 爬虫配置（Accept-Language、代理、语言检测）
+    ///
+    /// # 返回
+    /// 播客、剧集以及解析器记录的非致命警告
+    pub async fn crawl_once(
+        url: &str,
+        config: &CrawlerConfig,
+    ) -> AppResult<(NewPodcast, Vec<NewEpisode>, Vec<ParseWarning>)> {
+        let fetcher = RssFetcher::from_config(config)?;
+        let parsers = ParserRegistry::from_config(config);
+
+        let mut task = Task::new(0, url.to_string(), 0);
+        fetcher.fetch_with_task(&mut task).await?;
+        let parser = parsers
+            .select(task.content_type.as_deref(), &task.content)
+            .ok_or_else(|| unrecognized_format_error(url, &task.content))?;
+        let (podcast, mut episodes) = parser.parse_with_task(&mut task).await?;
+        if let Some(resolver) = EnclosureLengthResolver::from_config(config) {
+            resolver.resolve(&mut episodes).await;
+        }
+        // 不记录警告的解析器视为没有警告
+        let warnings = task
+            .get_stage_result_data_by_name("parsing")
+            .and_then(|data| data.get("warnings"))
+            .and_then(|warnings| serde_json::from_value(warnings.clone()).ok())
+            .unwrap_or_default();
+        Ok((podcast, episodes, warnings))
+    }
+
     /// 启动爬虫系统
     pub async fn start(&mut self) {
         self.system.start().await;
@@ -34,10 +77,14 @@ impl RssCrawler {
     ///
     /// # 参数
     /// - url: 要爬取的RSS feed URL
-    pub async fn add_task(&mut self, url: &str) -> Result<(), String> {
+    /// - options: 可选参数（优先级，数值越大越先分发；Basic Auth 凭据）
+    ///
+    /// # 返回
+    /// 新任务的 ID
+    pub async fn add_task(&mut self, url: &str, options: TaskOptions) -> AppResult<u64> {
         let start = Instant::now();
 
-        let result = self.system.add_task(url).await;
+        let result = self.system.add_task(url, options).await;
         let duration = start.elapsed().as_secs_f64();
 
         match &result {
@@ -52,6 +99,44 @@ impl RssCrawler {
         result
     }
 
+    /// 批量添加爬取任务（使用默认参数）
+    ///
+    /// # 返回
+    /// 与输入顺序一致的每个 URL 的结果（任务 ID 或错误）
+    pub async fn add_tasks(&mut self, urls: &[String]) -> Vec<AppResult<u64>> {
+        self.system.add_tasks(urls).await
+    }
+
+    /// 取消单个任务，任务以 "cancelled" 失败
+    ///
+    /// # 参数
+    /// - id: 任务 ID
+    pub async fn cancel_task(&self, id: u64) -> AppResult<()> {
+        self.system.cancel_task(id).await
+    }
+
+    /// 暂停领取新任务，正在处理的任务会继续完成
+    pub fn pause(&self) {
+        self.system.pause();
+    }
+
+    /// 恢复领取任务
+    pub fn resume(&self) {
+        self.system.resume();
+    }
+
+    /// 爬虫是否处于暂停状态
+    pub fn is_paused(&self) -> bool {
+        self.system.is_paused()
+    }
+
+    /// 订阅任务阶段和状态的变化
+    ///
+    /// 任务创建、进入新阶段或状态变化时收到一条任务概要。
+    pub fn subscribe_task_events(&self) -> tokio::sync::broadcast::Receiver<TaskSummary> {
+        self.system.subscribe_task_events()
+    }
+
     /// 获取所有任务状态
     pub async fn get_tasks(&self) -> Vec<Task> {
         self.system.get_task_info().await
@@ -91,7 +176,7 @@ mod tests {
 
         // 添加测试任务
         crawler
-            .add_task("http://example.com/feed.rss")
+            .add_task("http://example.com/feed.rss", TaskOptions::default())
             .await
             .unwrap();
 
@@ -0,0 +1,242 @@
+use crate::infrastructure::config::CrawlerConfig;
+use crate::infrastructure::error::{AppError, AppResult, DomainErrorKind};
+use crate::infrastructure::AppRepositories;
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+/// 提供需要重新爬取的 feed URL
+#[async_trait]
+pub trait StaleFeedSource: Send + Sync {
+    /// 返回最多 `limit` 个超过 `older_than` 未抓取的 feed URL，最久未抓取的在前
+    async fn stale_feed_urls(
+        &self,
+        older_than: chrono::Duration,
+        limit: i64,
+    ) -> AppResult<Vec<String>>;
+}
+
+#[async_trait]
+impl StaleFeedSource for AppRepositories {
+    async fn stale_feed_urls(
+        &self,
+        older_than: chrono::Duration,
+        limit: i64,
+    ) -> AppResult<Vec<String>> {
+        let podcasts = self.podcast.get_due_for_recrawl(older_than, limit).await?;
+        Ok(podcasts
+            .into_iter()
+            .filter_map(|podcast| podcast.rss_feed_url)
+            .collect())
+    }
+}
+
+/// 接收调度器提交的任务
+#[async_trait]
+pub trait TaskSink: Send + Sync {
+    /// 当前尚未结束的任务数量
+    async fn in_flight_count(&self) -> usize;
+    /// 提交一个爬取任务，返回任务 ID
+    async fn enqueue(&self, url: &str) -> AppResult<u64>;
+}
+
+/// 定期查询过期 feed 并重新提交爬取任务
+pub struct RecrawlScheduler {
+    source: Arc<dyn StaleFeedSource>,
+    sink: Arc<dyn TaskSink>,
+    interval: Duration,
+    stale_after: chrono::Duration,
+    max_in_flight: usize,
+    cancellation_token: CancellationToken,
+}
+
+impl RecrawlScheduler {
+    pub fn new(
+        source: Arc<dyn StaleFeedSource>,
+        sink: Arc<dyn TaskSink>,
+        config: &CrawlerConfig,
+        cancellation_token: CancellationToken,
+    ) -> Self {
+        Self {
+            source,
+            sink,
+            interval: Duration::from_secs(config.scheduler_interval_seconds),
+            stale_after: chrono::Duration::seconds(config.fetch_interval_seconds as i64),
+            max_in_flight: config.scheduler_max_in_flight,
+            cancellation_token,
+        }
+    }
+
+    /// 执行一轮调度，返回本轮提交的任务数
+    pub async fn tick(&self) -> usize {
+        let in_flight = self.sink.in_flight_count().await;
+        let budget = self.max_in_flight.saturating_sub(in_flight);
+        if budget == 0 {
+            tracing::debug!(
+                "⏸️ RecrawlScheduler: {} tasks in flight, skipping this round",
+                in_flight
+            );
+            return 0;
+        }
+
+        let urls = match self
+            .source
+            .stale_feed_urls(self.stale_after, budget as i64)
+            .await
+        {
+            Ok(urls) => urls,
+            Err(e) => {
+                tracing::error!("❌ RecrawlScheduler: Failed to load stale feeds: {}", e);
+                return 0;
+            }
+        };
+
+        let mut enqueued = 0;
+        for url in urls {
+            match self.sink.enqueue(&url).await {
+                Ok(_) => enqueued += 1,
+                // 仍在爬取中的 feed 直接跳过
+                Err(AppError::Domain(e)) if e.kind == DomainErrorKind::Duplicate => {
+                    tracing::debug!("♻️ RecrawlScheduler: '{}' is already in flight", url);
+                }
+                Err(e) => {
+                    tracing::warn!("⚠️ RecrawlScheduler: Failed to enqueue '{}': {}", url, e);
+                }
+            }
+        }
+        tracing::info!(
+            "🗓️ RecrawlScheduler: Enqueued {} stale feeds ({} already in flight)",
+            enqueued,
+            in_flight
+        );
+        enqueued
+    }
+
+    /// 按配置的间隔循环调度，直到取消
+    pub async fn run(&self) {
+        let mut ticker =
+            tokio::time::interval_at(tokio::time::Instant::now() + self.interval, self.interval);
+        loop {
+            tokio::select! {
+                _ = self.cancellation_token.cancelled() => break,
+                _ = ticker.tick() => {
+                    self.tick().await;
+                }
+            }
+        }
+        tracing::info!("🏁 RecrawlScheduler: Stopped");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use tokio::sync::Mutex;
+
+    struct FixedSource(Vec<String>);
+
+    #[async_trait]
+    impl StaleFeedSource for FixedSource {
+        async fn stale_feed_urls(
+            &self,
+            _older_than: chrono::Duration,
+            limit: i64,
+        ) -> AppResult<Vec<String>> {
+            Ok(self.0.iter().take(limit as usize).cloned().collect())
+        }
+    }
+
+    // 记录提交的 URL，重复提交视为仍在进行中
+    #[derive(Default)]
+    struct RecordingSink {
+        enqueued: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl TaskSink for RecordingSink {
+        async fn in_flight_count(&self) -> usize {
+            self.enqueued.lock().await.len()
+        }
+
+        async fn enqueue(&self, url: &str) -> AppResult<u64> {
+            let mut enqueued = self.enqueued.lock().await;
+            if enqueued.iter().any(|existing| existing == url) {
+                return Err(crate::infrastructure::error::DomainError::new(
+                    DomainErrorKind::Duplicate,
+                    "already in flight".to_string(),
+                    Some(url.to_string()),
+                    None,
+                )
+                .into());
+            }
+            enqueued.push(url.to_string());
+            Ok(enqueued.len() as u64)
+        }
+    }
+
+    fn scheduler(
+        urls: &[&str],
+        max_in_flight: usize,
+        interval: Duration,
+    ) -> (RecrawlScheduler, Arc<RecordingSink>, CancellationToken) {
+        let sink = Arc::new(RecordingSink::default());
+        let token = CancellationToken::new();
+        let config = CrawlerConfig {
+            scheduler_max_in_flight: max_in_flight,
+            ..CrawlerConfig::default()
+        };
+        let mut scheduler = RecrawlScheduler::new(
+            Arc::new(FixedSource(urls.iter().map(|u| u.to_string()).collect())),
+            sink.clone(),
+            &config,
+            token.clone(),
+        );
+        scheduler.interval = interval;
+        (scheduler, sink, token)
+    }
+
+    #[tokio::test]
+    async fn test_tick_respects_max_in_flight() {
+        let (scheduler, sink, _token) = scheduler(
+            &[
+                "http://a.com/feed",
+                "http://b.com/feed",
+                "http://c.com/feed",
+            ],
+            2,
+            Duration::from_secs(60),
+        );
+
+        assert_eq!(scheduler.tick().await, 2);
+        // Both slots are taken, so nothing else is enqueued
+        assert_eq!(scheduler.tick().await, 0);
+        assert_eq!(
+            *sink.enqueued.lock().await,
+            vec!["http://a.com/feed", "http://b.com/feed"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_enqueues_periodically_until_cancelled() {
+        let (scheduler, sink, token) = scheduler(
+            &["http://a.com/feed", "http://b.com/feed"],
+            10,
+            Duration::from_millis(20),
+        );
+        let scheduler = Arc::new(scheduler);
+        let handle = {
+            let scheduler = scheduler.clone();
+            tokio::spawn(async move { scheduler.run().await })
+        };
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        token.cancel();
+        handle.await.unwrap();
+
+        let enqueued: HashSet<String> = sink.enqueued.lock().await.iter().cloned().collect();
+        assert_eq!(enqueued.len(), 2);
+        assert!(enqueued.contains("http://a.com/feed"));
+    }
+}
@@ -1,10 +1,14 @@
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use sha2::{Digest, Sha256};
 use std::cmp::Ordering;
 use std::fmt::Debug;
-use std::time::Instant;
-use tracing::{error, info};
+use std::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info, Span};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum StageStatus {
     Pending,
     InProgress,
@@ -15,18 +19,115 @@ pub enum StageStatus {
 // Task 结构体
 use std::fmt;
 
+/// 默认任务优先级，数值越大越先被分发
+pub const PRIORITY_NORMAL: u8 = 128;
+
+/// 受保护 feed 的 HTTP Basic Auth 凭据，Debug 输出时隐藏
+#[derive(Clone, PartialEq, Eq, Deserialize)]
+pub struct BasicAuth {
+    pub username: String,
+    pub password: String,
+}
+
+impl fmt::Debug for BasicAuth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("BasicAuth(<redacted>)")
+    }
+}
+
+/// 添加任务时的可选参数
+#[derive(Debug, Clone, Default)]
+pub struct TaskOptions {
+    pub priority: Option<u8>,
+    pub auth: Option<BasicAuth>,
+    pub max_retries: Option<u32>,
+    /// 已经爬取过的旧 feed 地址，跟随 `<itunes:new-feed-url>` 时用于避免循环
+    pub visited_feed_urls: Vec<String>,
+}
+
 #[derive(Clone)]
 pub struct Task {
     pub id: u64,
     pub target_thread_id: usize,
+    pub dispatch_seq: u64, // 发往目标 worker 的第几个任务（从 1 开始），由 PriorityQueue 分发时设置
     pub payload: String,
+    pub final_url: Option<String>, // 重定向后的最终 URL，在 fetching 阶段设置
     pub content: Vec<u8>,
+    pub content_type: Option<String>, // 响应的 Content-Type，用于选择解析器
+    pub content_hash: Option<String>, // 抓取内容的 SHA-256，插入时随播客一起保存
+    pub new_feed_url: Option<String>, // feed 通过 <itunes:new-feed-url> 声明的新地址，在 parsing 阶段设置
+    pub visited_feed_urls: Vec<String>, // 迁移到本任务地址之前爬取过的旧地址
     pub retries: u32,
     pub max_retries: u32,
+    pub priority: u8,
+    pub auth: Option<BasicAuth>,
     pub backoff_timer: Option<Instant>,
     pub stages: Vec<Stage>, // Vec 存储不同类型的 Stage
     pub error_message: Option<String>,
     pub shutdown: bool,
+    pub span: Span, // 任务级 tracing span，贯穿分发、抓取、解析与插入
+    pub cancellation_token: CancellationToken, // 系统 token 的子 token，克隆的任务共享同一个
+}
+
+/// 对外展示的任务概要（不包含内容与凭据）
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskSummary {
+    pub id: u64,
+    pub url: String,
+    pub final_url: Option<String>,
+    pub priority: u8,
+    pub retries: u32,
+    pub max_retries: u32,
+    pub stage: Option<String>,
+    pub status: StageStatus,
+    pub error_message: Option<String>,
+}
+
+impl From<&Task> for TaskSummary {
+    fn from(task: &Task) -> Self {
+        Self {
+            id: task.id,
+            url: task.payload.clone(),
+            final_url: task.final_url.clone(),
+            priority: task.priority,
+            retries: task.retries,
+            max_retries: task.max_retries,
+            stage: task.stages.last().map(|stage| stage.name.clone()),
+            status: task.get_task_status(),
+            error_message: task
+                .get_current_stage_error_message()
+                .or(task.error_message.as_ref())
+                .cloned(),
+        }
+    }
+}
+
+/// 等待超时时仍未结束的任务，以及它停留的阶段和时长
+#[derive(Debug, Clone)]
+pub struct StuckTask {
+    pub task: Task,
+    pub stage: Option<String>,
+    pub status: StageStatus,
+    pub stuck_for: Duration,
+}
+
+impl StuckTask {
+    /// 任务已结束时返回 `None`；尚未开始任何阶段的任务 `stuck_for` 为 0
+    pub fn from_task(task: &Task) -> Option<Self> {
+        if task.is_finished() {
+            return None;
+        }
+        let stage = task.stages.last();
+        Some(Self {
+            task: task.clone(),
+            stage: stage.map(|stage| stage.name.clone()),
+            status: task.get_task_status(),
+            stuck_for: stage
+                .and_then(|stage| stage.start_time)
+                .map(|start| start.elapsed())
+                .unwrap_or_default(),
+        })
+    }
 }
 
 // 阶段数据结构体
@@ -46,14 +147,24 @@ impl Task {
         Task {
             id,
             target_thread_id: 0,
+            dispatch_seq: 0,
             payload,
+            final_url: None,
             content: Vec::new(),
+            content_type: None,
+            content_hash: None,
+            new_feed_url: None,
+            visited_feed_urls: Vec::new(),
             retries: 0,
             max_retries,
+            priority: PRIORITY_NORMAL,
+            auth: None,
             backoff_timer: None,
             stages: Vec::new(),
             error_message: None,
             shutdown: false,
+            span: Span::none(),
+            cancellation_token: CancellationToken::new(),
         }
     }
 
@@ -155,6 +266,37 @@ impl Task {
         }
     }
 
+    // 获取规范化的 feed URL（优先使用重定向后的最终 URL）
+    pub fn get_feed_url(&self) -> &str {
+        self.final_url.as_deref().unwrap_or(&self.payload)
+    }
+
+    // feed 声明迁移到的新地址；与当前地址相同或已爬取过时返回 None，避免重定向循环
+    pub fn feed_move_target(&self) -> Option<&str> {
+        let new_url = self.new_feed_url.as_deref()?;
+        let visited = new_url == self.payload
+            || new_url == self.get_feed_url()
+            || self.visited_feed_urls.iter().any(|url| url == new_url);
+        (!visited).then_some(new_url)
+    }
+
+    // 跟随新地址时新任务的访问记录：本任务之前的地址加上本任务的地址
+    pub fn visited_feed_urls_after_move(&self) -> Vec<String> {
+        let mut visited = self.visited_feed_urls.clone();
+        for url in [self.payload.as_str(), self.get_feed_url()] {
+            if !visited.iter().any(|visited| visited == url) {
+                visited.push(url.to_string());
+            }
+        }
+        visited
+    }
+
+    // 计算抓取内容的哈希，用于判断 feed 自上次爬取后是否变化
+    pub fn compute_content_hash(&mut self) -> &str {
+        let hash = content_hash(&self.content);
+        self.content_hash.insert(hash)
+    }
+
     pub fn get_content(&self) -> Option<&[u8]> {
         Some(self.content.as_slice())
     }
@@ -167,6 +309,13 @@ impl Task {
         })
     }
 
+    // 替换指定阶段的结果数据（如解析后补全的字段）
+    pub fn set_stage_result_data(&mut self, stage_name: &str, result_data: Value) {
+        if let Some(stage) = self.stages.iter_mut().rev().find(|s| s.name == stage_name) {
+            stage.result_data = Some(result_data);
+        }
+    }
+
     pub fn get_stage_result_data_by_name(&self, stage_name: &str) -> Option<&Value> {
         self.stages
             .iter()
@@ -190,10 +339,35 @@ impl Task {
         self.get_task_status() == StageStatus::Failed
     }
 
+    // 任务是否已被单独取消（或系统已关闭）
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellation_token.is_cancelled()
+    }
+
     pub fn is_completed(&self) -> bool {
         self.get_task_status() == StageStatus::Completed
     }
+
+    // 任务是否已结束：任一阶段失败，或 inserting 阶段已完成
+    pub fn is_finished(&self) -> bool {
+        match self.stages.last() {
+            Some(stage) => {
+                stage.status == StageStatus::Failed
+                    || (stage.name == "inserting" && stage.status == StageStatus::Completed)
+            }
+            None => false,
+        }
+    }
 }
+
+/// 内容的 SHA-256 十六进制字符串
+pub fn content_hash(content: &[u8]) -> String {
+    Sha256::digest(content)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
 impl Ord for Task {
     fn cmp(&self, other: &Self) -> Ordering {
         other.backoff_timer.cmp(&self.backoff_timer)
@@ -230,9 +404,15 @@ impl fmt::Debug for Task {
             .field("id", &self.id)
             .field("target_thread_id", &self.target_thread_id)
             .field("payload", &self.payload)
+            .field("final_url", &self.final_url)
             .field("content", &content_preview)
+            .field("content_hash", &self.content_hash)
+            .field("new_feed_url", &self.new_feed_url)
+            .field("visited_feed_urls", &self.visited_feed_urls)
             .field("retries", &self.retries)
             .field("max_retries", &self.max_retries)
+            .field("priority", &self.priority)
+            .field("auth", &self.auth)
             .field("backoff_timer", &self.backoff_timer)
             .field("stages", &self.stages)
             .field("error_message", &self.error_message)
@@ -240,3 +420,88 @@ impl fmt::Debug for Task {
             .finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_feed_move_target() {
+        let mut task = Task::new(1, "https://old.example.com/feed.xml".to_string(), 0);
+        assert_eq!(task.feed_move_target(), None);
+
+        task.new_feed_url = Some("https://new.example.com/feed.xml".to_string());
+        assert_eq!(
+            task.feed_move_target(),
+            Some("https://new.example.com/feed.xml")
+        );
+        assert_eq!(
+            task.visited_feed_urls_after_move(),
+            vec!["https://old.example.com/feed.xml"]
+        );
+
+        // 指回自身或已经访问过的地址时不再跟随
+        task.new_feed_url = Some(task.payload.clone());
+        assert_eq!(task.feed_move_target(), None);
+        let mut moved = Task::new(2, "https://new.example.com/feed.xml".to_string(), 0);
+        moved.visited_feed_urls = task.visited_feed_urls_after_move();
+        moved.new_feed_url = Some("https://old.example.com/feed.xml".to_string());
+        assert_eq!(moved.feed_move_target(), None);
+    }
+
+    #[test]
+    fn test_debug_redacts_credentials() {
+        let mut task = Task::new(1, "https://example.com/private.xml".to_string(), 0);
+        task.auth = Some(BasicAuth {
+            username: "listener".to_string(),
+            password: "s3cret".to_string(),
+        });
+
+        let output = format!("{:?}", task);
+        assert!(output.contains("BasicAuth(<redacted>)"));
+        assert!(!output.contains("listener"));
+        assert!(!output.contains("s3cret"));
+    }
+
+    #[test]
+    fn test_summary_reports_effective_retries() {
+        let mut task = Task::new(3, "https://example.com/feed.xml".to_string(), 5);
+        task.add_stage("fetching");
+
+        let summary = serde_json::to_value(TaskSummary::from(&task)).unwrap();
+        assert_eq!(summary["max_retries"], 5);
+        assert_eq!(summary["stage"], "fetching");
+        assert_eq!(summary["status"], "in_progress");
+    }
+
+    #[test]
+    fn test_content_hash() {
+        let mut task = Task::new(4, "https://example.com/feed.xml".to_string(), 0);
+        task.content = b"<rss/>".to_vec();
+        let hash = task.compute_content_hash().to_string();
+
+        assert_eq!(hash.len(), 64);
+        assert_eq!(task.content_hash.as_deref(), Some(hash.as_str()));
+        // 相同内容的哈希一致，不同内容不同
+        assert_eq!(content_hash(b"<rss/>"), hash);
+        assert_ne!(content_hash(b"<rss version=\"2.0\"/>"), hash);
+    }
+
+    #[test]
+    fn test_stuck_task_reports_current_stage() {
+        let mut task = Task::new(5, "https://example.com/feed.xml".to_string(), 0);
+        task.add_stage("fetching");
+        task.complete_stage(Value::Null);
+        task.add_stage("parsing");
+        task.stages.last_mut().unwrap().start_time = Some(Instant::now() - Duration::from_secs(30));
+
+        let stuck = StuckTask::from_task(&task).unwrap();
+        assert_eq!(stuck.stage.as_deref(), Some("parsing"));
+        assert_eq!(stuck.status, StageStatus::InProgress);
+        assert!(stuck.stuck_for >= Duration::from_secs(30));
+
+        // 已结束的任务不算卡住
+        task.fail_stage("parse error".to_string());
+        assert!(StuckTask::from_task(&task).is_none());
+    }
+}
@@ -1,22 +1,29 @@
 use super::distributor::Distributor;
-use super::inserter_refactored::BatchInserter;
-use super::pipeline::{Fetcher, Parser};
-use super::rss::RssFeedParser;
+use super::enclosure_resolver::EnclosureLengthResolver;
+use super::inserter_refactored::{BatchInserter, BatchInserterConfig};
+use super::parser_registry::{ParserRegistry, PodcastParser};
+use super::pipeline::Fetcher;
 use super::rss_fetcher::RssFetcher;
-use super::thread_manager::ThreadManager;
-use crate::crawler_refactor::task::Task;
-use crate::infrastructure::persistence::models::{NewEpisode, NewPodcast};
-use crate::infrastructure::AppState;
+use super::scheduler::{RecrawlScheduler, TaskSink};
+use super::thread_manager::{ThreadManager, WorkerScaling};
+use super::webhook::WebhookNotifier;
+use super::worker::Worker;
+use crate::crawler_refactor::task::{StuckTask, Task, TaskOptions, TaskSummary};
+use crate::infrastructure::error::{AppResult, DomainError, DomainErrorKind};
+use crate::infrastructure::persistence::models::{NewEpisode, NewFailedFeed, NewPodcast};
+use crate::infrastructure::{AppRepositories, AppState};
+use async_trait::async_trait;
 use serde::Deserialize;
 use std::collections::{HashMap, VecDeque};
 use std::future::Future;
 use std::pin::Pin;
 use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock, Weak};
 use std::time::Duration;
-use tokio::sync::{broadcast, RwLock};
+use tokio::sync::{broadcast, watch, Mutex, RwLock};
 use tokio_util::sync::CancellationToken;
 use tokio_util::task::TaskTracker;
+use tracing::Instrument;
 
 pub struct ShutdownCoordinator {
     pub worker_count: AtomicUsize,
@@ -56,10 +63,22 @@ struct ResultData {
 #[derive(Clone, Debug)]
 pub struct TaskWorkerMaps {
     worker_metadata: Arc<RwLock<HashMap<usize, RwLock<VecDeque<String>>>>>,
-    task_metadata: Arc<RwLock<HashMap<u64, RwLock<Task>>>>,
+    task_metadata: TaskMetadata,
+    // 每个 worker 上未完成的任务数，随任务状态写回增量维护
+    in_flight_by_worker: InFlightCounts,
     fetcher: Arc<dyn Fetcher + Send + Sync>,
-    parser: Arc<dyn Parser<(NewPodcast, Vec<NewEpisode>)> + Send + Sync>,
+    parsers: Arc<ParserRegistry>,
     batch_inserter: Arc<BatchInserter>,
+    webhook: Option<WebhookNotifier>,
+    enclosure_resolver: Option<EnclosureLengthResolver>,
+    // 单个任务抓取、解析、写库的总时限，None 表示不限时
+    task_deadline: Option<Duration>,
+    repositories: Arc<AppRepositories>,
+    paused: Arc<watch::Sender<bool>>,
+    // 任务进入新阶段或状态变化时发布任务概要，没有订阅者时直接丢弃
+    task_events: broadcast::Sender<TaskSummary>,
+    // 由 TaskManagementSystem 在创建 distributor 后设置，用于提交迁移后的 feed 地址
+    task_sink: OnceLock<Weak<DistributorSink>>,
 }
 
 impl Default for TaskWorkerMaps {
@@ -68,40 +87,117 @@ impl Default for TaskWorkerMaps {
     }
 }
 
+type TaskMetadata = Arc<RwLock<HashMap<u64, RwLock<Task>>>>;
+
+type InFlightCounts = Arc<std::sync::Mutex<HashMap<usize, usize>>>;
+
+// 任务事件的缓冲数量，订阅者落后超过该数量时丢失最早的事件
+const TASK_EVENT_CAPACITY: usize = 1024;
+
+// 阶段或状态与之前不同时发布事件
+fn publish_task_transition(
+    task_events: &broadcast::Sender<TaskSummary>,
+    previous: Option<&Task>,
+    task: &Task,
+) {
+    let summary = TaskSummary::from(task);
+    let changed = previous.is_none_or(|previous| {
+        let previous = TaskSummary::from(previous);
+        previous.stage != summary.stage || previous.status != summary.status
+    });
+    if changed {
+        let _ = task_events.send(summary);
+    }
+}
+
+// 按任务写回前后的状态调整 worker 的未完成任务数
+fn track_in_flight(counts: &InFlightCounts, previous: Option<&Task>, task: &Task) {
+    let mut counts = counts.lock().unwrap();
+    if let Some(previous) = previous.filter(|previous| !previous.is_finished()) {
+        if let Some(count) = counts.get_mut(&previous.target_thread_id) {
+            *count = count.saturating_sub(1);
+        }
+    }
+    if !task.is_finished() {
+        *counts.entry(task.target_thread_id).or_insert(0) += 1;
+    }
+}
+
 fn create_process_batch_fn(
     state: Arc<AppState>,
+    task_metadata: TaskMetadata,
+    in_flight_by_worker: InFlightCounts,
+    webhook: Option<WebhookNotifier>,
+    task_events: broadcast::Sender<TaskSummary>,
 ) -> impl Fn(Vec<Task>) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send>> + Clone {
     move |batch: Vec<Task>| {
         let state = state.clone();
+        let task_metadata = task_metadata.clone();
+        let in_flight_by_worker = in_flight_by_worker.clone();
+        let webhook = webhook.clone();
+        let task_events = task_events.clone();
         Box::pin(async move {
             let podcast_repo = &state.repositories.podcast;
 
+            let webhook = &webhook;
+            let task_metadata = &task_metadata;
+            let in_flight_by_worker = &in_flight_by_worker;
+            let task_events = &task_events;
             for mut task in batch {
-                if let Some(result_data) = task.get_stage_result_data_by_name("parsing") {
-                    // 解码 JSON 数据
-                    if let Ok(result) = serde_json::from_value::<ResultData>(result_data.clone()) {
-                        // 插入数据库
-                        match podcast_repo
-                            .insert_with_episodes(&result.podcast, &result.episodes)
-                            .await
+                // 在任务自身的 span 中插入，使日志带上 task_id/url
+                let span = task.span.clone();
+                async move {
+                    if let Some(result_data) = task.get_stage_result_data_by_name("parsing") {
+                        // 解码 JSON 数据
+                        if let Ok(mut result) =
+                            serde_json::from_value::<ResultData>(result_data.clone())
                         {
-                            Ok(_) => {
-                                if task.get_task_status() == super::task::StageStatus::InProgress {
-                                    task.complete_stage(serde_json::json!({"status": "success"}));
+                            // 与播客一起保存内容哈希，下次内容未变化时跳过解析
+                            result.podcast.content_hash = task.content_hash.clone();
+                            // 增量插入数据库：只写入尚不存在的剧集，inserted_episodes 记录新增数量
+                            match podcast_repo
+                                .insert_new_episodes(&result.podcast, &result.episodes)
+                                .await
+                            {
+                                Ok(inserted) => {
+                                    if task.get_task_status()
+                                        == super::task::StageStatus::InProgress
+                                    {
+                                        task.complete_stage(serde_json::json!({
+                                            "status": "success",
+                                            "inserted_episodes": inserted,
+                                        }));
+                                    }
                                 }
-                            }
-                            Err(e) => {
-                                if task.get_task_status() == super::task::StageStatus::InProgress {
-                                    task.fail_stage(format!("Failed to insert podcast: {}", e));
+                                Err(e) => {
+                                    if task.get_task_status()
+                                        == super::task::StageStatus::InProgress
+                                    {
+                                        task.fail_stage(format!("Failed to insert podcast: {}", e));
+                                    }
                                 }
                             }
+                        } else if task.get_task_status() == super::task::StageStatus::InProgress {
+                            task.fail_stage("Failed to decode podcast data".to_string());
                         }
-                    } else if task.get_task_status() == super::task::StageStatus::InProgress {
-                        task.fail_stage("Failed to decode podcast data".to_string());
+                    } else {
+                        task.fail_stage("No result data available".to_string());
+                    }
+
+                    if let Some(webhook) = webhook {
+                        webhook.notify(&task);
+                    }
+
+                    // 回写最终状态，使任务不再被视为进行中
+                    if let Some(lock) = task_metadata.read().await.get(&task.id) {
+                        let mut stored = lock.write().await;
+                        publish_task_transition(task_events, Some(&stored), &task);
+                        track_in_flight(in_flight_by_worker, Some(&stored), &task);
+                        *stored = task;
                     }
-                } else {
-                    task.fail_stage("No result data available".to_string());
                 }
+                .instrument(span)
+                .await;
             }
 
             Ok(())
@@ -111,23 +207,44 @@ fn create_process_batch_fn(
 
 impl TaskWorkerMaps {
     pub fn new(state: Arc<AppState>) -> Self {
-        let fetcher = Arc::new(RssFetcher::new());
-        let parser = Arc::new(RssFeedParser::new());
+        let fetcher = Arc::new(
+            RssFetcher::from_config(&state.settings.crawler)
+                .expect("Crawler config is validated at startup"),
+        );
+        let parsers = Arc::new(ParserRegistry::from_config(&state.settings.crawler));
+        let task_metadata: TaskMetadata = Arc::new(RwLock::new(HashMap::new()));
+        let in_flight_by_worker: InFlightCounts = Arc::new(std::sync::Mutex::new(HashMap::new()));
+        let webhook = WebhookNotifier::from_config(&state.settings.crawler);
+        let enclosure_resolver = EnclosureLengthResolver::from_config(&state.settings.crawler);
+        let (task_events, _) = broadcast::channel(TASK_EVENT_CAPACITY);
 
         // Initialize batch inserter
         let batch_inserter = Arc::new(BatchInserter::new(
-            3,  // batch size
-            10, // max concurrent inserts
-            create_process_batch_fn(state.clone()),
-            Duration::from_secs(5), // batch timeout
+            BatchInserterConfig::from_config(&state.settings.crawler),
+            create_process_batch_fn(
+                state.clone(),
+                task_metadata.clone(),
+                in_flight_by_worker.clone(),
+                webhook.clone(),
+                task_events.clone(),
+            ),
         ));
 
         TaskWorkerMaps {
             worker_metadata: Arc::new(RwLock::new(HashMap::new())),
-            task_metadata: Arc::new(RwLock::new(HashMap::new())),
+            task_metadata,
+            in_flight_by_worker,
             fetcher,
-            parser,
+            parsers,
             batch_inserter,
+            webhook,
+            enclosure_resolver,
+            task_deadline: (state.settings.crawler.per_task_timeout_seconds > 0)
+                .then(|| Duration::from_secs(state.settings.crawler.per_task_timeout_seconds)),
+            repositories: state.repositories.clone(),
+            paused: Arc::new(watch::channel(false).0),
+            task_events,
+            task_sink: OnceLock::new(),
         }
     }
 
@@ -140,6 +257,14 @@ impl TaskWorkerMaps {
     // Insert a MyStruct into map_struct
     pub async fn insert_task(&self, key: u64, value: Task) {
         let mut map = self.task_metadata.write().await;
+        publish_task_transition(&self.task_events, None, &value);
+        match map.get(&key) {
+            Some(lock) => {
+                let previous = lock.read().await;
+                track_in_flight(&self.in_flight_by_worker, Some(&previous), &value);
+            }
+            None => track_in_flight(&self.in_flight_by_worker, None, &value),
+        }
         map.insert(key, RwLock::new(value));
     }
 
@@ -166,6 +291,8 @@ impl TaskWorkerMaps {
     pub async fn update_task(&self, key: u64, value: Task) {
         if let Some(lock) = self.task_metadata.read().await.get(&key) {
             let mut struct_value = lock.write().await;
+            publish_task_transition(&self.task_events, Some(&struct_value), &value);
+            track_in_flight(&self.in_flight_by_worker, Some(&struct_value), &value);
             *struct_value = value;
         }
     }
@@ -199,26 +326,252 @@ impl TaskWorkerMaps {
         .await
     }
 
+    // Find an unfinished task crawling the given URL
+    pub async fn find_in_flight_task(&self, url: &str) -> Option<u64> {
+        let map = self.task_metadata.read().await;
+        for (id, lock) in map.iter() {
+            let task = lock.read().await;
+            if task.payload == url && !task.is_finished() {
+                return Some(*id);
+            }
+        }
+        None
+    }
+
+    // Count tasks that have not completed or failed yet
+    pub async fn count_in_flight_tasks(&self) -> usize {
+        let map = self.task_metadata.read().await;
+        let mut count = 0;
+        for lock in map.values() {
+            if !lock.read().await.is_finished() {
+                count += 1;
+            }
+        }
+        count
+    }
+
+    // Count unfinished tasks per assigned worker
+    pub fn count_in_flight_tasks_by_worker(&self) -> HashMap<usize, usize> {
+        self.in_flight_by_worker.lock().unwrap().clone()
+    }
+
+    pub fn get_task_deadline(&self) -> Option<Duration> {
+        self.task_deadline
+    }
+
     pub fn get_fetcher(&self) -> Arc<dyn Fetcher + Send + Sync> {
         self.fetcher.clone()
     }
 
-    pub fn get_parser(&self) -> Arc<dyn Parser<(NewPodcast, Vec<NewEpisode>)> + Send + Sync> {
-        self.parser.clone()
+    /// 根据任务抓取到的内容选择解析器
+    pub fn get_parser(&self, task: &Task) -> Option<PodcastParser> {
+        self.parsers
+            .select(task.content_type.as_deref(), &task.content)
+    }
+
+    pub fn get_enclosure_resolver(&self) -> Option<&EnclosureLengthResolver> {
+        self.enclosure_resolver.as_ref()
+    }
+
+    // 计算抓取内容的哈希，与上次爬取保存的相同时返回 true
+    pub async fn is_content_unchanged(&self, task: &mut Task) -> bool {
+        let hash = task.compute_content_hash().to_string();
+        match self
+            .repositories
+            .podcast
+            .get_content_hash(task.get_feed_url())
+            .await
+        {
+            Ok(stored) => stored.as_deref() == Some(hash.as_str()),
+            Err(e) => {
+                // 查询失败时按内容已变化处理，正常解析
+                tracing::warn!(
+                    "⚠️ Failed to load content hash for '{}': {}",
+                    task.get_feed_url(),
+                    e
+                );
+                false
+            }
+        }
+    }
+
+    /// 跟随 `<itunes:new-feed-url>` 声明的新地址
+    ///
+    /// 已保存的播客改为使用新地址，本次解析结果也写入新地址，然后提交新地址的爬取任务。
+    /// 新地址与任务访问过的地址重复时不跟随。返回新任务的 ID。
+    pub async fn follow_feed_move(&self, task: &mut Task) -> Option<u64> {
+        let new_url = task.feed_move_target()?.to_string();
+        let old_url = task.get_feed_url().to_string();
+        match self
+            .repositories
+            .podcast
+            .update_feed_url(&old_url, &new_url)
+            .await
+        {
+            Ok(moved) => tracing::info!(
+                "🚚 Feed '{}' moved to '{}' (stored podcast updated: {})",
+                old_url,
+                new_url,
+                moved
+            ),
+            Err(e) => {
+                // 保持原地址，下次爬取时再尝试迁移
+                tracing::warn!(
+                    "⚠️ Failed to move feed '{}' to '{}': {}",
+                    old_url,
+                    new_url,
+                    e
+                );
+                return None;
+            }
+        }
+        if let Some(mut result_data) = task.get_stage_result_data_by_name("parsing").cloned() {
+            result_data["podcast"]["rss_feed_url"] = serde_json::json!(new_url);
+            task.set_stage_result_data("parsing", result_data);
+        }
+
+        let sink = self.task_sink.get().and_then(Weak::upgrade)?;
+        let options = TaskOptions {
+            priority: Some(task.priority),
+            visited_feed_urls: task.visited_feed_urls_after_move(),
+            ..TaskOptions::default()
+        };
+        match sink.enqueue_with_options(&new_url, options).await {
+            Ok(id) => Some(id),
+            Err(e) => {
+                tracing::warn!("⚠️ Failed to enqueue moved feed '{}': {}", new_url, e);
+                None
+            }
+        }
+    }
+
+    // 将用尽重试次数的任务写入 failed_feeds，写入失败只记录警告
+    pub async fn record_failed_feed(&self, task: &Task, error: &str) {
+        let failed_feed = NewFailedFeed {
+            url: task.payload.clone(),
+            last_error: Some(error.to_string()),
+            attempts: (task.retries + 1) as i32,
+            failed_at: chrono::Utc::now(),
+        };
+        if let Err(e) = self.repositories.failed_feed.record(&failed_feed).await {
+            tracing::warn!("⚠️ Failed to record failed feed '{}': {}", task.payload, e);
+        }
+    }
+
+    // 内容未变化时跳过写库，仍然更新播客的 last_fetched_at
+    pub async fn touch_last_fetched(&self, task: &Task) {
+        if let Err(e) = self
+            .repositories
+            .podcast
+            .touch_last_fetched(task.get_feed_url())
+            .await
+        {
+            tracing::warn!(
+                "⚠️ Failed to update last_fetched_at for '{}': {}",
+                task.get_feed_url(),
+                e
+            );
+        }
+    }
+
+    // 未送达 worker 的任务（广播滞后丢失或分发失败）直接失败，不再重试
+    pub async fn fail_undelivered_task(&self, task_id: u64, error: &str) {
+        let Some(mut task) = self.read_task(&task_id).await else {
+            return;
+        };
+        if task.is_finished() {
+            return;
+        }
+        // 失败记录在 fetching 阶段上
+        if task.get_task_status() != super::task::StageStatus::InProgress {
+            task.add_stage("fetching");
+        }
+        task.error_message = Some(error.to_string());
+        task.fail_stage(error.to_string());
+        self.update_task(task.id, task.clone()).await;
+        self.notify_task_finished(&task);
+        self.record_failed_feed(&task, error).await;
+    }
+
+    // 暂停或恢复 worker 领取新任务，状态变化时返回 true
+    pub fn set_paused(&self, paused: bool) -> bool {
+        self.paused.send_if_modified(|current| {
+            let changed = *current != paused;
+            *current = paused;
+            changed
+        })
+    }
+
+    pub fn is_paused(&self) -> bool {
+        *self.paused.borrow()
+    }
+
+    // worker 通过该接收端等待暂停状态的变化
+    pub fn subscribe_paused(&self) -> watch::Receiver<bool> {
+        self.paused.subscribe()
+    }
+
+    // 订阅任务阶段和状态的变化
+    pub fn subscribe_task_events(&self) -> broadcast::Receiver<TaskSummary> {
+        self.task_events.subscribe()
     }
 
     pub fn get_inserter(&self) -> Arc<BatchInserter> {
         self.batch_inserter.clone()
     }
+
+    // Flush tasks still queued in the batch inserter and wait for the inserts
+    pub async fn finish_inserter(&self) -> Result<usize, String> {
+        self.batch_inserter.finish().await
+    }
+
+    // Report a task that ended before reaching the inserter to the webhook, if configured
+    pub fn notify_task_finished(&self, task: &Task) {
+        if let Some(webhook) = &self.webhook {
+            webhook.notify(task);
+        }
+    }
+}
+
+// 调度器通过 distributor 提交任务，与 add_task 共用去重逻辑
+struct DistributorSink {
+    distributor: Arc<Mutex<Distributor>>,
+    workers: Vec<Worker>,
+    task_worker_maps: Arc<TaskWorkerMaps>,
+}
+
+impl DistributorSink {
+    async fn enqueue_with_options(&self, url: &str, options: TaskOptions) -> AppResult<u64> {
+        let mut workers = self.workers.clone();
+        self.distributor
+            .lock()
+            .await
+            .create_task(url, options, &mut workers)
+            .await
+    }
+}
+
+#[async_trait]
+impl TaskSink for DistributorSink {
+    async fn in_flight_count(&self) -> usize {
+        self.task_worker_maps.count_in_flight_tasks().await
+    }
+
+    async fn enqueue(&self, url: &str) -> AppResult<u64> {
+        self.enqueue_with_options(url, TaskOptions::default()).await
+    }
 }
 
 /// Public-facing TaskManagementSystem structure
 pub struct TaskManagementSystem {
-    distributor: Distributor,
+    distributor: Arc<Mutex<Distributor>>,
     thread_manager: ThreadManager,
     task_tracker: Arc<TaskTracker>,
     cancellation_token: CancellationToken,
     task_worker_maps: Arc<TaskWorkerMaps>,
+    scheduler: Option<Arc<RecrawlScheduler>>,
+    // worker 只持有弱引用，由这里保持存活
+    _task_sink: Arc<DistributorSink>,
 }
 
 impl TaskManagementSystem {
@@ -232,16 +585,15 @@ impl TaskManagementSystem {
         let cancellation_token = CancellationToken::new();
         let (task_tx, _task_rx) = broadcast::channel::<Task>(5000);
         let task_worker_maps = Arc::new(TaskWorkerMaps::new(state.clone()));
+        // Counted up by the thread manager as workers start
         let shutdown_coordinator = Arc::new(ShutdownCoordinator {
-            worker_count: AtomicUsize::new(worker_count),
+            worker_count: AtomicUsize::new(0),
             timer_queue_notify: CancellationToken::new(),
             shutdown_complete: tokio::sync::Notify::new(),
         });
-        let distributor = Distributor::new(task_tx.clone(), task_worker_maps.clone());
-
         let thread_manager = ThreadManager::new(
             task_tx,
-            worker_count,
+            WorkerScaling::from_config(&state.settings.crawler, worker_count),
             max_history_size,
             task_tracker.clone(),
             cancellation_token.clone(),
@@ -249,6 +601,27 @@ impl TaskManagementSystem {
             task_worker_maps.clone(),
         )
         .await;
+        let distributor = Arc::new(Mutex::new(Distributor::new(
+            thread_manager.priority_queue.clone(),
+            task_worker_maps.clone(),
+            state.settings.crawler.default_max_retries,
+            state.settings.crawler.dispatch_strategy,
+            cancellation_token.clone(),
+        )));
+        let task_sink = Arc::new(DistributorSink {
+            distributor: distributor.clone(),
+            workers: thread_manager.workers.clone(),
+            task_worker_maps: task_worker_maps.clone(),
+        });
+        let _ = task_worker_maps.task_sink.set(Arc::downgrade(&task_sink));
+        let scheduler = state.settings.crawler.scheduler_enabled.then(|| {
+            Arc::new(RecrawlScheduler::new(
+                state.repositories.clone(),
+                task_sink.clone(),
+                &state.settings.crawler,
+                cancellation_token.clone(),
+            ))
+        });
 
         tracing::info!("🎉 TaskManagementSystem: Initialization complete");
 
@@ -258,6 +631,8 @@ impl TaskManagementSystem {
             task_tracker,
             cancellation_token,
             task_worker_maps,
+            scheduler,
+            _task_sink: task_sink,
         }
     }
 
@@ -265,24 +640,42 @@ impl TaskManagementSystem {
     pub async fn start(&mut self) {
         tracing::info!("🔥 TaskManagementSystem: Starting system");
         self.thread_manager.start().await;
+        if let Some(scheduler) = &self.scheduler {
+            tracing::info!("🗓️ TaskManagementSystem: Starting recrawl scheduler");
+            let scheduler = scheduler.clone();
+            tokio::spawn(async move { scheduler.run().await });
+        }
         tracing::info!("✅ TaskManagementSystem: System started successfully");
     }
 
     /// Add a new task
-    pub async fn add_task(&mut self, url: &str) -> Result<(), String> {
+    ///
+    /// Higher `options.priority` values are dispatched first; `None` uses `PRIORITY_NORMAL`.
+    /// `options.auth` is sent as HTTP Basic Auth when fetching the feed.
+    /// `options.max_retries` overrides `CrawlerConfig.default_max_retries`.
+    /// Returns the id of the new task, or fails with `DomainErrorKind::Duplicate`
+    /// if the URL is already being crawled.
+    pub async fn add_task(&mut self, url: &str, options: TaskOptions) -> AppResult<u64> {
         tracing::info!("➕ TaskManagementSystem: Adding task for URL '{}'", url);
 
         // Create a mutable reference to workers
         let mut workers = self.thread_manager.workers.clone();
 
         // Use the distributor to create and distribute the task
-        match self.distributor.create_task(url, &mut workers).await {
-            Ok(_) => {
+        match self
+            .distributor
+            .lock()
+            .await
+            .create_task(url, options, &mut workers)
+            .await
+        {
+            Ok(task_id) => {
                 tracing::info!(
-                    "🚀 TaskManagementSystem: Task for '{}' added successfully",
+                    "🚀 TaskManagementSystem: Task {} for '{}' added successfully",
+                    task_id,
                     url
                 );
-                Ok(())
+                Ok(task_id)
             }
             Err(e) => {
                 tracing::error!(
@@ -295,6 +688,98 @@ impl TaskManagementSystem {
         }
     }
 
+    /// Add a batch of tasks with default options
+    ///
+    /// The distributor is locked once for the whole batch. Returns one result
+    /// per URL, in input order, with the same errors as [`add_task`](Self::add_task).
+    pub async fn add_tasks(&mut self, urls: &[String]) -> Vec<AppResult<u64>> {
+        tracing::info!("➕ TaskManagementSystem: Adding {} tasks", urls.len());
+
+        let mut workers = self.thread_manager.workers.clone();
+        let mut distributor = self.distributor.lock().await;
+        let mut results = Vec::with_capacity(urls.len());
+        for url in urls {
+            let result = distributor
+                .create_task(url, TaskOptions::default(), &mut workers)
+                .await;
+            if let Err(e) = &result {
+                tracing::warn!(
+                    "⚠️ TaskManagementSystem: Rejected task for '{}': {}",
+                    url,
+                    e
+                );
+            }
+            results.push(result);
+        }
+
+        let accepted = results.iter().filter(|result| result.is_ok()).count();
+        tracing::info!(
+            "🚀 TaskManagementSystem: Accepted {} of {} tasks",
+            accepted,
+            urls.len()
+        );
+        results
+    }
+
+    /// Cancel a single task
+    ///
+    /// A fetch in progress is aborted, and a task that is still queued or
+    /// waiting for a retry fails as soon as a worker picks it up. The task
+    /// ends in the failed state with the error "cancelled". Fails with
+    /// `DomainErrorKind::NotFound` for unknown ids and
+    /// `DomainErrorKind::InvalidState` if the task already finished.
+    pub async fn cancel_task(&self, id: u64) -> AppResult<()> {
+        let task = self.task_worker_maps.read_task(&id).await.ok_or_else(|| {
+            DomainError::new(
+                DomainErrorKind::NotFound,
+                format!("Task {} not found", id),
+                None,
+                None,
+            )
+        })?;
+        if task.is_finished() {
+            return Err(DomainError::new(
+                DomainErrorKind::InvalidState,
+                format!("Task {} has already finished", id),
+                None,
+                None,
+            )
+            .into());
+        }
+        task.cancellation_token.cancel();
+        tracing::info!("🚫 TaskManagementSystem: Cancelled task {}", id);
+        Ok(())
+    }
+
+    /// Stop workers from picking up new tasks
+    ///
+    /// Tasks that are already being processed run to completion. New and
+    /// retried tasks stay queued until [`resume`](Self::resume) is called.
+    pub fn pause(&self) {
+        if self.task_worker_maps.set_paused(true) {
+            tracing::info!("⏸️ TaskManagementSystem: Paused, workers stop picking up tasks");
+        }
+    }
+
+    /// Let paused workers pick up queued tasks again
+    pub fn resume(&self) {
+        if self.task_worker_maps.set_paused(false) {
+            tracing::info!("▶️ TaskManagementSystem: Resumed");
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.task_worker_maps.is_paused()
+    }
+
+    /// Subscribe to task progress
+    ///
+    /// A summary is published when a task is created and whenever its stage
+    /// or status changes. Receivers that fall behind skip the oldest events.
+    pub fn subscribe_task_events(&self) -> broadcast::Receiver<TaskSummary> {
+        self.task_worker_maps.subscribe_task_events()
+    }
+
     // Get real-time task metadata
     pub async fn get_task_info(&self) -> Vec<Task> {
         tracing::info!("📋 TaskManagementSystem: Retrieving task information");
@@ -308,12 +793,23 @@ impl TaskManagementSystem {
 
     /// Wait for all tasks to complete and return the list of tasks
     pub async fn wait_for_all_tasks_completed(&self) -> Vec<Task> {
-        self.wait_for_all_tasks_completed_with_timeout(Duration::from_secs(300))
+        match self
+            .wait_for_all_tasks_completed_with_timeout(Duration::from_secs(300))
             .await
+        {
+            Ok(tasks) => tasks,
+            Err(_) => self.task_worker_maps.read_all_tasks().await,
+        }
     }
 
     /// Wait for all tasks to complete with a custom timeout
-    pub async fn wait_for_all_tasks_completed_with_timeout(&self, timeout: Duration) -> Vec<Task> {
+    ///
+    /// Returns all tasks on completion, or on timeout the tasks that have not
+    /// finished yet with the stage they are stuck in.
+    pub async fn wait_for_all_tasks_completed_with_timeout(
+        &self,
+        timeout: Duration,
+    ) -> Result<Vec<Task>, Vec<StuckTask>> {
         tracing::info!(
             "⏳ TaskManagementSystem: Waiting for all tasks to complete (timeout: {:?})",
             timeout
@@ -349,18 +845,37 @@ impl TaskManagementSystem {
                     completed_tasks.iter().filter(|t| t.is_failed()).count()
                 );
 
-                completed_tasks
+                Ok(completed_tasks)
             }
             Err(_) => {
                 tracing::error!("❌ TaskManagementSystem: Timeout waiting for tasks to complete");
                 tracing::info!("🚨 Diagnostic details at timeout:");
                 tracing::info!("   - Elapsed time: {:?}", start_time.elapsed());
 
+                // Report tasks that are still running before forcing shutdown
+                let stuck_tasks: Vec<StuckTask> = self
+                    .task_worker_maps
+                    .read_all_tasks()
+                    .await
+                    .iter()
+                    .filter_map(StuckTask::from_task)
+                    .collect();
+                tracing::info!("   - Stuck tasks: {}", stuck_tasks.len());
+                for stuck in &stuck_tasks {
+                    tracing::warn!(
+                        "   - Task {} ({}) stuck in stage {} ({:?}) for {:?}",
+                        stuck.task.id,
+                        stuck.task.payload,
+                        stuck.stage.as_deref().unwrap_or("<none>"),
+                        stuck.status,
+                        stuck.stuck_for
+                    );
+                }
+
                 // Attempt to force shutdown
                 self.cancellation_token.cancel();
 
-                // Return any tasks that have been processed
-                self.task_worker_maps.read_all_tasks().await
+                Err(stuck_tasks)
             }
         }
     }
@@ -396,7 +911,7 @@ impl TaskManagementSystem {
 
         match shutdown_result {
             Ok(_) => {
-                tracing::info!("👋 TaskManagementSystem: Shutdown completed successfully");
+                tracing::info!("👋 TaskManagementSystem: Workers stopped");
             }
             Err(_) => {
                 tracing::error!("❌ TaskManagementSystem: Shutdown timed out");
@@ -407,6 +922,19 @@ impl TaskManagementSystem {
                 );
             }
         }
+
+        // Flush parsed tasks that have not reached a full batch yet
+        match self.task_worker_maps.finish_inserter().await {
+            Ok(batches) => tracing::info!(
+                "💾 TaskManagementSystem: Batch inserter drained ({} batches processed)",
+                batches
+            ),
+            Err(e) => tracing::error!(
+                "❌ TaskManagementSystem: Failed to drain batch inserter: {}",
+                e
+            ),
+        }
+        tracing::info!("👋 TaskManagementSystem: Shutdown completed successfully");
     }
 }
 
@@ -417,6 +945,59 @@ mod tests {
     use std::time::Duration;
     use tokio::runtime::Runtime;
 
+    #[test]
+    fn test_task_events_published_on_transition() {
+        let (task_events, mut rx) = broadcast::channel(16);
+        let mut task = Task::new(1, "https://example.com/feed.xml".to_string(), 3);
+
+        // 新任务总是发布
+        publish_task_transition(&task_events, None, &task);
+        assert_eq!(rx.try_recv().unwrap().id, 1);
+
+        // 阶段和状态都未变化时不发布
+        let previous = task.clone();
+        publish_task_transition(&task_events, Some(&previous), &task);
+        assert!(rx.try_recv().is_err());
+
+        // 进入新阶段时发布
+        task.add_stage("fetching");
+        publish_task_transition(&task_events, Some(&previous), &task);
+        let summary = rx.try_recv().unwrap();
+        assert_eq!(summary.stage.as_deref(), Some("fetching"));
+
+        // 同一阶段的状态变化也会发布
+        let previous = task.clone();
+        task.fail_stage("timeout".to_string());
+        publish_task_transition(&task_events, Some(&previous), &task);
+        assert_eq!(rx.try_recv().unwrap().status, task.get_task_status());
+    }
+
+    #[test]
+    fn test_in_flight_counts_follow_task_updates() {
+        let counts: InFlightCounts = Arc::new(std::sync::Mutex::new(HashMap::new()));
+        let mut task = Task::new(1, "https://example.com/feed.xml".to_string(), 3);
+        task.target_thread_id = 2;
+
+        // 新任务计入所分配的 worker
+        track_in_flight(&counts, None, &task);
+        assert_eq!(counts.lock().unwrap().get(&2), Some(&1));
+
+        // 重试时换到其他 worker，计数随之转移
+        let previous = task.clone();
+        task.target_thread_id = 0;
+        track_in_flight(&counts, Some(&previous), &task);
+        assert_eq!(counts.lock().unwrap().get(&2), Some(&0));
+        assert_eq!(counts.lock().unwrap().get(&0), Some(&1));
+
+        // 任务结束后不再计入，重复写回已结束的任务也不会减成负数
+        let previous = task.clone();
+        task.add_stage("fetching");
+        task.fail_stage("timeout".to_string());
+        track_in_flight(&counts, Some(&previous), &task);
+        track_in_flight(&counts, Some(&task.clone()), &task);
+        assert_eq!(counts.lock().unwrap().get(&0), Some(&0));
+    }
+
     #[tokio::test]
     async fn test_task_creation_and_distribution() {
         // Initialize system with 3 workers
@@ -425,9 +1006,14 @@ mod tests {
         system.start().await;
 
         // Add some tasks
-        system.add_task("http://example1.com").await;
         system
-            .add_task("https://justpodmedia.com/rss/middle-ground.xml")
+            .add_task("http://example1.com", TaskOptions::default())
+            .await;
+        system
+            .add_task(
+                "https://justpodmedia.com/rss/middle-ground.xml",
+                TaskOptions::default(),
+            )
             .await;
         // system.add_task("http://example3.com").await;
 
@@ -436,11 +1022,15 @@ mod tests {
         // system.add_task("http://example4.com").await;
         // system.add_task("http://example5.com").await;
         // tokio::time::sleep(Duration::from_millis(1000)).await;
-        system.add_task("http://example6.com").await;
-        system.add_task("http://example7.com").await;
+        system
+            .add_task("http://example6.com", TaskOptions::default())
+            .await;
+        system
+            .add_task("http://example7.com", TaskOptions::default())
+            .await;
         tokio::time::sleep(Duration::from_millis(1000)).await;
-        system.add_task("").await;
-        system.add_task("").await;
+        system.add_task("", TaskOptions::default()).await;
+        system.add_task("", TaskOptions::default()).await;
         // Check task metadata
         let _a = system.wait_for_all_tasks_completed().await;
         system.shutdown().await;
@@ -469,7 +1059,7 @@ mod tests {
             let test_url = "http://example.com";
             for _ in 0..5 {
                 tokio::time::sleep(Duration::from_millis(100)).await;
-                system.add_task(test_url).await;
+                system.add_task(test_url, TaskOptions::default()).await;
             }
 
             tokio::time::sleep(Duration::from_millis(100)).await;
@@ -500,7 +1090,7 @@ mod tests {
 
             // Add a task with empty payload (which should fail)
             println!("🚀 Adding task with empty payload");
-            system.add_task("").await;
+            system.add_task("", TaskOptions::default()).await;
 
             // Wait for initial attempt and retries
             println!("⏳ Waiting for retry attempts");
@@ -521,6 +1111,293 @@ mod tests {
         });
     }
 
+    #[tokio::test]
+    async fn test_add_tasks_reports_each_url() {
+        let state = initialize().await.unwrap();
+        let mut system = TaskManagementSystem::new(Arc::new(state), 2, 5).await;
+
+        let urls = vec![
+            "http://example.com/a.xml".to_string(),
+            "http://example.com/b.xml".to_string(),
+            "http://example.com/a.xml".to_string(),
+        ];
+        let results = system.add_tasks(&urls).await;
+        assert_eq!(results.len(), 3);
+        assert!(results[0].is_ok());
+        assert!(results[1].is_ok());
+        // The repeated URL is still in flight
+        assert!(results[2].is_err());
+    }
+
+    #[tokio::test]
+    async fn test_unchanged_content_is_inserted_once() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_bytes(
+                    include_bytes!("../../tests/data/five_episode_feed.xml").to_vec(),
+                ),
+            )
+            .mount(&mock_server)
+            .await;
+        // 每次运行使用不同的 feed URL，避免受数据库中已有记录影响
+        let url = format!(
+            "{}/feed-{}.xml",
+            mock_server.uri(),
+            chrono::Utc::now().timestamp_nanos_opt().unwrap()
+        );
+
+        let state = initialize().await.unwrap();
+        let mut system = TaskManagementSystem::new(Arc::new(state), 1, 5).await;
+        system.start().await;
+
+        let first = system.add_task(&url, TaskOptions::default()).await.unwrap();
+        system.wait_for_all_tasks_completed().await;
+        let second = system.add_task(&url, TaskOptions::default()).await.unwrap();
+        let tasks = system.wait_for_all_tasks_completed().await;
+        system.shutdown().await;
+
+        let inserting = |id: u64| {
+            tasks
+                .iter()
+                .find(|task| task.id == id)
+                .and_then(|task| task.get_stage_result_data_by_name("inserting"))
+                .cloned()
+                .unwrap()
+        };
+        assert_eq!(inserting(first)["status"], "success");
+        assert_eq!(inserting(first)["inserted_episodes"], 5);
+        // 第二次抓取到相同内容，跳过解析与写库
+        assert_eq!(inserting(second)["status"], "unchanged");
+        assert_eq!(inserting(second)["inserted_episodes"], 0);
+        let second_task = tasks.iter().find(|task| task.id == second).unwrap();
+        assert!(second_task
+            .get_stage_result_data_by_name("parsing")
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_new_feed_url_is_followed() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+        let suffix = chrono::Utc::now().timestamp_nanos_opt().unwrap();
+        let old_url = format!("{}/old-{}.xml", mock_server.uri(), suffix);
+        let new_url = format!("{}/new-{}.xml", mock_server.uri(), suffix);
+        // 新地址返回的 feed 又指回旧地址，不应再次跟随
+        let feed = include_str!("../../tests/data/moved_feed.xml");
+        Mock::given(method("GET"))
+            .and(path(format!("/old-{}.xml", suffix)))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(feed.replace("https://example.com/moved/new.xml", &new_url)),
+            )
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(format!("/new-{}.xml", suffix)))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(feed.replace("https://example.com/moved/new.xml", &old_url)),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let state = Arc::new(initialize().await.unwrap());
+        let mut system = TaskManagementSystem::new(state.clone(), 1, 5).await;
+        system.start().await;
+        system
+            .add_task(&old_url, TaskOptions::default())
+            .await
+            .unwrap();
+        // 新任务在旧任务写库前提交，等待时已包含在内
+        let tasks = system.wait_for_all_tasks_completed().await;
+        system.shutdown().await;
+
+        let urls: Vec<&str> = tasks.iter().map(|task| task.payload.as_str()).collect();
+        assert_eq!(urls.len(), 2, "unexpected tasks: {:?}", urls);
+        let moved = tasks.iter().find(|task| task.payload == new_url).unwrap();
+        assert_eq!(moved.visited_feed_urls, vec![old_url.clone()]);
+
+        let podcasts = &state.repositories.podcast;
+        assert_eq!(podcasts.get_content_hash(&old_url).await.unwrap(), None);
+        let stored = podcasts
+            .search_by_title("Moved Podcast", false)
+            .await
+            .unwrap()
+            .into_iter()
+            .find(|podcast| podcast.rss_feed_url.as_deref() == Some(new_url.as_str()))
+            .unwrap();
+        podcasts.delete_by_id(stored.podcast_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_funding_and_persons_round_trip() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_bytes(
+                    include_bytes!("../../tests/data/podcasting2_feed.xml").to_vec(),
+                ),
+            )
+            .mount(&mock_server)
+            .await;
+        let url = format!(
+            "{}/feed-{}.xml",
+            mock_server.uri(),
+            chrono::Utc::now().timestamp_nanos_opt().unwrap()
+        );
+
+        let state = Arc::new(initialize().await.unwrap());
+        let mut system = TaskManagementSystem::new(state.clone(), 1, 5).await;
+        system.start().await;
+        system.add_task(&url, TaskOptions::default()).await.unwrap();
+        system.wait_for_all_tasks_completed().await;
+        system.shutdown().await;
+
+        let podcasts = &state.repositories.podcast;
+        let stored = podcasts
+            .search_by_title("Podcasting 2.0 Test Podcast", false)
+            .await
+            .unwrap()
+            .into_iter()
+            .find(|podcast| podcast.rss_feed_url.as_deref() == Some(url.as_str()))
+            .unwrap();
+        podcasts.delete_by_id(stored.podcast_id).await.unwrap();
+        assert_eq!(
+            stored.funding_url.as_deref(),
+            Some("https://example.com/support")
+        );
+        assert_eq!(stored.funding_text.as_deref(), Some("Support the show!"));
+        let persons = stored.persons.unwrap();
+        assert_eq!(persons[0]["name"], "Alice Host");
+        assert_eq!(persons[0]["role"], "host");
+        assert_eq!(persons[1]["name"], "Bob Guest");
+        assert_eq!(persons.as_array().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_cancel_in_flight_task() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        // 响应一直不返回，模拟持续传输的 feed
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(30)))
+            .mount(&mock_server)
+            .await;
+
+        let state = initialize().await.unwrap();
+        let mut system = TaskManagementSystem::new(Arc::new(state), 1, 5).await;
+        system.start().await;
+        let url = format!("{}/endless.xml", mock_server.uri());
+        let id = system.add_task(&url, TaskOptions::default()).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(500)).await;
+
+        system.cancel_task(id).await.unwrap();
+        let tasks = system.wait_for_all_tasks_completed().await;
+        let task = tasks.iter().find(|task| task.id == id).unwrap();
+        assert!(task.is_failed());
+        assert_eq!(
+            task.get_current_stage_error_message().map(String::as_str),
+            Some("cancelled")
+        );
+        // 已结束的任务不能再次取消，未知任务返回 NotFound
+        assert!(system.cancel_task(id).await.is_err());
+        assert!(system.cancel_task(id + 1000).await.is_err());
+        system.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_task_deadline_exceeded() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        // 响应迟迟不返回，任务应在时限到达时失败并释放 worker
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(30)))
+            .mount(&mock_server)
+            .await;
+
+        let mut state = initialize().await.unwrap();
+        Arc::make_mut(&mut state.settings)
+            .crawler
+            .per_task_timeout_seconds = 1;
+        let mut system = TaskManagementSystem::new(Arc::new(state), 1, 5).await;
+        system.start().await;
+        let url = format!("{}/slow.xml", mock_server.uri());
+        let id = system.add_task(&url, TaskOptions::default()).await.unwrap();
+
+        let tasks = system
+            .wait_for_all_tasks_completed_with_timeout(Duration::from_secs(10))
+            .await
+            .unwrap();
+        let task = tasks.iter().find(|task| task.id == id).unwrap();
+        assert!(task.is_failed());
+        assert_eq!(task.retries, 0);
+        assert_eq!(
+            task.get_current_stage_error_message().map(String::as_str),
+            Some("task deadline exceeded")
+        );
+        system.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_pause_and_resume() {
+        let state = initialize().await.unwrap();
+        let mut system = TaskManagementSystem::new(Arc::new(state), 1, 5).await;
+        system.start().await;
+
+        system.pause();
+        assert!(system.is_paused());
+        // 暂停期间任务留在队列中，不会被 worker 领取
+        let id = system.add_task("", TaskOptions::default()).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        let task = system
+            .get_task_info()
+            .await
+            .into_iter()
+            .find(|task| task.id == id)
+            .unwrap();
+        assert_eq!(task.stages.last().unwrap().name, "distribution");
+
+        system.resume();
+        assert!(!system.is_paused());
+        let tasks = system.wait_for_all_tasks_completed().await;
+        system.shutdown().await;
+        let task = tasks.iter().find(|task| task.id == id).unwrap();
+        assert!(task.is_finished());
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_in_flight_url_rejected() {
+        let state = initialize().await.unwrap();
+        let mut system = TaskManagementSystem::new(Arc::new(state), 2, 5).await;
+
+        // Workers are not started, so the first task stays in flight
+        let url = "http://example.com/duplicate.xml";
+        assert!(system.add_task(url, TaskOptions::default()).await.is_ok());
+        match system.add_task(url, TaskOptions::default()).await {
+            Err(crate::infrastructure::AppError::Domain(e)) => {
+                assert_eq!(
+                    e.kind,
+                    crate::infrastructure::error::DomainErrorKind::Duplicate
+                )
+            }
+            other => panic!("expected duplicate error, got {:?}", other),
+        }
+        assert_eq!(system.get_task_info().await.len(), 1);
+    }
+
     #[test]
     fn test_system_shutdown() {
         let rt = Runtime::new().unwrap();
@@ -531,7 +1408,9 @@ mod tests {
 
             // Add some tasks
             for i in 0..5 {
-                system.add_task(&format!("http://example{}.com", i)).await;
+                system
+                    .add_task(&format!("http://example{}.com", i), TaskOptions::default())
+                    .await;
             }
 
             // Immediate shutdown
@@ -542,7 +1421,9 @@ mod tests {
             assert!(!tasks.is_empty());
 
             // Try to add a task after shutdown (should not panic)
-            system.add_task("http://example.com").await;
+            system
+                .add_task("http://example.com", TaskOptions::default())
+                .await;
         });
     }
 
@@ -557,7 +1438,7 @@ mod tests {
             // Add more tasks than the history size
             let test_url = "http://example.com";
             for _ in 0..5 {
-                system.add_task(test_url).await;
+                system.add_task(test_url, TaskOptions::default()).await;
             }
             tokio::time::sleep(Duration::from_millis(100)).await;
             system.shutdown().await;
@@ -573,7 +1454,9 @@ mod tests {
             system.start().await;
 
             // Add a task and track its progress
-            system.add_task("http://example.com").await;
+            system
+                .add_task("http://example.com", TaskOptions::default())
+                .await;
 
             // Initial state check
             let initial_info = system.get_task_info().await;
@@ -1,14 +1,51 @@
-use std::sync::Arc;
+use std::collections::HashSet;
+use std::sync::atomic::Ordering;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use tokio::sync::broadcast;
 use tokio_util::{sync::CancellationToken, task::TaskTracker};
 
 use super::{
+    priority_queue::PriorityQueue,
     task::Task,
     task_management_system::{ShutdownCoordinator, TaskWorkerMaps},
     timer_queue::TimerQueue,
     worker::Worker,
 };
+use crate::infrastructure::config::CrawlerConfig;
+
+// 检查队列积压、决定是否启动新 worker 的间隔
+const SCALE_UP_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Worker pool bounds, from `CrawlerConfig`
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct WorkerScaling {
+    pub initial_workers: usize,
+    pub min_workers: usize,
+    pub max_workers: usize,
+    pub idle_timeout: Option<Duration>,
+}
+
+impl WorkerScaling {
+    /// `worker_count` workers are started, and is the upper bound unless
+    /// `CrawlerConfig.max_workers` is set
+    pub fn from_config(config: &CrawlerConfig, worker_count: usize) -> Self {
+        let max_workers = match config.max_workers {
+            0 => worker_count,
+            max_workers => max_workers,
+        }
+        .max(config.min_workers);
+        Self {
+            initial_workers: worker_count.clamp(config.min_workers, max_workers),
+            min_workers: config.min_workers,
+            max_workers,
+            idle_timeout: (config.worker_idle_timeout_seconds > 0)
+                .then(|| Duration::from_secs(config.worker_idle_timeout_seconds)),
+        }
+    }
+}
+
 /// Internal ThreadManager structure
 pub(crate) struct ThreadManager {
     pub task_tx: broadcast::Sender<Task>,
@@ -17,12 +54,16 @@ pub(crate) struct ThreadManager {
     pub cancellation_token: CancellationToken,
     pub shutdown_coordinator: Arc<ShutdownCoordinator>,
     pub timer_queue: Arc<TimerQueue>,
+    pub priority_queue: Arc<PriorityQueue>,
+    scaling: WorkerScaling,
+    // 正在运行（包括正在退出）的 worker，退出完成前不会以同一 id 重新启动
+    running: Arc<Mutex<HashSet<usize>>>,
 }
 
 impl ThreadManager {
     pub async fn new(
         task_tx: broadcast::Sender<Task>,
-        worker_count: usize,
+        scaling: WorkerScaling,
         max_history_size: usize,
         task_tracker: Arc<TaskTracker>,
         cancellation_token: CancellationToken,
@@ -30,14 +71,23 @@ impl ThreadManager {
         task_worker_maps: Arc<TaskWorkerMaps>,
     ) -> Self {
         let mut workers = Vec::new();
-        for i in 0..worker_count {
+        for i in 0..scaling.max_workers {
             task_worker_maps.insert_worker(i).await;
         }
-        for i in 0..worker_count {
-            workers.push(Worker::new(i, max_history_size, task_worker_maps.clone()));
+        for i in 0..scaling.max_workers {
+            workers.push(
+                Worker::new(i, max_history_size, task_worker_maps.clone())
+                    .with_idle_timeout(scaling.idle_timeout, scaling.min_workers),
+            );
         }
+        // 每个启动的 worker 提供一个槽位
+        let priority_queue = Arc::new(
+            PriorityQueue::new(task_tx.clone(), 0, cancellation_token.clone())
+                .with_task_worker_maps(task_worker_maps),
+        );
         let timer_queue = Arc::new(TimerQueue::new(
             task_tx.clone(),
+            priority_queue.clone(),
             cancellation_token.clone(),
             shutdown_coordinator.clone(),
         ));
@@ -48,6 +98,9 @@ impl ThreadManager {
             cancellation_token,
             shutdown_coordinator,
             timer_queue,
+            priority_queue,
+            scaling,
+            running: Arc::new(Mutex::new(HashSet::new())),
         }
     }
 
@@ -56,44 +109,149 @@ impl ThreadManager {
         tracing::info!("⏰ Starting timer queue worker");
         self.task_tracker
             .spawn(async move { timer_queue.start_worker().await });
+        let priority_queue = self.priority_queue.clone();
+        tracing::info!("📤 Starting priority queue dispatcher");
+        self.task_tracker
+            .spawn(async move { priority_queue.start_dispatcher().await });
         tracing::info!(
-            "🚀 ThreadManager: Starting workers. Total workers: {}",
-            self.workers.len()
+            "🚀 ThreadManager: Starting workers. Total workers: {} (min {}, max {})",
+            self.scaling.initial_workers,
+            self.scaling.min_workers,
+            self.scaling.max_workers
         );
 
-        for worker in self.workers.iter_mut() {
-            let worker_cancellation_token = self.cancellation_token.clone();
-            let timer_queue = self.timer_queue.clone();
-            let worker_task_rx = self.task_tx.subscribe();
-            let shutdown_coordinator = self.shutdown_coordinator.clone();
+        let launcher = self.launcher();
+        for worker in &self.workers[..self.scaling.initial_workers] {
+            launcher.launch(worker);
+        }
+        // 只有空闲的 worker 会退出，未配置空闲超时时无需补充
+        if self.scaling.idle_timeout.is_some() {
+            let workers = self.workers.clone();
+            self.task_tracker
+                .spawn(async move { launcher.scale_up(workers).await });
+        }
+        tracing::info!("🎉 ThreadManager start completed");
+    }
 
-            // Safely clone the worker
-            let mut worker_clone = worker.clone();
+    fn launcher(&self) -> WorkerLauncher {
+        WorkerLauncher {
+            task_tx: self.task_tx.clone(),
+            task_tracker: self.task_tracker.clone(),
+            cancellation_token: self.cancellation_token.clone(),
+            shutdown_coordinator: self.shutdown_coordinator.clone(),
+            timer_queue: self.timer_queue.clone(),
+            priority_queue: self.priority_queue.clone(),
+            running: self.running.clone(),
+        }
+    }
+}
 
-            self.task_tracker.spawn(async move {
-                crate::metrics::ACTIVE_WORKERS.inc();
-                tracing::info!(
-                    "🚀 Worker {} started, active workers: {}",
-                    worker_clone.id,
-                    crate::metrics::ACTIVE_WORKERS.get()
-                );
-                let result = worker_clone
-                    .start(
-                        worker_task_rx,
-                        worker_cancellation_token,
-                        timer_queue,
-                        shutdown_coordinator,
-                    )
-                    .await;
-                crate::metrics::ACTIVE_WORKERS.dec();
+// 启动 worker 所需的共享状态，可在扩容任务中使用
+#[derive(Clone)]
+struct WorkerLauncher {
+    task_tx: broadcast::Sender<Task>,
+    task_tracker: Arc<TaskTracker>,
+    cancellation_token: CancellationToken,
+    shutdown_coordinator: Arc<ShutdownCoordinator>,
+    timer_queue: Arc<TimerQueue>,
+    priority_queue: Arc<PriorityQueue>,
+    running: Arc<Mutex<HashSet<usize>>>,
+}
+
+impl WorkerLauncher {
+    fn launch(&self, worker: &Worker) {
+        let worker_cancellation_token = self.cancellation_token.clone();
+        let timer_queue = self.timer_queue.clone();
+        let priority_queue = self.priority_queue.clone();
+        // 先订阅再登记，登记后分发给该 worker 的任务都能收到
+        let worker_task_rx = self.task_tx.subscribe();
+        let shutdown_coordinator = self.shutdown_coordinator.clone();
+        let running = self.running.clone();
+
+        running.lock().unwrap().insert(worker.id);
+        shutdown_coordinator
+            .worker_count
+            .fetch_add(1, Ordering::SeqCst);
+        self.priority_queue.add_worker(worker.id);
+
+        // Safely clone the worker
+        let mut worker_clone = worker.clone();
+
+        self.task_tracker.spawn(async move {
+            crate::metrics::ACTIVE_WORKERS.inc();
+            tracing::info!(
+                "🚀 Worker {} started, active workers: {}",
+                worker_clone.id,
+                crate::metrics::ACTIVE_WORKERS.get()
+            );
+            let result = worker_clone
+                .start(
+                    worker_task_rx,
+                    worker_cancellation_token,
+                    timer_queue,
+                    priority_queue,
+                    shutdown_coordinator,
+                )
+                .await;
+            running.lock().unwrap().remove(&worker_clone.id);
+            crate::metrics::ACTIVE_WORKERS.dec();
+            tracing::info!(
+                "🛑 Worker {} stopped, active workers: {}",
+                worker_clone.id,
+                crate::metrics::ACTIVE_WORKERS.get()
+            );
+            result
+        });
+    }
+
+    // 队列中有任务等待且没有空闲槽位时，重新启动一个已退出的 worker
+    async fn scale_up(&self, workers: Vec<Worker>) {
+        let mut interval = tokio::time::interval(SCALE_UP_INTERVAL);
+        loop {
+            tokio::select! {
+                _ = self.cancellation_token.cancelled() => break,
+                _ = interval.tick() => {}
+            }
+            if self.priority_queue.is_empty() || self.priority_queue.available_slots() > 0 {
+                continue;
+            }
+            let stopped = {
+                let running = self.running.lock().unwrap();
+                workers
+                    .iter()
+                    .find(|worker| !running.contains(&worker.id))
+                    .cloned()
+            };
+            if let Some(worker) = stopped {
                 tracing::info!(
-                    "🛑 Worker {} stopped, active workers: {}",
-                    worker_clone.id,
-                    crate::metrics::ACTIVE_WORKERS.get()
+                    "📈 ThreadManager: {} tasks waiting, starting worker {}",
+                    self.priority_queue.len(),
+                    worker.id
                 );
-                result
-            });
+                self.launch(&worker);
+            }
         }
-        tracing::info!("🎉 ThreadManager start completed");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_worker_scaling_from_config() {
+        let scaling = WorkerScaling::from_config(&CrawlerConfig::default(), 4);
+        assert_eq!((scaling.initial_workers, scaling.max_workers), (4, 4));
+        assert_eq!(scaling.idle_timeout, None);
+
+        let config = CrawlerConfig {
+            min_workers: 2,
+            max_workers: 8,
+            worker_idle_timeout_seconds: 30,
+            ..CrawlerConfig::default()
+        };
+        let scaling = WorkerScaling::from_config(&config, 1);
+        assert_eq!((scaling.initial_workers, scaling.max_workers), (2, 8));
+        assert_eq!(scaling.idle_timeout, Some(Duration::from_secs(30)));
     }
 }
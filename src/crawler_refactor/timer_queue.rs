@@ -1,3 +1,4 @@
+use crate::crawler_refactor::priority_queue::PriorityQueue;
 use crate::crawler_refactor::task::Task;
 use crate::crawler_refactor::task_management_system::ShutdownCoordinator;
 use std::collections::BinaryHeap;
@@ -11,18 +12,21 @@ use tokio_util::sync::CancellationToken;
 pub struct TimerQueue {
     timers: Arc<Mutex<BinaryHeap<Task>>>,
     worker_task_tx: broadcast::Sender<Task>,
+    priority_queue: Arc<PriorityQueue>,
     cancellation_token: CancellationToken,
     shutdown_coordinator: Arc<ShutdownCoordinator>,
 }
 impl TimerQueue {
     pub fn new(
         worker_task_tx: broadcast::Sender<Task>,
+        priority_queue: Arc<PriorityQueue>,
         cancellation_token: CancellationToken,
         shutdown_coordinator: Arc<ShutdownCoordinator>,
     ) -> Self {
         Self {
             timers: Arc::new(Mutex::new(BinaryHeap::new())),
             worker_task_tx,
+            priority_queue,
             cancellation_token,
             shutdown_coordinator,
         }
@@ -99,11 +103,9 @@ impl TimerQueue {
 
                 match next_task {
                     Some(task) => {
-                        if let Err(e) = self.worker_task_tx.send(task) {
-                            tracing::error!("❌ TimerQueue: Failed to send retry task: {}", e);
-                        } else {
-                            tracing::debug!("✅ TimerQueue: Retry task sent successfully");
-                        }
+                        // 重试任务同样经过优先级队列，与新任务一起按优先级分发
+                        self.priority_queue.push(task);
+                        tracing::debug!("✅ TimerQueue: Retry task requeued successfully");
                     }
                     None => {
                         // Sleep before checking again
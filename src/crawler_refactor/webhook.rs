@@ -0,0 +1,216 @@
+use crate::crawler_refactor::task::Task;
+use crate::infrastructure::config::CrawlerConfig;
+use reqwest::Client;
+use serde::Serialize;
+use std::time::Duration;
+
+/// Payload POSTed to the webhook when a task reaches a terminal stage
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct TaskWebhookPayload {
+    pub task_id: u64,
+    pub feed_url: String,
+    pub status: String,
+    pub inserted_episodes: u64,
+    pub error: Option<String>,
+}
+
+impl TaskWebhookPayload {
+    pub fn from_task(task: &Task) -> Self {
+        let status = if task.is_failed() {
+            "failed"
+        } else {
+            "completed"
+        };
+        // inserting 阶段完成时在结果数据中记录插入的剧集数量
+        let inserted_episodes = task
+            .get_stage_result_data_by_name("inserting")
+            .and_then(|data| data.get("inserted_episodes"))
+            .and_then(|count| count.as_u64())
+            .unwrap_or(0);
+        let error = task
+            .get_current_stage_error_message()
+            .or(task.error_message.as_ref())
+            .cloned();
+
+        Self {
+            task_id: task.id,
+            feed_url: task.get_feed_url().to_string(),
+            status: status.to_string(),
+            inserted_episodes,
+            error,
+        }
+    }
+}
+
+/// Fire-and-forget notifier for finished tasks
+#[derive(Clone, Debug)]
+pub struct WebhookNotifier {
+    client: Client,
+    url: String,
+    max_attempts: u32,
+    retry_delay: Duration,
+}
+
+impl WebhookNotifier {
+    /// Creates a notifier from the crawler config, `None` when no webhook URL is set
+    pub fn from_config(config: &CrawlerConfig) -> Option<Self> {
+        if config.webhook_url.trim().is_empty() {
+            return None;
+        }
+        let builder =
+            Client::builder().timeout(Duration::from_secs(config.request_timeout_seconds));
+        let builder = match config.proxy().expect("Invalid crawler proxy URL") {
+            Some(proxy) => builder.proxy(proxy),
+            None => builder.no_proxy(),
+        };
+        let client = builder
+            .build()
+            .expect("Failed to create webhook HTTP client");
+        Some(Self {
+            client,
+            url: config.webhook_url.clone(),
+            max_attempts: 3,
+            retry_delay: Duration::from_millis(500),
+        })
+    }
+
+    /// Sends the task outcome in the background without blocking the caller
+    pub fn notify(&self, task: &Task) {
+        let notifier = self.clone();
+        let payload = TaskWebhookPayload::from_task(task);
+        tokio::spawn(async move {
+            notifier.send(&payload).await;
+        });
+    }
+
+    // 发送 webhook，失败时短暂等待后重试，返回是否最终成功
+    async fn send(&self, payload: &TaskWebhookPayload) -> bool {
+        for attempt in 1..=self.max_attempts {
+            match self.client.post(&self.url).json(payload).send().await {
+                Ok(response) if response.status().is_success() => {
+                    tracing::debug!(
+                        "📨 Webhook delivered for task {} (attempt {})",
+                        payload.task_id,
+                        attempt
+                    );
+                    return true;
+                }
+                Ok(response) => tracing::warn!(
+                    "⚠️ Webhook for task {} returned status {} (attempt {}/{})",
+                    payload.task_id,
+                    response.status(),
+                    attempt,
+                    self.max_attempts
+                ),
+                Err(e) => tracing::warn!(
+                    "⚠️ Webhook for task {} failed: {} (attempt {}/{})",
+                    payload.task_id,
+                    e,
+                    attempt,
+                    self.max_attempts
+                ),
+            }
+            if attempt < self.max_attempts {
+                tokio::time::sleep(self.retry_delay).await;
+            }
+        }
+        tracing::error!(
+            "❌ Giving up on webhook for task {} after {} attempts",
+            payload.task_id,
+            self.max_attempts
+        );
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use wiremock::matchers::{body_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn notifier_for(url: String) -> WebhookNotifier {
+        let config = CrawlerConfig {
+            webhook_url: url,
+            ..CrawlerConfig::default()
+        };
+        let mut notifier = WebhookNotifier::from_config(&config).unwrap();
+        notifier.retry_delay = Duration::from_millis(10);
+        notifier
+    }
+
+    #[test]
+    fn test_disabled_when_url_empty() {
+        assert!(WebhookNotifier::from_config(&CrawlerConfig::default()).is_none());
+    }
+
+    #[test]
+    fn test_payload_from_completed_and_failed_tasks() {
+        let mut task = Task::new(7, "http://example.com/feed".to_string(), 0);
+        task.final_url = Some("https://example.com/feed".to_string());
+        task.add_stage("inserting");
+        task.complete_stage(json!({"status": "success", "inserted_episodes": 12}));
+        assert_eq!(
+            TaskWebhookPayload::from_task(&task),
+            TaskWebhookPayload {
+                task_id: 7,
+                feed_url: "https://example.com/feed".to_string(),
+                status: "completed".to_string(),
+                inserted_episodes: 12,
+                error: None,
+            }
+        );
+
+        let mut task = Task::new(8, "http://example.com/broken".to_string(), 0);
+        task.add_stage("fetching");
+        task.fail_stage("connection refused".to_string());
+        let payload = TaskWebhookPayload::from_task(&task);
+        assert_eq!(payload.status, "failed");
+        assert_eq!(payload.inserted_episodes, 0);
+        assert_eq!(payload.error.as_deref(), Some("connection refused"));
+    }
+
+    #[tokio::test]
+    async fn test_send_retries_until_success() {
+        let mock_server = MockServer::start().await;
+        let payload = TaskWebhookPayload {
+            task_id: 1,
+            feed_url: "http://example.com/feed".to_string(),
+            status: "completed".to_string(),
+            inserted_episodes: 3,
+            error: None,
+        };
+
+        Mock::given(method("POST"))
+            .and(path("/hook"))
+            .respond_with(ResponseTemplate::new(500))
+            .up_to_n_times(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/hook"))
+            .and(body_json(&payload))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let notifier = notifier_for(format!("{}/hook", mock_server.uri()));
+        assert!(notifier.send(&payload).await);
+    }
+
+    #[tokio::test]
+    async fn test_send_gives_up_after_max_attempts() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(503))
+            .expect(3)
+            .mount(&mock_server)
+            .await;
+
+        let notifier = notifier_for(format!("{}/hook", mock_server.uri()));
+        let payload = TaskWebhookPayload::from_task(&Task::new(1, "u".to_string(), 0));
+        assert!(!notifier.send(&payload).await);
+    }
+}
@@ -1,14 +1,16 @@
 use std::{
-    sync::Arc,
+    sync::{atomic::Ordering, Arc},
     time::{Duration, Instant},
 };
 
 use tokio::sync::broadcast;
 use tokio_util::sync::CancellationToken;
-use tracing::{debug, error, info, warn};
+use tracing::{debug, error, info, warn, Instrument};
 
 use super::{
-    task::Task,
+    parser_registry::unrecognized_format_error,
+    priority_queue::PriorityQueue,
+    task::{StageStatus, Task},
     task_management_system::{ShutdownCoordinator, TaskWorkerMaps},
     timer_queue::TimerQueue,
 };
@@ -17,6 +19,13 @@ use crate::infrastructure::error::{
     AppError, DomainError, DomainErrorKind, NetworkError, NetworkErrorKind,
 };
 
+// 被取消任务的错误信息
+const CANCELLED: &str = "cancelled";
+// 超过单任务时限的错误信息
+const DEADLINE_EXCEEDED: &str = "task deadline exceeded";
+// 因广播通道滞后未收到的任务的错误信息
+const LOST_TO_LAG: &str = "lost to broadcast lag";
+
 /// Worker状态
 #[derive(Debug, Clone, PartialEq)]
 enum WorkerState {
@@ -34,6 +43,10 @@ pub struct Worker {
     state: WorkerState,
     task_worker_maps: Arc<TaskWorkerMaps>,
     metrics: WorkerMetrics,
+    next_dispatch_seq: u64,
+    // 空闲超过该时长的 worker 会退出，None 表示一直运行
+    idle_timeout: Option<Duration>,
+    min_workers: usize,
 }
 
 #[derive(Debug, Clone)]
@@ -57,26 +70,74 @@ impl Worker {
                 tasks_retried: 0,
                 avg_process_time: Duration::ZERO,
             },
+            next_dispatch_seq: 1,
+            idle_timeout: None,
+            min_workers: 0,
         }
     }
 
+    /// 空闲超过 `idle_timeout` 时退出，但至少保留 `min_workers` 个 worker
+    pub fn with_idle_timeout(mut self, idle_timeout: Option<Duration>, min_workers: usize) -> Self {
+        self.idle_timeout = idle_timeout;
+        self.min_workers = min_workers;
+        self
+    }
+
     pub async fn start(
         &mut self,
         mut worker_task_rx: broadcast::Receiver<Task>,
         worker_cancellation_token: CancellationToken,
         timer_queue: Arc<TimerQueue>,
+        priority_queue: Arc<PriorityQueue>,
         shutdown_coordinator: Arc<ShutdownCoordinator>,
     ) {
         info!(worker_id = self.id, "Starting worker");
         self.state = WorkerState::Processing;
 
         let mut in_progress_tasks = Vec::new();
+        let mut paused_rx = self.task_worker_maps.subscribe_paused();
+        let idle_timeout = self.idle_timeout.unwrap_or_default();
+        let mut idle_deadline = tokio::time::Instant::now() + idle_timeout;
 
         loop {
+            // 暂停期间不领取新任务，任务留在广播通道中，恢复后继续处理
+            if *paused_rx.borrow_and_update() {
+                tokio::select! {
+                    _ = paused_rx.changed() => {
+                        idle_deadline = tokio::time::Instant::now() + idle_timeout;
+                        continue;
+                    }
+                    _ = worker_cancellation_token.cancelled() => {
+                        self.handle_shutdown(&shutdown_coordinator, &mut in_progress_tasks).await;
+                        break;
+                    }
+                }
+            }
+
             tokio::select! {
+                _ = paused_rx.changed() => continue,
                 result = worker_task_rx.recv() => {
                     match result {
-                        Ok(mut task) => self.handle_task(&mut task, &timer_queue, &mut in_progress_tasks).await,
+                        Ok(mut task) => {
+                            // 归还丢失任务占用的槽位，否则分发器的容量会永久减少
+                            for _ in 0..self.fail_lost_tasks(&task, &priority_queue).await {
+                                priority_queue.release_slot();
+                            }
+                            if self.handle_task(&mut task, &timer_queue, &mut in_progress_tasks).await {
+                                priority_queue.release_slot();
+                                idle_deadline = tokio::time::Instant::now() + idle_timeout;
+                            }
+                        }
+                        Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                            // 广播通道中每个 worker 都会收到所有任务，被跳过的消息里
+                            // 发给本 worker 的任务会在收到下一个任务时通过 dispatch_seq 的缺口统计
+                            warn!(
+                                worker_id = self.id,
+                                skipped, "Task channel lagged, skipped {} messages", skipped
+                            );
+                            crate::metrics::WORKER_LAGGED_MESSAGES.inc_by(skipped);
+                            continue;
+                        }
                         Err(e) => {
                             warn!(worker_id = self.id, "Task channel error: {}", e);
                             continue;
@@ -87,19 +148,82 @@ impl Worker {
                     self.handle_shutdown(&shutdown_coordinator, &mut in_progress_tasks).await;
                     break;
                 }
+                _ = tokio::time::sleep_until(idle_deadline), if self.idle_timeout.is_some() => {
+                    if priority_queue.try_retire_worker(self.id, self.min_workers) {
+                        self.retire(&mut worker_task_rx, &priority_queue, &shutdown_coordinator);
+                        break;
+                    }
+                    idle_deadline = tokio::time::Instant::now() + idle_timeout;
+                }
             }
         }
     }
 
+    // 空闲退出：把已发给本 worker 但尚未处理的任务交还给优先级队列
+    fn retire(
+        &mut self,
+        worker_task_rx: &mut broadcast::Receiver<Task>,
+        priority_queue: &PriorityQueue,
+        shutdown_coordinator: &ShutdownCoordinator,
+    ) {
+        let mut requeued = 0;
+        loop {
+            match worker_task_rx.try_recv() {
+                Ok(task) if task.target_thread_id == self.id => {
+                    priority_queue.requeue(task);
+                    requeued += 1;
+                }
+                Ok(_) | Err(broadcast::error::TryRecvError::Lagged(_)) => continue,
+                Err(_) => break,
+            }
+        }
+        self.state = WorkerState::Shutdown;
+        shutdown_coordinator
+            .worker_count
+            .fetch_sub(1, Ordering::SeqCst);
+        info!(
+            worker_id = self.id,
+            requeued, "Worker idle for {:?}, stopping", self.idle_timeout
+        );
+    }
+
+    // 根据 dispatch_seq 的缺口找出发给本 worker 但未收到的任务，将其标记为失败，返回丢失的任务数
+    async fn fail_lost_tasks(&mut self, task: &Task, priority_queue: &PriorityQueue) -> u64 {
+        if task.target_thread_id != self.id || task.dispatch_seq == 0 {
+            return 0;
+        }
+        priority_queue.take_dispatched(self.id, task.dispatch_seq);
+        let first_lost_seq = self.next_dispatch_seq;
+        let lost = lost_dispatches(&mut self.next_dispatch_seq, task.dispatch_seq);
+        if lost > 0 {
+            error!(
+                worker_id = self.id,
+                lost, "Lost {} tasks to broadcast lag, they will not be retried", lost
+            );
+            self.metrics.tasks_failed += lost;
+            crate::metrics::LOST_TASKS.inc_by(lost);
+            crate::metrics::FAILED_TASKS.inc_by(lost);
+            for seq in first_lost_seq..task.dispatch_seq {
+                if let Some(task_id) = priority_queue.take_dispatched(self.id, seq) {
+                    self.task_worker_maps
+                        .fail_undelivered_task(task_id, LOST_TO_LAG)
+                        .await;
+                }
+            }
+        }
+        lost
+    }
+
+    // 返回 true 表示任务由本 worker 处理，调用方需归还调度槽位
     async fn handle_task(
         &mut self,
         task: &mut Task,
         timer_queue: &Arc<TimerQueue>,
         in_progress_tasks: &mut Vec<u64>,
-    ) {
+    ) -> bool {
         if self.state != WorkerState::Processing || task.target_thread_id != self.id {
             // debug!(worker_id = self.id, "Skipping non-target task");
-            return;
+            return false;
         }
 
         info!(worker_id = self.id, task_id = task.id, "Processing task");
@@ -107,7 +231,8 @@ impl Worker {
         in_progress_tasks.push(task.id);
 
         let start_time = Instant::now();
-        let result = self.process_task(task, timer_queue).await;
+        let span = task.span.clone();
+        let result = self.process_task(task, timer_queue).instrument(span).await;
         let process_time = start_time.elapsed();
 
         self.update_metrics(process_time, result.is_err());
@@ -118,25 +243,69 @@ impl Worker {
         } else {
             info!(worker_id = self.id, task_id = task.id, "Task completed");
         }
+        true
     }
 
+    // 配置了单任务时限时，超时的任务直接失败，不再重试
+    // 时限覆盖抓取、解析和交给插入器为止；批量写库由多个任务共享，不计入时限
     async fn process_task(
         &mut self,
         task: &mut Task,
         timer_queue: &Arc<TimerQueue>,
     ) -> Result<(), AppError> {
+        let Some(deadline) = self.task_worker_maps.get_task_deadline() else {
+            return self.run_task(task, timer_queue).await;
+        };
+        match tokio::time::timeout(deadline, self.run_task(task, timer_queue)).await {
+            Ok(result) => result,
+            Err(_) => self.fail_deadline_exceeded(task, deadline).await,
+        }
+    }
+
+    async fn run_task(
+        &mut self,
+        task: &mut Task,
+        timer_queue: &Arc<TimerQueue>,
+    ) -> Result<(), AppError> {
+        // 排队或等待重试期间被取消的任务不再抓取
+        if task.is_cancelled() {
+            return self.fail_cancelled(task).await;
+        }
+
         let fetch_result = self.fetch_task(task).await;
         if let Err(e) = fetch_result {
+            if task.is_cancelled() {
+                return self.fail_cancelled(task).await;
+            }
             return self.handle_fetch_error(task, timer_queue, e).await;
         }
 
-        self.parse_task(task).await?;
+        if self.task_worker_maps.is_content_unchanged(task).await {
+            self.complete_unchanged(task).await;
+            self.update_history(&task.payload).await;
+            return Ok(());
+        }
+
+        if let Err(e) = self.parse_task(task).await {
+            self.task_worker_maps
+                .update_task(task.id, task.clone())
+                .await;
+            self.task_worker_maps.notify_task_finished(task);
+            return Err(e);
+        }
+
+        // feed 声明了新地址时先迁移已保存的播客，再写入本次结果
+        if let Some(new_task_id) = self.task_worker_maps.follow_feed_move(task).await {
+            info!(
+                worker_id = self.id,
+                task_id = task.id,
+                new_task_id,
+                "Feed moved, enqueued crawl of the new URL"
+            );
+        }
 
         // Insert parsed data
         self.insert_task(task).await?;
-        self.task_worker_maps
-            .update_task(task.id, task.clone())
-            .await;
         self.update_history(&task.payload).await;
 
         Ok(())
@@ -146,9 +315,20 @@ impl Worker {
         &mut self,
         task: &mut Task,
         timer_queue: &Arc<TimerQueue>,
-        error: String,
+        error: AppError,
     ) -> Result<(), AppError> {
-        if task.retries < task.max_retries {
+        // 无法解析的主机和 robots.txt 禁止的地址重试也不会成功，直接失败
+        let not_retried = match &error {
+            AppError::Network(e) if e.kind == NetworkErrorKind::DnsResolution => {
+                Some("Host unresolvable, not retried")
+            }
+            AppError::Network(e) if e.kind == NetworkErrorKind::RobotsDisallowed => {
+                Some("Disallowed by robots.txt, not retried")
+            }
+            _ => None,
+        };
+        let error = error.to_string();
+        if task.retries < task.max_retries && not_retried.is_none() {
             self.metrics.tasks_retried += 1;
             task.retries += 1;
             task.backoff_timer = Some(Instant::now() + Duration::from_secs(1));
@@ -173,6 +353,8 @@ impl Worker {
         self.task_worker_maps
             .update_task(task.id, task.clone())
             .await;
+        self.task_worker_maps.notify_task_finished(task);
+        self.task_worker_maps.record_failed_feed(task, &error).await;
 
         Err(AppError::Network(NetworkError::new(
             NetworkErrorKind::Connection,
@@ -180,11 +362,62 @@ impl Worker {
             None,
             Some(Box::new(std::io::Error::new(
                 std::io::ErrorKind::Other,
-                format!("Max retries ({}) reached", task.max_retries),
+                match not_retried {
+                    Some(reason) => reason.to_string(),
+                    None => format!("Max retries ({}) reached", task.max_retries),
+                },
             ))),
         )))
     }
 
+    // 被取消的任务以 "cancelled" 失败，不再重试
+    async fn fail_cancelled(&mut self, task: &mut Task) -> Result<(), AppError> {
+        info!(worker_id = self.id, task_id = task.id, "Task cancelled");
+        // 尚未开始抓取时补上 fetching 阶段，失败记录在该阶段上
+        if task.get_task_status() != StageStatus::InProgress {
+            task.add_stage("fetching");
+        }
+        task.error_message = Some(CANCELLED.to_string());
+        task.fail_stage(CANCELLED.to_string());
+        self.task_worker_maps
+            .update_task(task.id, task.clone())
+            .await;
+        self.task_worker_maps.notify_task_finished(task);
+        Err(DomainError::new(DomainErrorKind::Other, "Task cancelled", None, None).into())
+    }
+
+    async fn fail_deadline_exceeded(
+        &mut self,
+        task: &mut Task,
+        deadline: Duration,
+    ) -> Result<(), AppError> {
+        warn!(
+            worker_id = self.id,
+            task_id = task.id,
+            "Task exceeded its deadline of {:?}",
+            deadline
+        );
+        if task.get_task_status() != StageStatus::InProgress {
+            task.add_stage("fetching");
+        }
+        task.error_message = Some(DEADLINE_EXCEEDED.to_string());
+        task.fail_stage(DEADLINE_EXCEEDED.to_string());
+        self.task_worker_maps
+            .update_task(task.id, task.clone())
+            .await;
+        self.task_worker_maps.notify_task_finished(task);
+        self.task_worker_maps
+            .record_failed_feed(task, DEADLINE_EXCEEDED)
+            .await;
+        Err(DomainError::new(
+            DomainErrorKind::Other,
+            format!("Task deadline of {:?} exceeded", deadline),
+            None,
+            None,
+        )
+        .into())
+    }
+
     async fn handle_shutdown(
         &mut self,
         shutdown_coordinator: &Arc<ShutdownCoordinator>,
@@ -223,23 +456,66 @@ impl Worker {
         self.metrics.avg_process_time = total_time / (self.metrics.tasks_processed + 1) as u32;
     }
 
-    async fn fetch_task(&mut self, task: &mut Task) -> Result<(), String> {
+    async fn fetch_task(&mut self, task: &mut Task) -> Result<(), AppError> {
         let fetcher = self.task_worker_maps.get_fetcher();
-        fetcher
-            .fetch_with_task(task)
-            .await
-            .map_err(|e| e.to_string())
+        fetcher.fetch_with_task(task).await
     }
 
     async fn parse_task(&mut self, task: &mut Task) -> Result<(), AppError> {
-        let parser = self.task_worker_maps.get_parser();
-        parser.parse_with_task(task).await?;
+        // 不是 feed 的内容（如 HTML 页面）不交给解析器，直接说明原因
+        let parser = self
+            .task_worker_maps
+            .get_parser(task)
+            .ok_or_else(|| unrecognized_format_error(task.get_feed_url(), &task.content))?;
+        let (podcast, mut episodes) = parser.parse_with_task(task).await?;
+        if let Some(resolver) = self.task_worker_maps.get_enclosure_resolver() {
+            if resolver.resolve(&mut episodes).await > 0 {
+                // 保留解析器记录的警告
+                let warnings = task
+                    .get_stage_result_data_by_name("parsing")
+                    .and_then(|data| data.get("warnings"))
+                    .cloned()
+                    .unwrap_or_else(|| serde_json::json!([]));
+                task.set_stage_result_data(
+                    "parsing",
+                    serde_json::json!({
+                        "podcast": podcast,
+                        "episodes": episodes,
+                        "warnings": warnings,
+                    }),
+                );
+            }
+        }
         Ok(())
     }
 
+    // 内容与上次爬取相同：跳过解析和写库，直接完成插入阶段
+    async fn complete_unchanged(&mut self, task: &mut Task) {
+        info!(
+            worker_id = self.id,
+            task_id = task.id,
+            "Feed content unchanged, skipping parse"
+        );
+        task.add_stage("inserting");
+        task.complete_stage(serde_json::json!({
+            "status": "unchanged",
+            "inserted_episodes": 0,
+        }));
+        crate::metrics::UNCHANGED_TASKS.inc();
+        self.task_worker_maps.touch_last_fetched(task).await;
+        self.task_worker_maps
+            .update_task(task.id, task.clone())
+            .await;
+        self.task_worker_maps.notify_task_finished(task);
+    }
+
     async fn insert_task(&mut self, task: &mut Task) -> Result<(), AppError> {
         let inserter = self.task_worker_maps.get_inserter();
         task.add_stage("inserting");
+        // Record the state before handing off, the inserter writes back the final one
+        self.task_worker_maps
+            .update_task(task.id, task.clone())
+            .await;
         if let Err(e) = inserter.insert(task.clone()).await {
             error!(
                 worker_id = self.id,
@@ -248,6 +524,10 @@ impl Worker {
                 e
             );
             task.fail_stage(e.to_string());
+            self.task_worker_maps
+                .update_task(task.id, task.clone())
+                .await;
+            self.task_worker_maps.notify_task_finished(task);
             return Err(DomainError::new(
                 DomainErrorKind::BatchProcessing,
                 "insert submit fail",
@@ -285,3 +565,28 @@ impl Worker {
         &self.metrics
     }
 }
+
+// 收到序号为 `seq` 的任务时，返回之前缺失的任务数并更新期望的下一个序号
+fn lost_dispatches(next_seq: &mut u64, seq: u64) -> u64 {
+    let lost = seq.saturating_sub(*next_seq);
+    *next_seq = (*next_seq).max(seq + 1);
+    lost
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lost_dispatches() {
+        let mut next_seq = 1;
+        assert_eq!(lost_dispatches(&mut next_seq, 1), 0);
+        assert_eq!(lost_dispatches(&mut next_seq, 2), 0);
+        // 3 和 4 在广播通道滞后时被丢弃
+        assert_eq!(lost_dispatches(&mut next_seq, 5), 2);
+        assert_eq!(next_seq, 6);
+        // 关闭时直接发送的旧任务不会产生缺口
+        assert_eq!(lost_dispatches(&mut next_seq, 3), 0);
+        assert_eq!(next_seq, 6);
+    }
+}
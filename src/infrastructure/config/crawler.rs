@@ -11,23 +11,106 @@
 //! - `CRAWLER_USER_AGENT`: User agent string for HTTP requests
 //! - `CRAWLER_MAX_TASKS`: Maximum number of concurrent crawling tasks
 //! - `CRAWLER_FETCH_INTERVAL`: Interval between fetches in seconds
+//! - `CRAWLER_REQUEST_TIMEOUT`: Total HTTP request timeout in seconds (optional)
+//! - `CRAWLER_CONNECT_TIMEOUT`: HTTP connect timeout in seconds (optional)
+//! - `CRAWLER_MAX_REDIRECTS`: Maximum number of HTTP redirects to follow (optional)
+//! - `CRAWLER_MAX_FEED_BYTES`: Maximum feed body size in bytes (optional)
+//! - `CRAWLER_WEBHOOK_URL`: URL notified when a task finishes, empty disables it (optional)
+//! - `CRAWLER_ACCEPT_LANGUAGE`: `Accept-Language` header sent with feed requests (optional)
+//! - `CRAWLER_DETECT_LANGUAGE`: Guess the language of feeds that do not declare one (optional)
+//! - `CRAWLER_DEFAULT_MAX_RETRIES`: Fetch retries for tasks without an override (optional)
+//! - `CRAWLER_PROXY_URL`: HTTP proxy for all outbound requests, empty disables it (optional)
+//! - `CRAWLER_SCHEDULER_ENABLED`: Periodically recrawl stale feeds (optional)
+//! - `CRAWLER_SCHEDULER_INTERVAL`: Seconds between stale-feed checks (optional)
+//! - `CRAWLER_SCHEDULER_MAX_IN_FLIGHT`: Unfinished tasks above which the scheduler waits (optional)
+//! - `CRAWLER_RESOLVE_ENCLOSURE_LENGTH`: HEAD enclosures to fill missing lengths (optional)
+//! - `CRAWLER_INSERT_BATCH_SIZE`: Initial number of tasks per insert batch (optional)
+//! - `CRAWLER_MAX_CONCURRENT_INSERTS`: Maximum number of insert batches running at once (optional)
+//! - `CRAWLER_INSERT_BATCH_TIMEOUT_MS`: Milliseconds a partial insert batch waits before flushing (optional)
+//! - `CRAWLER_GLOBAL_REQUESTS_PER_SECOND`: Maximum feed requests per second across all workers, 0 for unlimited (optional)
+//! - `CRAWLER_LENIENT_CONTENT_TYPE_HOSTS`: Comma-separated hosts whose feeds may be served as text/html (optional)
+//! - `CRAWLER_MIN_WORKERS`: Workers kept running when idle workers are stopped (optional)
+//! - `CRAWLER_MAX_WORKERS`: Upper bound when idle-stopped workers are started again, 0 for the crawler's worker count (optional)
+//! - `CRAWLER_WORKER_IDLE_TIMEOUT_SECONDS`: Seconds a worker may stay idle before it is stopped, 0 to keep workers running (optional)
+//! - `CRAWLER_POOL_MAX_IDLE_PER_HOST`: Idle connections kept per host for reuse, 0 to disable keep-alive (optional)
+//! - `CRAWLER_POOL_IDLE_TIMEOUT_SECONDS`: Seconds an idle pooled connection is kept open (optional)
+//! - `CRAWLER_DNS_FAILURE_TTL_SECONDS`: Seconds an unresolvable host fails without a new lookup, 0 to disable (optional)
+//! - `CRAWLER_CAPTURE_UNKNOWN_TAGS`: Store unhandled namespaced elements in the extra column (optional)
+//! - `CRAWLER_CIRCUIT_BREAKER_THRESHOLD`: Consecutive failures after which a host is skipped, 0 to disable (optional)
+//! - `CRAWLER_CIRCUIT_BREAKER_COOLDOWN_SECONDS`: Seconds an open circuit fails fast before a test request (optional)
+//! - `CRAWLER_PARSE_MEDIA_RSS`: Use Media RSS media:content/media:thumbnail when an item has no enclosure/image (optional)
+//! - `CRAWLER_RESPECT_ROBOTS_TXT`: Skip feeds disallowed by the host's robots.txt (optional)
+//! - `CRAWLER_ROBOTS_TXT_TTL_SECONDS`: How long a host's robots.txt is cached (optional)
+//! - `CRAWLER_MAX_TITLE_LENGTH`: Maximum title length in characters, longer text is truncated, 0 for no limit (optional)
+//! - `CRAWLER_MAX_DESCRIPTION_LENGTH`: Maximum description length in characters, longer text is truncated, 0 for no limit (optional)
+//! - `CRAWLER_MAX_SUMMARY_LENGTH`: Maximum summary length in characters, longer text is truncated, 0 for no limit (optional)
+//! - `CRAWLER_MAX_SUBTITLE_LENGTH`: Maximum subtitle length in characters, longer text is truncated, 0 for no limit (optional)
+//! - `CRAWLER_DISPATCH_STRATEGY`: How new tasks are assigned to workers: round_robin, least_loaded or similarity (optional)
+//! - `CRAWLER_PER_TASK_TIMEOUT_SECONDS`: Seconds a task may spend fetching, parsing and handing its result to the inserter before it fails, 0 for no deadline (optional)
+//! - `CRAWLER_STRIPPED_TRACKING_PARAMS`: Comma-separated query parameters removed from enclosure and link URLs, `name*` matches a prefix (optional)
+//! - `CRAWLER_ENCLOSURE_RESOLVE_CONCURRENCY`: Maximum concurrent enclosure length requests, shared by all feeds (optional)
+//! - `CRAWLER_SKIP_INVALID_ITEMS`: Drop items that fail validation instead of failing the whole feed (optional)
+//! - `CRAWLER_MAX_EPISODES`: Maximum episodes kept per feed, the first items in the feed win, 0 for no limit (optional)
+//! - `CRAWLER_REQUIRE_AUDIO_ENCLOSURE`: Reject enclosures that are not audio by type or file extension (optional)
 //!
 //! # Example
 //!
 //! ```rust
-//! use podcast_crawler::infrastructure::config::CrawlerConfig;
+//! use podcast_crawler::infrastructure::config::{CrawlerConfig, DispatchStrategy};
 //!
 //! let config = CrawlerConfig {
 //!     max_concurrent_tasks: 5,
 //!     fetch_interval_seconds: 3600,
 //!     user_agent: "PodcastCrawler/1.0".to_string(),
+//!     request_timeout_seconds: 30,
+//!     connect_timeout_seconds: 10,
+//!     max_redirects: 10,
+//!     max_feed_bytes: 50 * 1024 * 1024,
+//!     webhook_url: String::new(),
+//!     accept_language: String::new(),
+//!     detect_language: false,
+//!     default_max_retries: 3,
+//!     proxy_url: String::new(),
+//!     scheduler_enabled: false,
+//!     scheduler_interval_seconds: 300,
+//!     scheduler_max_in_flight: 100,
+//!     resolve_enclosure_length: false,
+//!     insert_batch_size: 3,
+//!     max_concurrent_inserts: 10,
+//!     insert_batch_timeout_ms: 5000,
+//!     global_requests_per_second: 0,
+//!     lenient_content_type_hosts: "".to_string(),
+//!     min_workers: 1,
+//!     max_workers: 0,
+//!     worker_idle_timeout_seconds: 0,
+//!     pool_max_idle_per_host: 8,
+//!     pool_idle_timeout_seconds: 90,
+//!     dns_failure_ttl_seconds: 300,
+//!     capture_unknown_tags: false,
+//!     circuit_breaker_threshold: 5,
+//!     circuit_breaker_cooldown_seconds: 60,
+//!     parse_media_rss: true,
+//!     respect_robots_txt: false,
+//!     robots_txt_ttl_seconds: 3600,
+//!     max_title_length: 0,
+//!     max_description_length: 0,
+//!     max_summary_length: 0,
+//!     max_subtitle_length: 0,
+//!     dispatch_strategy: DispatchStrategy::RoundRobin,
+//!     per_task_timeout_seconds: 0,
+//!     stripped_tracking_params: "".to_string(),
+//!     enclosure_resolve_concurrency: 8,
+//!     skip_invalid_items: false,
+//!     max_episodes: 0,
+//!     require_audio_enclosure: false,
 //! };
 //!
 //! assert!(config.validate().is_ok());
 //! ```
 
 use crate::infrastructure::config::AppResult;
-use crate::{config_set_env, config_set_string, config_validate};
+use crate::infrastructure::{AppError, InfrastructureError, InfrastructureErrorKind};
+use crate::{config_set_env, config_set_env_optional, config_set_string, config_validate};
 use serde::{Deserialize, Serialize};
 
 /// Crawler configuration
@@ -38,18 +121,141 @@ use serde::{Deserialize, Serialize};
 ///
 /// * `max_concurrent_tasks` - Maximum number of concurrent crawling tasks
 /// * `fetch_interval_seconds` - Interval between fetches in seconds
-/// * `user_agent` - User agent string for HTTP requests
+/// * `user_agent` - User agent string for feed and robots.txt requests; its first product token (lowercased) is the name matched in robots.txt
+/// * `request_timeout_seconds` - Total timeout for a single HTTP request in seconds
+/// * `connect_timeout_seconds` - Timeout for establishing an HTTP connection in seconds
+/// * `max_redirects` - Maximum number of HTTP redirects to follow per request
+/// * `max_feed_bytes` - Maximum size of a feed body in bytes
+/// * `webhook_url` - URL that receives a JSON POST when a task finishes (empty to disable)
+/// * `accept_language` - `Accept-Language` header value for feed requests (empty to omit)
+/// * `detect_language` - Detect the podcast language when the feed has no `<language>`
+/// * `default_max_retries` - Fetch retries per task unless `add_task` overrides it
+/// * `proxy_url` - Proxy that all outbound requests go through (empty for direct connections)
+/// * `scheduler_enabled` - Recrawl feeds not updated within `fetch_interval_seconds`
+/// * `scheduler_interval_seconds` - How often the scheduler looks for stale feeds
+/// * `scheduler_max_in_flight` - Scheduler only tops up the queue to this many unfinished tasks
+/// * `resolve_enclosure_length` - Fill a missing `enclosure_length` from the enclosure's `Content-Length`
+/// * `insert_batch_size` - Initial number of tasks per insert batch (grows and shrinks with load)
+/// * `max_concurrent_inserts` - Maximum number of insert batches written concurrently
+/// * `insert_batch_timeout_ms` - How long a partial insert batch waits for more tasks before flushing
+/// * `global_requests_per_second` - Maximum feed requests per second across all workers (0 = unlimited)
+/// * `lenient_content_type_hosts` - Comma-separated hosts (and their subdomains) whose responses skip the HTML Content-Type check
+/// * `min_workers` - Workers that are never stopped for being idle
+/// * `max_workers` - Upper bound for workers started when the queue grows (0 = the crawler's worker count)
+/// * `worker_idle_timeout_seconds` - How long a worker may go without a task before it is stopped (0 = never)
+/// * `pool_max_idle_per_host` - Idle connections kept open per host so later requests skip the TCP/TLS handshake (0 = no reuse)
+/// * `pool_idle_timeout_seconds` - How long an idle pooled connection is kept before it is closed
+/// * `dns_failure_ttl_seconds` - How long a host that could not be resolved is remembered, so new tasks for it fail without a network attempt (0 = no cache)
+/// * `capture_unknown_tags` - Keep namespaced elements the parser does not handle in the podcast/episode `extra` column
+/// * `circuit_breaker_threshold` - Consecutive failed requests to a host before its circuit opens and requests to it fail without a network attempt (0 = disabled)
+/// * `circuit_breaker_cooldown_seconds` - How long an open circuit fails fast before one request is let through to test whether the host recovered
+/// * `parse_media_rss` - Map Media RSS `<media:content>` to the enclosure of items without an `<enclosure>`, and `<media:thumbnail>` to the episode image
+/// * `respect_robots_txt` - Fetch each host's `/robots.txt` before its feeds and skip feed URLs it disallows for the crawler's User-Agent
+/// * `robots_txt_ttl_seconds` - How long a fetched robots.txt is cached before it is requested again
+/// * `max_title_length` - Longest podcast/episode title kept, in characters; longer text is truncated with "…" and a parse warning (0 = no limit)
+/// * `max_description_length` - Longest podcast/episode description kept, in characters; longer text is truncated with "…" and a parse warning (0 = no limit)
+/// * `max_summary_length` - Longest podcast/episode summary kept, in characters; longer text is truncated with "…" and a parse warning (0 = no limit)
+/// * `max_subtitle_length` - Longest podcast/episode subtitle kept, in characters; longer text is truncated with "…" and a parse warning (0 = no limit)
+/// * `dispatch_strategy` - How the distributor assigns new tasks to workers
+/// * `per_task_timeout_seconds` - Deadline for one task's fetch, parse and handoff to the batch inserter, after which it fails (0 to disable); the batched database write is not covered
+/// * `stripped_tracking_params` - Query parameters removed from enclosure and link URLs (comma-separated, `utm_*` matches a prefix, empty to keep all)
+/// * `enclosure_resolve_concurrency` - Maximum number of enclosure length requests in flight across all feeds, separate from the feed fetch limit
+/// * `skip_invalid_items` - Drop an item that fails validation (e.g. an empty title) with a parse warning, instead of failing the whole feed
+/// * `max_episodes` - Most episodes kept per feed; items after the first N in the feed are dropped (0 = no limit)
+/// * `require_audio_enclosure` - Treat an enclosure whose `type` is not `audio/*` (or, without a type, whose URL has no audio extension) as invalid: an error in strict mode, otherwise a parse warning
 ///
 /// # Default Values
 ///
 /// - Max Concurrent Tasks: 5
 /// - Fetch Interval: 3600 seconds (1 hour)
 /// - User Agent: "PodcastCrawler/1.0"
+/// - Request Timeout: 30 seconds
+/// - Connect Timeout: 10 seconds
+/// - Max Redirects: 10
+/// - Max Feed Bytes: 50 MB
+/// - Webhook URL: "" (disabled)
+/// - Accept-Language: "" (not sent)
+/// - Detect Language: false
+/// - Default Max Retries: 3
+/// - Proxy URL: "" (direct connections)
+/// - Scheduler Enabled: false
+/// - Scheduler Interval: 300 seconds
+/// - Scheduler Max In Flight: 100
+/// - Resolve Enclosure Length: false
+/// - Insert Batch Size: 3
+/// - Max Concurrent Inserts: 10
+/// - Insert Batch Timeout: 5000 ms
+/// - Global Requests Per Second: 0 (unlimited)
+/// - Lenient Content-Type Hosts: "" (every host is checked)
+/// - Min Workers: 1
+/// - Max Workers: 0 (the crawler's worker count)
+/// - Worker Idle Timeout: 0 seconds (workers are never stopped)
+/// - Pool Max Idle Per Host: 8
+/// - Pool Idle Timeout: 90 seconds
+/// - DNS Failure TTL: 300 seconds
+/// - Capture Unknown Tags: disabled
+/// - Circuit Breaker Threshold: 5 failures
+/// - Circuit Breaker Cooldown: 60 seconds
+/// - Parse Media RSS: enabled
+/// - Respect robots.txt: disabled
+/// - Robots.txt TTL: 3600 seconds
+/// - Max Title Length: unlimited
+/// - Max Description Length: unlimited
+/// - Max Summary Length: unlimited
+/// - Max Subtitle Length: unlimited
+/// - Dispatch Strategy: round robin
+/// - Per-Task Timeout: none
+/// - Stripped Tracking Params: none
+/// - Enclosure Resolve Concurrency: 8
+/// - Skip Invalid Items: disabled
+/// - Max Episodes: unlimited
+/// - Require Audio Enclosure: disabled
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct CrawlerConfig {
     pub max_concurrent_tasks: usize,
     pub fetch_interval_seconds: u64,
     pub user_agent: String,
+    pub request_timeout_seconds: u64,
+    pub connect_timeout_seconds: u64,
+    pub max_redirects: usize,
+    pub max_feed_bytes: u64,
+    pub webhook_url: String,
+    pub accept_language: String,
+    pub detect_language: bool,
+    pub default_max_retries: u32,
+    pub proxy_url: String,
+    pub scheduler_enabled: bool,
+    pub scheduler_interval_seconds: u64,
+    pub scheduler_max_in_flight: usize,
+    pub resolve_enclosure_length: bool,
+    pub insert_batch_size: usize,
+    pub max_concurrent_inserts: usize,
+    pub insert_batch_timeout_ms: u64,
+    pub global_requests_per_second: u32,
+    pub lenient_content_type_hosts: String,
+    pub min_workers: usize,
+    pub max_workers: usize,
+    pub worker_idle_timeout_seconds: u64,
+    pub pool_max_idle_per_host: usize,
+    pub pool_idle_timeout_seconds: u64,
+    pub dns_failure_ttl_seconds: u64,
+    pub capture_unknown_tags: bool,
+    pub circuit_breaker_threshold: u32,
+    pub circuit_breaker_cooldown_seconds: u64,
+    pub parse_media_rss: bool,
+    pub respect_robots_txt: bool,
+    pub robots_txt_ttl_seconds: u64,
+    pub max_title_length: usize,
+    pub max_description_length: usize,
+    pub max_summary_length: usize,
+    pub max_subtitle_length: usize,
+    pub dispatch_strategy: DispatchStrategy,
+    pub per_task_timeout_seconds: u64,
+    pub stripped_tracking_params: String,
+    pub enclosure_resolve_concurrency: usize,
+    pub skip_invalid_items: bool,
+    pub max_episodes: usize,
+    pub require_audio_enclosure: bool,
 }
 
 impl Default for CrawlerConfig {
@@ -58,6 +264,47 @@ impl Default for CrawlerConfig {
             max_concurrent_tasks: 5,
             fetch_interval_seconds: 3600,
             user_agent: "PodcastCrawler/1.0".to_string(),
+            request_timeout_seconds: 30,
+            connect_timeout_seconds: 10,
+            max_redirects: 10,
+            max_feed_bytes: 50 * 1024 * 1024,
+            webhook_url: String::new(),
+            accept_language: String::new(),
+            detect_language: false,
+            default_max_retries: 3,
+            proxy_url: String::new(),
+            scheduler_enabled: false,
+            scheduler_interval_seconds: 300,
+            scheduler_max_in_flight: 100,
+            resolve_enclosure_length: false,
+            insert_batch_size: 3,
+            max_concurrent_inserts: 10,
+            insert_batch_timeout_ms: 5000,
+            global_requests_per_second: 0,
+            lenient_content_type_hosts: String::new(),
+            min_workers: 1,
+            max_workers: 0,
+            worker_idle_timeout_seconds: 0,
+            pool_max_idle_per_host: 8,
+            pool_idle_timeout_seconds: 90,
+            dns_failure_ttl_seconds: 300,
+            capture_unknown_tags: false,
+            circuit_breaker_threshold: 5,
+            circuit_breaker_cooldown_seconds: 60,
+            parse_media_rss: true,
+            respect_robots_txt: false,
+            robots_txt_ttl_seconds: 3600,
+            max_title_length: 0,
+            max_description_length: 0,
+            max_summary_length: 0,
+            max_subtitle_length: 0,
+            dispatch_strategy: DispatchStrategy::default(),
+            per_task_timeout_seconds: 0,
+            stripped_tracking_params: String::new(),
+            enclosure_resolve_concurrency: 8,
+            skip_invalid_items: false,
+            max_episodes: 0,
+            require_audio_enclosure: false,
         }
     }
 }
@@ -70,6 +317,47 @@ impl CrawlerConfig {
     /// - `CRAWLER_USER_AGENT`: User agent string
     /// - `CRAWLER_MAX_TASKS`: Maximum concurrent tasks
     /// - `CRAWLER_FETCH_INTERVAL`: Fetch interval in seconds
+    /// - `CRAWLER_REQUEST_TIMEOUT`: Request timeout in seconds (optional)
+    /// - `CRAWLER_CONNECT_TIMEOUT`: Connect timeout in seconds (optional)
+    /// - `CRAWLER_MAX_REDIRECTS`: Maximum redirects to follow (optional)
+    /// - `CRAWLER_MAX_FEED_BYTES`: Maximum feed size in bytes (optional)
+    /// - `CRAWLER_WEBHOOK_URL`: Task completion webhook URL (optional)
+    /// - `CRAWLER_ACCEPT_LANGUAGE`: `Accept-Language` header value (optional)
+    /// - `CRAWLER_DETECT_LANGUAGE`: Enable language detection fallback (optional)
+    /// - `CRAWLER_DEFAULT_MAX_RETRIES`: Default fetch retries per task (optional)
+    /// - `CRAWLER_PROXY_URL`: Outbound HTTP proxy URL (optional)
+    /// - `CRAWLER_SCHEDULER_ENABLED`: Enable periodic recrawl of stale feeds (optional)
+    /// - `CRAWLER_SCHEDULER_INTERVAL`: Scheduler interval in seconds (optional)
+    /// - `CRAWLER_SCHEDULER_MAX_IN_FLIGHT`: Scheduler in-flight task cap (optional)
+    /// - `CRAWLER_RESOLVE_ENCLOSURE_LENGTH`: Enable enclosure length resolution (optional)
+    /// - `CRAWLER_INSERT_BATCH_SIZE`: Initial insert batch size (optional)
+    /// - `CRAWLER_MAX_CONCURRENT_INSERTS`: Maximum concurrent insert batches (optional)
+    /// - `CRAWLER_INSERT_BATCH_TIMEOUT_MS`: Partial insert batch timeout in milliseconds (optional)
+    /// - `CRAWLER_GLOBAL_REQUESTS_PER_SECOND`: Global feed request rate cap (optional)
+    /// - `CRAWLER_LENIENT_CONTENT_TYPE_HOSTS`: Hosts that skip the HTML Content-Type check (optional)
+    /// - `CRAWLER_MIN_WORKERS`: Minimum number of workers kept running (optional)
+    /// - `CRAWLER_MAX_WORKERS`: Maximum number of workers (optional)
+    /// - `CRAWLER_WORKER_IDLE_TIMEOUT_SECONDS`: Worker idle timeout in seconds (optional)
+    /// - `CRAWLER_POOL_MAX_IDLE_PER_HOST`: Idle connections kept per host (optional)
+    /// - `CRAWLER_POOL_IDLE_TIMEOUT_SECONDS`: Idle pooled connection timeout in seconds (optional)
+    /// - `CRAWLER_DNS_FAILURE_TTL_SECONDS`: Unresolvable host cache TTL in seconds (optional)
+    /// - `CRAWLER_CAPTURE_UNKNOWN_TAGS`: Enable capturing of unhandled namespaced elements (optional)
+    /// - `CRAWLER_CIRCUIT_BREAKER_THRESHOLD`: Consecutive failures before a host's circuit opens (optional)
+    /// - `CRAWLER_CIRCUIT_BREAKER_COOLDOWN_SECONDS`: Open circuit cooldown in seconds (optional)
+    /// - `CRAWLER_PARSE_MEDIA_RSS`: Enable or disable Media RSS parsing (optional)
+    /// - `CRAWLER_RESPECT_ROBOTS_TXT`: Enable or disable robots.txt checks (optional)
+    /// - `CRAWLER_ROBOTS_TXT_TTL_SECONDS`: Set the robots.txt cache TTL (optional)
+    /// - `CRAWLER_MAX_TITLE_LENGTH`: Set the maximum title length (optional)
+    /// - `CRAWLER_MAX_DESCRIPTION_LENGTH`: Set the maximum description length (optional)
+    /// - `CRAWLER_MAX_SUMMARY_LENGTH`: Set the maximum summary length (optional)
+    /// - `CRAWLER_MAX_SUBTITLE_LENGTH`: Set the maximum subtitle length (optional)
+    /// - `CRAWLER_DISPATCH_STRATEGY`: Worker dispatch strategy: round_robin, least_loaded or similarity (optional)
+    /// - `CRAWLER_PER_TASK_TIMEOUT_SECONDS`: Per-task deadline in seconds, 0 for none (optional)
+    /// - `CRAWLER_STRIPPED_TRACKING_PARAMS`: Comma-separated query parameters stripped from enclosure and link URLs (optional)
+    /// - `CRAWLER_ENCLOSURE_RESOLVE_CONCURRENCY`: Maximum concurrent enclosure length requests (optional)
+    /// - `CRAWLER_SKIP_INVALID_ITEMS`: Enable or disable skipping of invalid items (optional)
+    /// - `CRAWLER_MAX_EPISODES`: Set the maximum episodes kept per feed (optional)
+    /// - `CRAWLER_REQUIRE_AUDIO_ENCLOSURE`: Enable or disable the audio enclosure check (optional)
     ///
     /// # Returns
     ///
@@ -78,6 +366,143 @@ impl CrawlerConfig {
         config_set_string!(self, "CRAWLER_USER_AGENT", self.user_agent);
         config_set_env!(self, "CRAWLER_MAX_TASKS", self.max_concurrent_tasks);
         config_set_env!(self, "CRAWLER_FETCH_INTERVAL", self.fetch_interval_seconds);
+        config_set_env_optional!(
+            self,
+            "CRAWLER_REQUEST_TIMEOUT",
+            self.request_timeout_seconds
+        );
+        config_set_env_optional!(
+            self,
+            "CRAWLER_CONNECT_TIMEOUT",
+            self.connect_timeout_seconds
+        );
+        config_set_env_optional!(self, "CRAWLER_MAX_REDIRECTS", self.max_redirects);
+        config_set_env_optional!(self, "CRAWLER_MAX_FEED_BYTES", self.max_feed_bytes);
+        config_set_env_optional!(self, "CRAWLER_WEBHOOK_URL", self.webhook_url);
+        config_set_env_optional!(self, "CRAWLER_ACCEPT_LANGUAGE", self.accept_language);
+        config_set_env_optional!(self, "CRAWLER_DETECT_LANGUAGE", self.detect_language);
+        config_set_env_optional!(
+            self,
+            "CRAWLER_DEFAULT_MAX_RETRIES",
+            self.default_max_retries
+        );
+        config_set_env_optional!(self, "CRAWLER_PROXY_URL", self.proxy_url);
+        config_set_env_optional!(self, "CRAWLER_SCHEDULER_ENABLED", self.scheduler_enabled);
+        config_set_env_optional!(
+            self,
+            "CRAWLER_SCHEDULER_INTERVAL",
+            self.scheduler_interval_seconds
+        );
+        config_set_env_optional!(
+            self,
+            "CRAWLER_SCHEDULER_MAX_IN_FLIGHT",
+            self.scheduler_max_in_flight
+        );
+        config_set_env_optional!(
+            self,
+            "CRAWLER_RESOLVE_ENCLOSURE_LENGTH",
+            self.resolve_enclosure_length
+        );
+        config_set_env_optional!(self, "CRAWLER_INSERT_BATCH_SIZE", self.insert_batch_size);
+        config_set_env_optional!(
+            self,
+            "CRAWLER_MAX_CONCURRENT_INSERTS",
+            self.max_concurrent_inserts
+        );
+        config_set_env_optional!(
+            self,
+            "CRAWLER_INSERT_BATCH_TIMEOUT_MS",
+            self.insert_batch_timeout_ms
+        );
+        config_set_env_optional!(
+            self,
+            "CRAWLER_GLOBAL_REQUESTS_PER_SECOND",
+            self.global_requests_per_second
+        );
+        config_set_env_optional!(
+            self,
+            "CRAWLER_LENIENT_CONTENT_TYPE_HOSTS",
+            self.lenient_content_type_hosts
+        );
+        config_set_env_optional!(self, "CRAWLER_MIN_WORKERS", self.min_workers);
+        config_set_env_optional!(self, "CRAWLER_MAX_WORKERS", self.max_workers);
+        config_set_env_optional!(
+            self,
+            "CRAWLER_WORKER_IDLE_TIMEOUT_SECONDS",
+            self.worker_idle_timeout_seconds
+        );
+        config_set_env_optional!(
+            self,
+            "CRAWLER_POOL_MAX_IDLE_PER_HOST",
+            self.pool_max_idle_per_host
+        );
+        config_set_env_optional!(
+            self,
+            "CRAWLER_POOL_IDLE_TIMEOUT_SECONDS",
+            self.pool_idle_timeout_seconds
+        );
+        config_set_env_optional!(
+            self,
+            "CRAWLER_DNS_FAILURE_TTL_SECONDS",
+            self.dns_failure_ttl_seconds
+        );
+        config_set_env_optional!(
+            self,
+            "CRAWLER_CAPTURE_UNKNOWN_TAGS",
+            self.capture_unknown_tags
+        );
+        config_set_env_optional!(
+            self,
+            "CRAWLER_CIRCUIT_BREAKER_THRESHOLD",
+            self.circuit_breaker_threshold
+        );
+        config_set_env_optional!(
+            self,
+            "CRAWLER_CIRCUIT_BREAKER_COOLDOWN_SECONDS",
+            self.circuit_breaker_cooldown_seconds
+        );
+        config_set_env_optional!(self, "CRAWLER_PARSE_MEDIA_RSS", self.parse_media_rss);
+        config_set_env_optional!(self, "CRAWLER_RESPECT_ROBOTS_TXT", self.respect_robots_txt);
+        config_set_env_optional!(
+            self,
+            "CRAWLER_ROBOTS_TXT_TTL_SECONDS",
+            self.robots_txt_ttl_seconds
+        );
+        config_set_env_optional!(self, "CRAWLER_MAX_TITLE_LENGTH", self.max_title_length);
+        config_set_env_optional!(
+            self,
+            "CRAWLER_MAX_DESCRIPTION_LENGTH",
+            self.max_description_length
+        );
+        config_set_env_optional!(self, "CRAWLER_MAX_SUMMARY_LENGTH", self.max_summary_length);
+        config_set_env_optional!(
+            self,
+            "CRAWLER_MAX_SUBTITLE_LENGTH",
+            self.max_subtitle_length
+        );
+        config_set_env_optional!(self, "CRAWLER_DISPATCH_STRATEGY", self.dispatch_strategy);
+        config_set_env_optional!(
+            self,
+            "CRAWLER_PER_TASK_TIMEOUT_SECONDS",
+            self.per_task_timeout_seconds
+        );
+        config_set_env_optional!(
+            self,
+            "CRAWLER_STRIPPED_TRACKING_PARAMS",
+            self.stripped_tracking_params
+        );
+        config_set_env_optional!(
+            self,
+            "CRAWLER_ENCLOSURE_RESOLVE_CONCURRENCY",
+            self.enclosure_resolve_concurrency
+        );
+        config_set_env_optional!(self, "CRAWLER_SKIP_INVALID_ITEMS", self.skip_invalid_items);
+        config_set_env_optional!(self, "CRAWLER_MAX_EPISODES", self.max_episodes);
+        config_set_env_optional!(
+            self,
+            "CRAWLER_REQUIRE_AUDIO_ENCLOSURE",
+            self.require_audio_enclosure
+        );
         Ok(())
     }
 
@@ -87,6 +512,13 @@ impl CrawlerConfig {
     /// - Maximum concurrent tasks is greater than 0
     /// - Fetch interval is greater than 0
     /// - User agent is not empty
+    /// - Request and connect timeouts are greater than 0
+    /// - Max feed size is greater than 0
+    /// - Accept-Language is a valid header value
+    /// - Proxy URL, when set, can be used to build a proxy
+    /// - Scheduler interval and in-flight cap are greater than 0
+    /// - Insert batch size, concurrent inserts and batch timeout are greater than 0
+    /// - Enclosure resolve concurrency is greater than 0
     ///
     /// # Returns
     ///
@@ -101,6 +533,99 @@ impl CrawlerConfig {
             "Fetch interval must be > 0"
         );
         config_validate!(!self.user_agent.is_empty(), "User agent cannot be empty");
+        config_validate!(
+            self.request_timeout_seconds > 0,
+            "Request timeout must be > 0"
+        );
+        config_validate!(
+            self.connect_timeout_seconds > 0,
+            "Connect timeout must be > 0"
+        );
+        config_validate!(self.max_feed_bytes > 0, "Max feed bytes must be > 0");
+        config_validate!(
+            reqwest::header::HeaderValue::from_str(&self.accept_language).is_ok(),
+            "Accept-Language must be a valid header value"
+        );
+        config_validate!(
+            self.scheduler_interval_seconds > 0,
+            "Scheduler interval must be > 0"
+        );
+        config_validate!(
+            self.scheduler_max_in_flight > 0,
+            "Scheduler max in flight must be > 0"
+        );
+        config_validate!(
+            self.insert_batch_size >= 1,
+            "Insert batch size must be >= 1"
+        );
+        config_validate!(
+            self.max_concurrent_inserts >= 1,
+            "Max concurrent inserts must be >= 1"
+        );
+        config_validate!(
+            self.insert_batch_timeout_ms > 0,
+            "Insert batch timeout must be > 0"
+        );
+        config_validate!(
+            self.enclosure_resolve_concurrency >= 1,
+            "Enclosure resolve concurrency must be >= 1"
+        );
+        config_validate!(
+            self.pool_idle_timeout_seconds > 0,
+            "Pool idle timeout must be > 0"
+        );
+        config_validate!(
+            self.max_workers == 0 || self.min_workers <= self.max_workers,
+            "Min workers must be <= max workers"
+        );
+        self.proxy()?;
         Ok(())
     }
+
+    /// Builds the outbound proxy from `proxy_url`
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(None)` when no proxy is configured, or an error if the
+    /// proxy URL cannot be parsed.
+    pub fn proxy(&self) -> AppResult<Option<reqwest::Proxy>> {
+        if self.proxy_url.trim().is_empty() {
+            return Ok(None);
+        }
+        reqwest::Proxy::all(self.proxy_url.trim())
+            .map(Some)
+            .map_err(|e| {
+                AppError::Infrastructure(InfrastructureError::new(
+                    InfrastructureErrorKind::Config,
+                    format!("Invalid crawler proxy URL: {}", self.proxy_url),
+                    Some(Box::new(e)),
+                ))
+            })
+    }
+}
+
+/// How the distributor assigns new tasks to workers
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DispatchStrategy {
+    /// Cycle through the workers in order (default)
+    #[default]
+    RoundRobin,
+    /// Pick the worker with the fewest unfinished tasks
+    LeastLoaded,
+    /// Prefer short queues, then workers that recently crawled similar URLs
+    Similarity,
+}
+
+impl std::str::FromStr for DispatchStrategy {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().replace('-', "_").as_str() {
+            "round_robin" => Ok(Self::RoundRobin),
+            "least_loaded" => Ok(Self::LeastLoaded),
+            "similarity" => Ok(Self::Similarity),
+            other => Err(format!("Unknown dispatch strategy: {}", other)),
+        }
+    }
 }
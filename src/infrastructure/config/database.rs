@@ -14,6 +14,9 @@
 //! - `DATABASE_MIN_CONNECTIONS`: Minimum number of connections in the pool
 //! - `DATABASE_CONNECT_TIMEOUT`: Connection timeout in seconds
 //! - `DATABASE_IDLE_TIMEOUT`: Idle connection timeout in seconds
+//! - `DATABASE_STATEMENT_TIMEOUT_MS`: Per-statement timeout in milliseconds, 0 disables it (optional)
+//! - `DATABASE_WARMUP`: Open `min_connections` connections during startup (optional)
+//! - `DATABASE_CONFLICT_STRATEGY`: What upserts do with stored rows (overwrite, skip_existing, update_if_newer) (optional)
 //!
 //! # Example
 //!
@@ -26,6 +29,10 @@
 //!     min_connections: 2,
 //!     connect_timeout_seconds: 30,
 //!     idle_timeout_seconds: 300,
+//!     no_ssl: true,
+//!     statement_timeout_ms: 30_000,
+//!     warmup: true,
+//!     conflict_strategy: "update_if_newer".to_string(),
 //! };
 //!
 //! assert!(config.validate().is_ok());
@@ -33,7 +40,8 @@
 
 use crate::infrastructure::config::utils;
 use crate::infrastructure::config::AppResult;
-use crate::{config_set_env, config_validate};
+use crate::infrastructure::persistence::repositories::ConflictStrategy;
+use crate::{config_set_env, config_set_env_optional, config_validate};
 use serde::{Deserialize, Serialize};
 
 /// Database configuration
@@ -48,6 +56,9 @@ use serde::{Deserialize, Serialize};
 /// * `min_connections` - Minimum number of connections in the pool
 /// * `connect_timeout_seconds` - Connection timeout in seconds
 /// * `idle_timeout_seconds` - Idle connection timeout in seconds
+/// * `statement_timeout_ms` - `statement_timeout` set on every new connection (0 for no limit)
+/// * `warmup` - Eagerly open `min_connections` connections during startup
+/// * `conflict_strategy` - What upserts do with podcasts and episodes that are already stored
 ///
 /// # Default Values
 ///
@@ -56,6 +67,9 @@ use serde::{Deserialize, Serialize};
 /// - Min Connections: 2
 /// - Connect Timeout: 30 seconds
 /// - Idle Timeout: 300 seconds
+/// - Statement Timeout: 0 (no limit)
+/// - Warmup: false
+/// - Conflict Strategy: "overwrite"
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct DatabaseConfig {
     pub url: String,
@@ -64,6 +78,9 @@ pub struct DatabaseConfig {
     pub connect_timeout_seconds: u64,
     pub idle_timeout_seconds: u64,
     pub no_ssl: bool,
+    pub statement_timeout_ms: i64,
+    pub warmup: bool,
+    pub conflict_strategy: String,
 }
 
 impl Default for DatabaseConfig {
@@ -75,6 +92,9 @@ impl Default for DatabaseConfig {
             connect_timeout_seconds: 30,
             idle_timeout_seconds: 300,
             no_ssl: true,
+            statement_timeout_ms: 0,
+            warmup: false,
+            conflict_strategy: "overwrite".to_string(),
         }
     }
 }
@@ -93,6 +113,9 @@ impl DatabaseConfig {
     /// - `DATABASE_MIN_CONNECTIONS`
     /// - `DATABASE_CONNECT_TIMEOUT`
     /// - `DATABASE_IDLE_TIMEOUT`
+    /// - `DATABASE_STATEMENT_TIMEOUT_MS` (optional)
+    /// - `DATABASE_WARMUP` (optional)
+    /// - `DATABASE_CONFLICT_STRATEGY` (optional)
     ///
     /// # Returns
     ///
@@ -112,6 +135,13 @@ impl DatabaseConfig {
         );
         config_set_env!(self, "DATABASE_IDLE_TIMEOUT", self.idle_timeout_seconds);
         config_set_env!(self, "NO_SSL", self.no_ssl);
+        config_set_env_optional!(
+            self,
+            "DATABASE_STATEMENT_TIMEOUT_MS",
+            self.statement_timeout_ms
+        );
+        config_set_env_optional!(self, "DATABASE_WARMUP", self.warmup);
+        config_set_env_optional!(self, "DATABASE_CONFLICT_STRATEGY", self.conflict_strategy);
         Ok(())
     }
 
@@ -123,6 +153,8 @@ impl DatabaseConfig {
     /// - Min connections > 0
     /// - Connect timeout > 0
     /// - Idle timeout > 0
+    /// - Statement timeout >= 0
+    /// - Conflict strategy is one of: overwrite, skip_existing, update_if_newer
     ///
     /// # Returns
     ///
@@ -138,6 +170,24 @@ impl DatabaseConfig {
             "Connect timeout must be > 0"
         );
         config_validate!(self.idle_timeout_seconds > 0, "Idle timeout must be > 0");
+        config_validate!(
+            self.statement_timeout_ms >= 0,
+            "Statement timeout must be >= 0"
+        );
+        config_validate!(
+            self.conflict_strategy().is_some(),
+            "Invalid conflict strategy. Must be one of: overwrite, skip_existing, update_if_newer"
+        );
         Ok(())
     }
+
+    /// Returns the upsert conflict strategy, or `None` if `conflict_strategy` is not a known value
+    pub fn conflict_strategy(&self) -> Option<ConflictStrategy> {
+        match self.conflict_strategy.as_str() {
+            "overwrite" => Some(ConflictStrategy::Overwrite),
+            "skip_existing" => Some(ConflictStrategy::SkipExisting),
+            "update_if_newer" => Some(ConflictStrategy::UpdateIfNewer),
+            _ => None,
+        }
+    }
 }
@@ -4,6 +4,8 @@
 //! - Log level
 //! - Log file path
 //! - Log format (plain text or JSON)
+//! - Log file rotation and retention
+//! - Console output
 //!
 //! # Environment Variables
 //!
@@ -11,6 +13,10 @@
 //! - `LOG_LEVEL`: Log level (error, warn, info, debug, trace)
 //! - `LOG_FILE`: Path to log file
 //! - `LOG_JSON`: Whether to use JSON format (true/false)
+//! - `LOG_ROTATION`: Log file rotation (daily, hourly, never)
+//! - `LOG_MAX_FILES`: Number of rotated log files to keep, 0 keeps all
+//! - `LOG_CONSOLE`: Whether to also log to stdout (true/false)
+//! - `LOG_CONSOLE_ANSI`: Whether console output uses ANSI colors (true/false)
 //!
 //! # Example
 //!
@@ -21,14 +27,19 @@
 //!     level: "info".to_string(),
 //!     file_path: "logs".to_string(),
 //!     json_format: false,
+//!     rotation: "hourly".to_string(),
+//!     max_files: 24,
+//!     console_enabled: false,
+//!     console_ansi: false,
 //! };
 //!
 //! assert!(config.validate().is_ok());
 //! ```
 
 use crate::infrastructure::config::AppResult;
-use crate::{config_set_env, config_set_string, config_validate};
+use crate::{config_set_env, config_set_env_optional, config_set_string, config_validate};
 use serde::{Deserialize, Serialize};
+use tracing_appender::rolling::Rotation;
 
 /// Logging configuration
 ///
@@ -39,17 +50,29 @@ use serde::{Deserialize, Serialize};
 /// * `level` - Log level (error, warn, info, debug, trace)
 /// * `file_path` - Path to log file
 /// * `json_format` - Whether to use JSON format for logs
+/// * `rotation` - Log file rotation (daily, hourly, never)
+/// * `max_files` - Number of rotated log files to keep, 0 keeps all
+/// * `console_enabled` - Whether to also log to stdout
+/// * `console_ansi` - Whether console output uses ANSI colors
 ///
 /// # Default Values
 ///
 /// - Level: "info"
 /// - File Path: "logs"
 /// - JSON Format: false
+/// - Rotation: "daily"
+/// - Max Files: 0
+/// - Console Enabled: true
+/// - Console ANSI: true
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct LoggingConfig {
     pub level: String,
     pub file_path: String,
     pub json_format: bool,
+    pub rotation: String,
+    pub max_files: usize,
+    pub console_enabled: bool,
+    pub console_ansi: bool,
 }
 
 impl Default for LoggingConfig {
@@ -58,6 +81,10 @@ impl Default for LoggingConfig {
             level: "info".to_string(),
             file_path: "logs".to_string(),
             json_format: false,
+            rotation: "daily".to_string(),
+            max_files: 0,
+            console_enabled: true,
+            console_ansi: true,
         }
     }
 }
@@ -70,6 +97,10 @@ impl LoggingConfig {
     /// - `LOG_LEVEL`: Log level
     /// - `LOG_FILE`: Log file path
     /// - `LOG_JSON`: JSON format flag
+    /// - `LOG_ROTATION`: Log file rotation (optional)
+    /// - `LOG_MAX_FILES`: Number of log files to keep (optional)
+    /// - `LOG_CONSOLE`: Console output flag (optional)
+    /// - `LOG_CONSOLE_ANSI`: Console ANSI colors flag (optional)
     ///
     /// # Returns
     ///
@@ -79,6 +110,10 @@ impl LoggingConfig {
         config_set_string!(self, "LOG_LEVEL", self.level);
         config_set_string!(self, "LOG_FILE", self.file_path);
         config_set_env!(self, "LOG_JSON", self.json_format);
+        config_set_env_optional!(self, "LOG_ROTATION", self.rotation);
+        config_set_env_optional!(self, "LOG_MAX_FILES", self.max_files);
+        config_set_env_optional!(self, "LOG_CONSOLE", self.console_enabled);
+        config_set_env_optional!(self, "LOG_CONSOLE_ANSI", self.console_ansi);
         self.validate()?;
         Ok(())
     }
@@ -89,6 +124,7 @@ impl LoggingConfig {
     /// - Log level is not empty
     /// - Log level is one of: error, warn, info, debug, trace
     /// - Log file path is not empty
+    /// - Rotation is one of: daily, hourly, never
     ///
     /// # Returns
     ///
@@ -103,6 +139,20 @@ impl LoggingConfig {
             "Invalid log level. Must be one of: error, warn, info, debug, trace"
         );
         config_validate!(!self.file_path.is_empty(), "Log file path cannot be empty");
+        config_validate!(
+            self.rotation().is_some(),
+            "Invalid log rotation. Must be one of: daily, hourly, never"
+        );
         Ok(())
     }
+
+    /// Returns the file rotation, or `None` if `rotation` is not a known value
+    pub fn rotation(&self) -> Option<Rotation> {
+        match self.rotation.as_str() {
+            "daily" => Some(Rotation::DAILY),
+            "hourly" => Some(Rotation::HOURLY),
+            "never" => Some(Rotation::NEVER),
+            _ => None,
+        }
+    }
 }
@@ -53,6 +53,31 @@ macro_rules! config_set_env {
     };
 }
 
+/// Sets a configuration value from an optional environment variable
+///
+/// Like [`config_set_env!`], but leaves the current (default) value untouched
+/// when the environment variable is not set. Parsing errors are still returned.
+///
+/// # Arguments
+///
+/// * `$settings` - The configuration struct being modified
+/// * `$env` - The environment variable name
+/// * `$target` - The target field to set
+///
+/// # Example
+///
+/// ```rust,ignore
+/// config_set_env_optional!(self, "REQUEST_TIMEOUT", self.request_timeout_seconds);
+/// ```
+#[macro_export]
+macro_rules! config_set_env_optional {
+    ($settings:expr, $env:literal, $target:expr) => {
+        if std::env::var($env).is_ok() {
+            $crate::config_set_env!($settings, $env, $target);
+        }
+    };
+}
+
 /// Sets a string configuration value from an environment variable
 ///
 /// This macro attempts to get a string value from an environment variable.
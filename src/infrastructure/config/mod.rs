@@ -57,7 +57,7 @@ pub mod macros;
 pub mod server;
 pub mod utils;
 
-pub use crawler::CrawlerConfig;
+pub use crawler::{CrawlerConfig, DispatchStrategy};
 pub use database::DatabaseConfig;
 pub use logging::LoggingConfig;
 pub use server::ServerConfig;
@@ -4,6 +4,7 @@
 //! - Host address
 //! - Port number
 //! - Worker thread count
+//! - Pagination limits
 //!
 //! # Environment Variables
 //!
@@ -11,6 +12,8 @@
 //! - `SERVER_HOST`: Server host address
 //! - `SERVER_PORT`: Server port number
 //! - `SERVER_WORKERS`: Number of worker threads
+//! - `SERVER_DEFAULT_PER_PAGE`: Page size when a request does not give one (optional)
+//! - `SERVER_MAX_PER_PAGE`: Largest page size a request may ask for (optional)
 //!
 //! # Example
 //!
@@ -21,13 +24,15 @@
 //!     host: "127.0.0.1".to_string(),
 //!     port: 8080,
 //!     workers: 4,
+//!     default_per_page: 10,
+//!     max_per_page: 200,
 //! };
 //!
 //! assert!(config.validate().is_ok());
 //! ```
 
 use crate::infrastructure::AppResult;
-use crate::{config_set_env, config_set_string, config_validate};
+use crate::{config_set_env, config_set_env_optional, config_set_string, config_validate};
 use serde::{Deserialize, Serialize};
 
 /// Server configuration
@@ -39,17 +44,23 @@ use serde::{Deserialize, Serialize};
 /// * `host` - Server host address
 /// * `port` - Server port number
 /// * `workers` - Number of worker threads
+/// * `default_per_page` - Page size of paginated endpoints when the request does not give one
+/// * `max_per_page` - Larger page sizes are lowered to this value
 ///
 /// # Default Values
 ///
 /// - Host: "127.0.0.1"
 /// - Port: 8080
 /// - Workers: 4
+/// - Default Per Page: 10
+/// - Max Per Page: 200
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ServerConfig {
     pub host: String,
     pub port: u16,
     pub workers: usize,
+    pub default_per_page: i64,
+    pub max_per_page: i64,
 }
 
 impl Default for ServerConfig {
@@ -58,6 +69,8 @@ impl Default for ServerConfig {
             host: "127.0.0.1".to_string(),
             port: 8080,
             workers: 4,
+            default_per_page: 10,
+            max_per_page: 200,
         }
     }
 }
@@ -70,6 +83,8 @@ impl ServerConfig {
     /// - `SERVER_HOST`
     /// - `SERVER_PORT`
     /// - `SERVER_WORKERS`
+    /// - `SERVER_DEFAULT_PER_PAGE` (optional)
+    /// - `SERVER_MAX_PER_PAGE` (optional)
     ///
     /// # Returns
     ///
@@ -78,6 +93,8 @@ impl ServerConfig {
         config_set_string!(self, "SERVER_HOST", self.host);
         config_set_env!(self, "SERVER_PORT", self.port);
         config_set_env!(self, "SERVER_WORKERS", self.workers);
+        config_set_env_optional!(self, "SERVER_DEFAULT_PER_PAGE", self.default_per_page);
+        config_set_env_optional!(self, "SERVER_MAX_PER_PAGE", self.max_per_page);
         Ok(())
     }
 
@@ -87,6 +104,7 @@ impl ServerConfig {
     /// - Host is not empty
     /// - Port is greater than 0
     /// - Worker count is greater than 0
+    /// - Default page size is greater than 0 and not above the maximum
     ///
     /// # Returns
     ///
@@ -95,6 +113,11 @@ impl ServerConfig {
         config_validate!(!self.host.is_empty(), "Server host cannot be empty");
         config_validate!(self.port > 0, "Server port cannot be 0");
         config_validate!(self.workers > 0, "Server workers cannot be 0");
+        config_validate!(self.default_per_page > 0, "Default per_page must be > 0");
+        config_validate!(
+            self.max_per_page >= self.default_per_page,
+            "Max per_page must be >= default per_page"
+        );
         Ok(())
     }
 }
@@ -54,6 +54,8 @@ pub enum DomainErrorKind {
     InvalidState,
     /// Batch processing errors
     BatchProcessing,
+    /// Duplicate entity or work item errors
+    Duplicate,
     /// Other domain-specific errors
     Other,
     /// Unexpected errors
@@ -67,6 +69,7 @@ impl fmt::Display for DomainErrorKind {
             Self::NotFound => write!(f, "Not found"),
             Self::InvalidState => write!(f, "Invalid state"),
             Self::BatchProcessing => write!(f, "Batch processing error"),
+            Self::Duplicate => write!(f, "Duplicate"),
             Self::Other => write!(f, "Other domain error"),
             Self::Unexpected => write!(f, "Unexpected error"),
         }
@@ -160,6 +163,7 @@ impl DomainError {
     /// - `NOT_FOUND_ERROR` for not found errors
     /// - `INVALID_STATE_ERROR` for invalid state errors
     /// - `BATCH_ERROR` for batch processing errors
+    /// - `DUPLICATE_ERROR` for duplicate errors
     /// - `DOMAIN_ERROR` for other domain errors
     /// - `UNEXPECTED_ERROR` for unexpected errors
     pub fn error_code(&self) -> &'static str {
@@ -168,6 +172,7 @@ impl DomainError {
             DomainErrorKind::NotFound => "NOT_FOUND_ERROR",
             DomainErrorKind::InvalidState => "INVALID_STATE_ERROR",
             DomainErrorKind::BatchProcessing => "BATCH_ERROR",
+            DomainErrorKind::Duplicate => "DUPLICATE_ERROR",
             DomainErrorKind::Other => "DOMAIN_ERROR",
             DomainErrorKind::Unexpected => "UNEXPECTED_ERROR",
         }
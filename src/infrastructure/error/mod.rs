@@ -266,14 +266,25 @@ impl From<DieselError> for AppError {
     ///
     /// Maps database-specific errors to appropriate AppError variants
     fn from(err: DieselError) -> Self {
+        // Statement timeouts are reported as database errors so that callers retry them
+        let message = if is_statement_timeout(&err) {
+            format!("Database statement timeout exceeded: {}", err)
+        } else {
+            err.to_string()
+        };
         AppError::Infrastructure(InfrastructureError::new(
             InfrastructureErrorKind::Database,
-            err.to_string(),
+            message,
             Some(Box::new(err)),
         ))
     }
 }
 
+// Postgres cancels the query with "canceling statement due to statement timeout"
+fn is_statement_timeout(err: &DieselError) -> bool {
+    matches!(err, DieselError::DatabaseError(_, info) if info.message().contains("statement timeout"))
+}
+
 impl From<PoolError> for AppError {
     /// Converts a connection pool error into an AppError
     ///
@@ -312,6 +323,13 @@ impl From<reqwest::Error> for AppError {
                 None,
                 Some(Box::new(err)),
             ))
+        } else if err.is_redirect() {
+            AppError::Network(NetworkError::new(
+                NetworkErrorKind::TooManyRedirects,
+                err.to_string(),
+                None,
+                Some(Box::new(err)),
+            ))
         } else if err.is_connect() {
             AppError::Network(NetworkError::new(
                 NetworkErrorKind::Connection,
@@ -329,3 +347,22 @@ impl From<reqwest::Error> for AppError {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use diesel::result::DatabaseErrorKind;
+
+    #[test]
+    fn test_statement_timeout_is_retryable() {
+        let err: AppError = DieselError::DatabaseError(
+            DatabaseErrorKind::Unknown,
+            Box::new("canceling statement due to statement timeout".to_string()),
+        )
+        .into();
+        assert!(err.is_retryable());
+        assert!(err
+            .to_string()
+            .contains("Database statement timeout exceeded"));
+    }
+}
@@ -50,6 +50,8 @@ use thiserror::Error;
 pub enum NetworkErrorKind {
     /// Connection establishment errors
     Connection,
+    /// The host name could not be resolved
+    DnsResolution,
     /// Request timeout errors
     Timeout,
     /// Too many redirects in request chain
@@ -58,6 +60,10 @@ pub enum NetworkErrorKind {
     InvalidResponse,
     /// Rate limit exceeded
     RateLimit,
+    /// The host failed repeatedly and is skipped until its cooldown ends
+    CircuitOpen,
+    /// The host's robots.txt does not allow crawling the URL
+    RobotsDisallowed,
     /// Other network-related errors
     Other,
 }
@@ -66,10 +72,13 @@ impl fmt::Display for NetworkErrorKind {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::Connection => write!(f, "Connection error"),
+            Self::DnsResolution => write!(f, "Host unresolvable"),
             Self::Timeout => write!(f, "Timeout"),
             Self::TooManyRedirects => write!(f, "Too many redirects"),
             Self::InvalidResponse => write!(f, "Invalid response"),
             Self::RateLimit => write!(f, "Rate limit exceeded"),
+            Self::CircuitOpen => write!(f, "Circuit open"),
+            Self::RobotsDisallowed => write!(f, "Disallowed by robots.txt"),
             Self::Other => write!(f, "Other network error"),
         }
     }
@@ -165,6 +174,9 @@ impl NetworkError {
     /// - Connection errors
     /// - Timeout errors
     /// - Rate limit errors
+    ///
+    /// Unresolvable hosts are not retried, the name is unlikely to resolve
+    /// on the next attempt.
     pub fn is_retryable(&self) -> bool {
         matches!(
             self.kind,
@@ -181,18 +193,24 @@ impl NetworkError {
     ///
     /// Returns a static string containing the error code:
     /// - `CONNECTION_ERROR` for connection errors
+    /// - `DNS_ERROR` for unresolvable hosts
     /// - `TIMEOUT_ERROR` for timeout errors
     /// - `REDIRECT_ERROR` for too many redirects
     /// - `RESPONSE_ERROR` for invalid responses
     /// - `RATE_LIMIT_ERROR` for rate limit errors
+    /// - `CIRCUIT_OPEN_ERROR` for hosts skipped by the circuit breaker
+    /// - `ROBOTS_DISALLOWED_ERROR` for URLs disallowed by robots.txt
     /// - `NETWORK_ERROR` for other network errors
     pub fn error_code(&self) -> &'static str {
         match self.kind {
             NetworkErrorKind::Connection => "CONNECTION_ERROR",
+            NetworkErrorKind::DnsResolution => "DNS_ERROR",
             NetworkErrorKind::Timeout => "TIMEOUT_ERROR",
             NetworkErrorKind::TooManyRedirects => "REDIRECT_ERROR",
             NetworkErrorKind::InvalidResponse => "RESPONSE_ERROR",
             NetworkErrorKind::RateLimit => "RATE_LIMIT_ERROR",
+            NetworkErrorKind::CircuitOpen => "CIRCUIT_OPEN_ERROR",
+            NetworkErrorKind::RobotsDisallowed => "ROBOTS_DISALLOWED_ERROR",
             NetworkErrorKind::Other => "NETWORK_ERROR",
         }
     }
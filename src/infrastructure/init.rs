@@ -50,7 +50,8 @@
 
 use crate::infrastructure::logging::init_logger;
 use crate::infrastructure::persistence::repositories::{
-    EpisodeRepository, PodcastRankRepository, PodcastRepository,
+    ConflictStrategy, EpisodeRepository, FailedFeedRepository, PageLimits, PodcastRankRepository,
+    PodcastRepository,
 };
 use crate::infrastructure::Settings;
 use crate::infrastructure::{
@@ -59,7 +60,8 @@ use crate::infrastructure::{
 };
 
 use std::sync::Arc;
-use tracing::{error, info};
+use std::time::Instant;
+use tracing::{error, info, warn};
 
 use super::DatabaseContext;
 
@@ -73,6 +75,7 @@ use super::DatabaseContext;
 /// - `podcast`: Manages podcast metadata and information
 /// - `podcast_rank`: Handles podcast ranking and statistics
 /// - `episode`: Manages podcast episode data
+/// - `failed_feed`: Feeds whose crawl failed every retry
 ///
 /// # Example
 ///
@@ -94,6 +97,7 @@ pub struct AppRepositories {
     pub podcast: PodcastRepository,
     pub podcast_rank: PodcastRankRepository,
     pub episode: EpisodeRepository,
+    pub failed_feed: FailedFeedRepository,
 }
 
 impl AppRepositories {
@@ -110,7 +114,26 @@ impl AppRepositories {
         Self {
             podcast: PodcastRepository::new(database_context.clone()),
             podcast_rank: PodcastRankRepository::new(database_context.clone()),
-            episode: EpisodeRepository::new(database_context),
+            episode: EpisodeRepository::new(database_context.clone()),
+            failed_feed: FailedFeedRepository::new(database_context),
+        }
+    }
+
+    /// Applies `page_limits` to the paginated queries
+    pub fn with_page_limits(self, page_limits: PageLimits) -> Self {
+        Self {
+            podcast: self.podcast.with_page_limits(page_limits),
+            episode: self.episode.with_page_limits(page_limits),
+            failed_feed: self.failed_feed.with_page_limits(page_limits),
+            ..self
+        }
+    }
+
+    /// Applies `conflict_strategy` to the podcast and episode upserts
+    pub fn with_conflict_strategy(self, conflict_strategy: ConflictStrategy) -> Self {
+        Self {
+            podcast: self.podcast.with_conflict_strategy(conflict_strategy),
+            ..self
         }
     }
 }
@@ -227,9 +250,31 @@ impl AppState {
             )));
         }
 
+        // The pool opens the remaining connections lazily, warm it up if configured
+        if settings.database.warmup {
+            let start = Instant::now();
+            let count = settings.database.min_connections;
+            match database_context.warmup(count).await {
+                Ok(()) => info!(
+                    "Warmed up {} database connections in {:?}",
+                    count,
+                    start.elapsed()
+                ),
+                Err(e) => warn!("Database connection warmup failed: {}", e),
+            }
+        }
+
         // Initialize repositories
         info!("Initializing repositories...");
-        let repositories = Arc::new(AppRepositories::new(database_context.clone()));
+        let page_limits = PageLimits::new(
+            settings.server.default_per_page,
+            settings.server.max_per_page,
+        );
+        let repositories = Arc::new(
+            AppRepositories::new(database_context.clone())
+                .with_page_limits(page_limits)
+                .with_conflict_strategy(settings.database.conflict_strategy().unwrap_or_default()),
+        );
 
         info!("Application initialization complete!");
 
@@ -297,7 +342,7 @@ impl AppState {
         // Basic repository checks
         self.repositories
             .podcast
-            .get_all(1, 10)
+            .get_all(1, 10, false, true)
             .await
             .map_err(|e| {
                 AppError::Infrastructure(InfrastructureError::new(
@@ -309,6 +354,27 @@ impl AppState {
 
         Ok(())
     }
+
+    /// Lightweight liveness probe
+    ///
+    /// Unlike [`health_check`](Self::health_check), this only runs a
+    /// `SELECT 1` against the database, making it cheap enough to be
+    /// polled frequently by an orchestrator.
+    ///
+    /// # Returns
+    ///
+    /// Returns `AppResult<()>` which is:
+    /// - `Ok(())` if the database answered
+    /// - `Err(AppError)` if the database is unreachable
+    pub async fn liveness_check(&self) -> AppResult<()> {
+        self.database_context.ping().await.map_err(|e| {
+            AppError::Infrastructure(InfrastructureError::new(
+                InfrastructureErrorKind::Database,
+                "Database liveness check failed".to_string(),
+                Some(Box::new(e)),
+            ))
+        })
+    }
 }
 
 #[cfg(test)]
@@ -7,7 +7,7 @@
 //!
 //! - Multiple log formats (JSON, Text)
 //! - File and console output
-//! - Log rotation
+//! - Log rotation and retention
 //! - Log levels
 //! - Structured logging
 //! - Request tracing
@@ -47,19 +47,23 @@
 
 use std::{path::Path, sync::Once};
 use time::macros::format_description;
+use tracing::Subscriber;
 use tracing_appender::rolling::{RollingFileAppender, Rotation};
 use tracing_subscriber::{
     fmt::{
         self,
         format::{FmtSpan, Format},
-        time::LocalTime,
+        time::{FormatTime, LocalTime},
     },
     layer::SubscriberExt,
+    registry::LookupSpan,
     util::SubscriberInitExt,
     EnvFilter,
 };
 
-use crate::infrastructure::{config::LoggingConfig, error::AppResult};
+use crate::infrastructure::{
+    config::LoggingConfig, error::AppResult, InfrastructureError, InfrastructureErrorKind,
+};
 
 static LOGGER_INIT: Once = Once::new();
 
@@ -72,7 +76,8 @@ static LOGGER_INIT: Once = Once::new();
 ///
 /// - Multiple output formats (JSON, Text)
 /// - Console and file output
-/// - Log rotation (Daily, Hourly)
+/// - Log rotation (Daily, Hourly, Never)
+/// - Pruning of old log files on startup (`max_files`)
 /// - Configurable log levels
 /// - Request span tracking
 ///
@@ -103,26 +108,39 @@ static LOGGER_INIT: Once = Once::new();
 /// init_logger(&config)?;
 /// ```
 pub fn init_logger(config: &LoggingConfig) -> AppResult<()> {
+    config.validate()?;
+
     // Skip if logger is already initialized
     if LOGGER_INIT.is_completed() {
         return Ok(());
     }
 
-    // Initialize with both layers
-    LOGGER_INIT.call_once(|| {
-        // Create file appender
-        let file_appender = RollingFileAppender::new(
-            Rotation::DAILY,
-            Path::new(&config.file_path)
-                .parent()
-                .unwrap_or(Path::new(".")),
+    // Create file appender, pruning rotated files beyond `max_files` on startup
+    let file_appender = RollingFileAppender::builder()
+        .rotation(config.rotation().unwrap_or(Rotation::DAILY))
+        .filename_prefix(
             Path::new(&config.file_path)
                 .file_name()
                 .unwrap_or_default()
                 .to_str()
                 .unwrap_or("podcast_crawler.log"),
-        );
+        )
+        .max_log_files(config.max_files)
+        .build(
+            Path::new(&config.file_path)
+                .parent()
+                .unwrap_or(Path::new(".")),
+        )
+        .map_err(|e| {
+            InfrastructureError::new(
+                InfrastructureErrorKind::IO,
+                format!("Failed to create log file appender: {}", e),
+                Some(Box::new(e)),
+            )
+        })?;
 
+    // Initialize with both layers
+    LOGGER_INIT.call_once(|| {
         // Create time format
         let time_format = format_description!("[year]-[month]-[day] [hour]:[minute]:[second]");
         let timer = LocalTime::new(time_format);
@@ -174,8 +192,34 @@ pub fn init_logger(config: &LoggingConfig) -> AppResult<()> {
             .add_directive("reqwest=warn".parse().unwrap())
             .add_directive("html5ever=warn".parse().unwrap());
 
-        // Create console layer with colored output
-        let stdout_layer = fmt::layer()
+        // Console layer is skipped entirely when disabled (e.g. under journald)
+        let stdout_layer = console_layer(config, timer);
+
+        // Register subscriber with the layers and filter
+        tracing_subscriber::registry()
+            .with(env_filter)
+            .with(file_layer)
+            .with(stdout_layer)
+            .init();
+    });
+
+    Ok(())
+}
+
+/// Build the stdout layer, or `None` when console logging is disabled
+fn console_layer<S, T>(
+    config: &LoggingConfig,
+    timer: T,
+) -> Option<Box<dyn tracing_subscriber::Layer<S> + Send + Sync>>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    T: FormatTime + Send + Sync + 'static,
+{
+    if !config.console_enabled {
+        return None;
+    }
+    Some(Box::new(
+        fmt::layer()
             .with_writer(std::io::stdout)
             .event_format(
                 Format::default()
@@ -185,22 +229,13 @@ pub fn init_logger(config: &LoggingConfig) -> AppResult<()> {
                     .with_thread_names(false)
                     .with_file(false)
                     .with_line_number(true)
-                    .with_ansi(true)
+                    .with_ansi(config.console_ansi)
                     .with_source_location(true)
                     .with_timer(timer)
                     .compact(),
             )
-            .with_ansi(true);
-
-        // Register subscriber with both layers and filter
-        tracing_subscriber::registry()
-            .with(env_filter)
-            .with(file_layer)
-            .with(stdout_layer)
-            .init();
-    });
-
-    Ok(())
+            .with_ansi(config.console_ansi),
+    ))
 }
 
 #[cfg(test)]
@@ -215,6 +250,7 @@ mod tests {
             level: "debug".to_string(),
             file_path: "target/test-logs-json".to_string(),
             json_format: true,
+            ..Default::default()
         };
 
         assert!(init_logger(&config).is_ok());
@@ -229,6 +265,7 @@ mod tests {
             level: "debug".to_string(),
             file_path: "target/test-logs-text".to_string(),
             json_format: false,
+            ..Default::default()
         };
 
         assert!(init_logger(&config).is_ok());
@@ -243,8 +280,44 @@ mod tests {
             level: "debug".to_string(),
             file_path: "/invalid/path/that/should/not/exist".to_string(),
             json_format: false,
+            ..Default::default()
+        };
+
+        assert!(init_logger(&config).is_err());
+    }
+
+    /// Test that an unknown rotation is rejected
+    #[test]
+    fn test_init_logger_invalid_rotation() {
+        let config = LoggingConfig {
+            file_path: "target/test-logs-rotation".to_string(),
+            rotation: "weekly".to_string(),
+            ..Default::default()
         };
 
         assert!(init_logger(&config).is_err());
+        assert!(!Path::new("target/test-logs-rotation").exists());
+    }
+
+    /// Test that the console layer is only built when enabled
+    #[test]
+    fn test_console_layer_disabled() {
+        let config = LoggingConfig {
+            console_enabled: false,
+            ..Default::default()
+        };
+        assert!(
+            console_layer::<tracing_subscriber::Registry, _>(&config, LocalTime::rfc_3339())
+                .is_none()
+        );
+
+        let config = LoggingConfig {
+            console_ansi: false,
+            ..Default::default()
+        };
+        assert!(
+            console_layer::<tracing_subscriber::Registry, _>(&config, LocalTime::rfc_3339())
+                .is_some()
+        );
     }
 }
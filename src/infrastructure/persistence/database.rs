@@ -10,7 +10,7 @@
 use diesel::{ConnectionError, ConnectionResult};
 use diesel_async::pooled_connection::bb8::PooledConnection;
 use diesel_async::pooled_connection::{bb8::Pool, AsyncDieselConnectionManager, ManagerConfig};
-use diesel_async::AsyncPgConnection;
+use diesel_async::{AsyncConnection, AsyncPgConnection, RunQueryDsl};
 
 use crate::infrastructure::config::DatabaseConfig;
 use crate::infrastructure::Settings;
@@ -35,16 +35,15 @@ pub struct DatabaseContext {
 impl DatabaseContext {
     /// Creates a new `DatabaseContext` with the provided configuration
     pub async fn new_with_config(config: &DatabaseConfig) -> AppResult<Self> {
-        let manager = if config.no_ssl {
-            AsyncDieselConnectionManager::<AsyncPgConnection>::new(config.url.clone())
-        } else {
-            let mut mgr_config = ManagerConfig::default();
-            mgr_config.custom_setup = Box::new(establish_connection);
-            AsyncDieselConnectionManager::<AsyncPgConnection>::new_with_config(
-                config.url.clone(),
-                mgr_config,
-            )
-        };
+        let no_ssl = config.no_ssl;
+        let statement_timeout_ms = config.statement_timeout_ms;
+        let mut mgr_config = ManagerConfig::default();
+        mgr_config.custom_setup =
+            Box::new(move |url| establish_connection(url, no_ssl, statement_timeout_ms));
+        let manager = AsyncDieselConnectionManager::<AsyncPgConnection>::new_with_config(
+            config.url.clone(),
+            mgr_config,
+        );
 
         let mut builder = Pool::builder()
             .max_size(config.max_connections)
@@ -89,27 +88,68 @@ impl DatabaseContext {
     pub fn pool(&self) -> &DbPool {
         &self.pool
     }
+
+    /// Opens `count` connections up front so the first requests don't pay the setup cost
+    ///
+    /// All connections are held until the last one is open and then returned to
+    /// the pool; releasing each one straight away would just reuse the first.
+    pub async fn warmup(&self, count: u32) -> AppResult<()> {
+        let mut connections = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            connections.push(self.get_connection().await?);
+        }
+        Ok(())
+    }
+
+    /// Runs a trivial `SELECT 1` to verify the database is reachable
+    pub async fn ping(&self) -> AppResult<()> {
+        let mut conn = self.get_connection().await?;
+        diesel::sql_query("SELECT 1").execute(&mut conn).await?;
+        Ok(())
+    }
 }
 
-fn establish_connection(config: &str) -> BoxFuture<ConnectionResult<AsyncPgConnection>> {
-    let fut = async {
-        // Create a new connection to the database
-        // Setup the TLS configuration for the connection using native certs
-        // Using https://crates.io/crates/rustls-platform-verifier
-        // replaces using rustls-native-certs on its own (recommended)
-        let tls_config = ClientConfig::with_platform_verifier();
-        let tls = tokio_postgres_rustls::MakeRustlsConnect::new(tls_config);
-
-        // get the client and connection future
-        let (client, conn) = tokio_postgres::connect(config, tls)
-            .await
-            .map_err(|e| ConnectionError::BadConnection(e.to_string()))?;
+fn establish_connection(
+    config: &str,
+    no_ssl: bool,
+    statement_timeout_ms: i64,
+) -> BoxFuture<ConnectionResult<AsyncPgConnection>> {
+    let config = config.to_string();
+    let fut = async move {
+        let mut conn = if no_ssl {
+            AsyncPgConnection::establish(&config).await?
+        } else {
+            establish_tls_connection(&config).await?
+        };
 
-        AsyncPgConnection::try_from_client_and_connection(client, conn).await
+        // Bound every query so a struggling server cannot hold a pooled connection forever
+        if statement_timeout_ms > 0 {
+            diesel::sql_query(format!("SET statement_timeout = {}", statement_timeout_ms))
+                .execute(&mut conn)
+                .await
+                .map_err(ConnectionError::CouldntSetupConfiguration)?;
+        }
+        Ok(conn)
     };
     fut.boxed()
 }
 
+async fn establish_tls_connection(config: &str) -> ConnectionResult<AsyncPgConnection> {
+    // Create a new connection to the database
+    // Setup the TLS configuration for the connection using native certs
+    // Using https://crates.io/crates/rustls-platform-verifier
+    // replaces using rustls-native-certs on its own (recommended)
+    let tls_config = ClientConfig::with_platform_verifier();
+    let tls = tokio_postgres_rustls::MakeRustlsConnect::new(tls_config);
+
+    // get the client and connection future
+    let (client, conn) = tokio_postgres::connect(config, tls)
+        .await
+        .map_err(|e| ConnectionError::BadConnection(e.to_string()))?;
+
+    AsyncPgConnection::try_from_client_and_connection(client, conn).await
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -162,6 +202,20 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_warmup_opens_min_connections() {
+        let config = setup().await;
+        let db_context = DatabaseContext::new_with_config(&config.database)
+            .await
+            .expect("Failed to create DatabaseContext");
+
+        db_context
+            .warmup(config.database.min_connections)
+            .await
+            .expect("Warmup failed");
+        assert!(db_context.pool().state().connections >= config.database.min_connections);
+    }
+
     #[tokio::test]
     async fn test_connection_error_handling() {
         let mut config = setup().await;
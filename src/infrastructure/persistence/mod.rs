@@ -1,3 +1,4 @@
 pub mod database;
 pub mod models;
 pub mod repositories;
+pub mod retry;
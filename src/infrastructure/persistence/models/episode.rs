@@ -26,6 +26,11 @@ pub struct Episode {
     pub keywords: Option<Vec<Option<String>>>,
     pub category: Option<Vec<Option<String>>>,
     pub duration: Option<String>,
+    pub transcript_url: Option<String>,
+    pub transcript_type: Option<String>,
+    pub feed_position: Option<i32>,
+    pub extra: Option<serde_json::Value>,
+    pub blocked: Option<bool>,
 }
 
 #[derive(Insertable, Serialize, Deserialize, AsChangeset, Debug, Default, Clone)]
@@ -48,6 +53,11 @@ pub struct NewEpisode {
     pub keywords: Option<Vec<Option<String>>>,
     pub category: Option<Vec<Option<String>>>,
     pub duration: Option<String>,
+    pub transcript_url: Option<String>,
+    pub transcript_type: Option<String>,
+    pub feed_position: Option<i32>,
+    pub extra: Option<serde_json::Value>,
+    pub blocked: Option<bool>,
 }
 
 #[derive(AsChangeset, Serialize, Deserialize, Debug)]
@@ -70,6 +80,11 @@ pub struct UpdateEpisode {
     pub keywords: Option<Vec<Option<String>>>,
     pub category: Option<Vec<Option<String>>>,
     pub duration: Option<String>,
+    pub transcript_url: Option<String>,
+    pub transcript_type: Option<String>,
+    pub feed_position: Option<i32>,
+    pub extra: Option<serde_json::Value>,
+    pub blocked: Option<bool>,
 }
 
 impl From<&NewEpisode> for UpdateEpisode {
@@ -92,6 +107,11 @@ impl From<&NewEpisode> for UpdateEpisode {
             keywords: episode.keywords.clone(),
             category: episode.category.clone(),
             duration: episode.duration.clone(),
+            transcript_url: episode.transcript_url.clone(),
+            transcript_type: episode.transcript_type.clone(),
+            feed_position: episode.feed_position,
+            extra: episode.extra.clone(),
+            blocked: episode.blocked,
         }
     }
 }
@@ -0,0 +1,25 @@
+use crate::schema::failed_feeds;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A feed whose crawl task failed after using up its retries
+#[derive(Queryable, Selectable, Debug, Clone, Serialize, Deserialize)]
+#[diesel(table_name = failed_feeds)]
+pub struct FailedFeed {
+    pub id: i32,
+    pub url: String,
+    pub last_error: Option<String>,
+    /// Fetch attempts made by the last failed task, including the first one
+    pub attempts: i32,
+    pub failed_at: DateTime<Utc>,
+}
+
+#[derive(Insertable, AsChangeset, Debug, Clone, Serialize, Deserialize)]
+#[diesel(table_name = failed_feeds)]
+pub struct NewFailedFeed {
+    pub url: String,
+    pub last_error: Option<String>,
+    pub attempts: i32,
+    pub failed_at: DateTime<Utc>,
+}
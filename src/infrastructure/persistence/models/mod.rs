@@ -1,7 +1,11 @@
 pub mod episode;
+pub mod failed_feed;
 pub mod podcast;
 pub mod podcast_rank_model;
 
 pub use episode::{Episode, NewEpisode, UpdateEpisode};
-pub use podcast::{NewPodcast, Podcast, UpdatePodcast};
+pub use failed_feed::{FailedFeed, NewFailedFeed};
+pub use podcast::{
+    CategoryCount, LanguageCount, NewPodcast, Podcast, PodcastWithStats, UpdatePodcast,
+};
 pub use podcast_rank_model::{NewPodcastRank, PodcastRank, UpdatePodcastRank};
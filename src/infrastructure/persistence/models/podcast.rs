@@ -23,6 +23,42 @@ pub struct Podcast {
     pub explicit: Option<bool>,
     pub summary: Option<String>,
     pub subtitle: Option<String>,
+    pub content_hash: Option<String>,
+    pub funding_url: Option<String>,
+    pub funding_text: Option<String>,
+    pub persons: Option<serde_json::Value>,
+    pub extra: Option<serde_json::Value>,
+    /// When the crawler last fetched and stored this feed
+    pub last_fetched_at: Option<DateTime<Utc>>,
+    /// `<itunes:block>`: hidden from public listings
+    pub blocked: Option<bool>,
+}
+
+/// Podcast with aggregated episode statistics for list views
+#[derive(Debug, Clone, Serialize)]
+pub struct PodcastWithStats {
+    #[serde(flatten)]
+    pub podcast: Podcast,
+    pub episode_count: i64,
+    pub latest_episode_date: Option<DateTime<Utc>>,
+}
+
+/// Number of podcasts listing a category, for faceted browsing
+#[derive(QueryableByName, Debug, Clone, Serialize)]
+pub struct CategoryCount {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub category: String,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub podcast_count: i64,
+}
+
+/// Number of podcasts in a language, for browsing by language
+#[derive(QueryableByName, Debug, Clone, Serialize)]
+pub struct LanguageCount {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub language: String,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub podcast_count: i64,
 }
 
 #[derive(Insertable, Debug, Default, Clone, Serialize, Deserialize, AsChangeset)]
@@ -44,6 +80,12 @@ pub struct NewPodcast {
     pub explicit: Option<bool>,
     pub summary: Option<String>,
     pub subtitle: Option<String>,
+    pub content_hash: Option<String>,
+    pub funding_url: Option<String>,
+    pub funding_text: Option<String>,
+    pub persons: Option<serde_json::Value>,
+    pub extra: Option<serde_json::Value>,
+    pub blocked: Option<bool>,
 }
 
 #[derive(AsChangeset, Debug, Clone, Serialize, Deserialize)]
@@ -65,6 +107,12 @@ pub struct UpdatePodcast {
     pub explicit: Option<bool>,
     pub summary: Option<String>,
     pub subtitle: Option<String>,
+    pub content_hash: Option<String>,
+    pub funding_url: Option<String>,
+    pub funding_text: Option<String>,
+    pub persons: Option<serde_json::Value>,
+    pub extra: Option<serde_json::Value>,
+    pub blocked: Option<bool>,
 }
 
 impl From<&NewPodcast> for UpdatePodcast {
@@ -86,6 +134,12 @@ impl From<&NewPodcast> for UpdatePodcast {
             explicit: podcast.explicit,
             summary: podcast.summary.clone(),
             subtitle: podcast.subtitle.clone(),
+            content_hash: podcast.content_hash.clone(),
+            funding_url: podcast.funding_url.clone(),
+            funding_text: podcast.funding_text.clone(),
+            persons: podcast.persons.clone(),
+            extra: podcast.extra.clone(),
+            blocked: podcast.blocked,
         }
     }
 }
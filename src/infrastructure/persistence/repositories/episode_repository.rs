@@ -1,20 +1,39 @@
 use crate::infrastructure::error::AppResult;
 use crate::infrastructure::persistence::database::DatabaseContext;
 use crate::infrastructure::persistence::models::episode::{Episode, NewEpisode, UpdateEpisode};
+use crate::infrastructure::persistence::repositories::PageLimits;
+use chrono::{DateTime, Utc};
+use diesel::dsl::{exists, not, sql};
 use diesel::prelude::*;
+use diesel::sql_types::{Bool, Float, Text};
 use diesel_async::RunQueryDsl;
 use std::sync::Arc;
 
-use crate::schema::episodes;
+use crate::schema::{episodes, podcasts};
+
+// 全文搜索的文档，与迁移 add_episode_search_index 中 GIN 索引的表达式保持一致
+const SEARCH_DOCUMENT: &str = "(setweight(to_tsvector('simple', coalesce(title, '')), 'A') || \
+     setweight(to_tsvector('simple', coalesce(description, '')), 'B') || \
+     setweight(to_tsvector('simple', coalesce(summary, '')), 'C'))";
 
 #[derive(Debug)]
 pub struct EpisodeRepository {
     base: Arc<DatabaseContext>,
+    page_limits: PageLimits,
 }
 
 impl EpisodeRepository {
     pub fn new(pool: Arc<DatabaseContext>) -> Self {
-        Self { base: pool }
+        Self {
+            base: pool,
+            page_limits: PageLimits::default(),
+        }
+    }
+
+    // 设置分页方法的默认与最大每页数量
+    pub fn with_page_limits(mut self, page_limits: PageLimits) -> Self {
+        self.page_limits = page_limits;
+        self
     }
 
     pub async fn get_by_id(&self, id: i32) -> AppResult<Option<Episode>> {
@@ -27,6 +46,13 @@ impl EpisodeRepository {
         Ok(result)
     }
 
+    // 统计 Episode 总数，不加载记录
+    pub async fn count(&self) -> AppResult<i64> {
+        let mut conn = self.base.get_connection().await?;
+        let total = episodes::table.count().get_result(&mut conn).await?;
+        Ok(total)
+    }
+
     // 获取所有的 Episode 记录
     pub async fn get_all(&self) -> AppResult<Vec<Episode>> {
         let mut conn = self.base.get_connection().await?; // 获取数据库连接
@@ -34,6 +60,123 @@ impl EpisodeRepository {
         Ok(results)
     }
 
+    // 跨播客分页获取最新发布的 Episode（pub_date 为空的排在最后），并返回总数
+    pub async fn get_recent(
+        &self,
+        page: i64,
+        per_page: i64,
+        exclude_explicit: bool,
+        include_blocked: bool,
+    ) -> AppResult<(Vec<Episode>, i64)> {
+        let mut conn = self.base.get_connection().await?; // 获取数据库连接
+
+        // exclude_explicit 时排除 explicit 为 true 的剧集，NULL 视为非 explicit
+        let filtered = || {
+            let mut query = episodes::table.into_boxed();
+            if exclude_explicit {
+                query = query.filter(episodes::explicit.is_distinct_from(true));
+            }
+            // 默认排除标记了 <itunes:block> 的剧集，以及所属播客被 block 的剧集
+            if !include_blocked {
+                query = query
+                    .filter(episodes::blocked.is_distinct_from(true))
+                    .filter(not(exists(
+                        podcasts::table
+                            .filter(podcasts::podcast_id.nullable().eq(episodes::podcast_id))
+                            .filter(podcasts::blocked.eq(true)),
+                    )));
+            }
+            query
+        };
+
+        let total: i64 = filtered().count().get_result(&mut conn).await?;
+
+        let (offset, per_page) = self.page_limits.offset_limit(page, per_page);
+        let results = filtered()
+            .order((
+                episodes::pub_date.desc().nulls_last(),
+                episodes::episode_id.desc(),
+            ))
+            .limit(per_page)
+            .offset(offset)
+            .load::<Episode>(&mut conn)
+            .await?;
+
+        Ok((results, total))
+    }
+
+    // 分页获取 [start, end) 时间范围内发布的 Episode（按发布时间升序，排除 pub_date 为空的），并返回总数
+    pub async fn get_by_pub_date_range(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        page: i64,
+        per_page: i64,
+    ) -> AppResult<(Vec<Episode>, i64)> {
+        let mut conn = self.base.get_connection().await?; // 获取数据库连接
+
+        let in_range = episodes::pub_date.ge(start).and(episodes::pub_date.lt(end));
+        let total: i64 = episodes::table
+            .filter(in_range)
+            .count()
+            .get_result(&mut conn)
+            .await?;
+
+        let (offset, per_page) = self.page_limits.offset_limit(page, per_page);
+        let results = episodes::table
+            .filter(in_range)
+            .order((episodes::pub_date.asc(), episodes::episode_id.asc()))
+            .limit(per_page)
+            .offset(offset)
+            .load::<Episode>(&mut conn)
+            .await?;
+
+        Ok((results, total))
+    }
+
+    // 全文搜索标题、描述和摘要，按相关度排序分页返回，并返回总数
+    pub async fn search(
+        &self,
+        query: &str,
+        page: i64,
+        per_page: i64,
+    ) -> AppResult<(Vec<Episode>, i64)> {
+        let query = query.trim();
+        if query.is_empty() {
+            return Ok((Vec::new(), 0));
+        }
+        let mut conn = self.base.get_connection().await?; // 获取数据库连接
+
+        // 使用与索引相同的表达式和 simple 配置，才能命中 GIN 索引并得到一致的分词
+        let matches = || {
+            sql::<Bool>(SEARCH_DOCUMENT)
+                .sql(" @@ plainto_tsquery('simple', ")
+                .bind::<Text, _>(query)
+                .sql(")")
+        };
+        let total: i64 = episodes::table
+            .filter(matches())
+            .count()
+            .get_result(&mut conn)
+            .await?;
+
+        let (offset, per_page) = self.page_limits.offset_limit(page, per_page);
+        let rank = sql::<Float>("ts_rank(")
+            .sql(SEARCH_DOCUMENT)
+            .sql(", plainto_tsquery('simple', ")
+            .bind::<Text, _>(query)
+            .sql("))");
+        let results = episodes::table
+            .filter(matches())
+            .order((rank.desc(), episodes::episode_id.desc()))
+            .limit(per_page)
+            .offset(offset)
+            .load::<Episode>(&mut conn)
+            .await?;
+
+        Ok((results, total))
+    }
+
     // 插入新的 Episode 记录
     pub async fn insert(&self, new_episode: &NewEpisode) -> AppResult<()> {
         let mut conn = self.base.get_connection().await?; // 获取数据库连接
@@ -73,3 +216,110 @@ impl EpisodeRepository {
         Ok(rows_affected > 0) // 返回是否成功删除
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::persistence::models::podcast::NewPodcast;
+
+    #[tokio::test]
+    async fn test_search_matches_title_description_and_summary() -> AppResult<()> {
+        let state = crate::infrastructure::initialize().await?;
+        let podcasts = &state.repositories.podcast;
+        let repo = &state.repositories.episode;
+        // 用唯一的词避免与库中已有数据混淆
+        let suffix = Utc::now().timestamp_nanos_opt().unwrap();
+        let word = format!("needle{}", suffix);
+        let episode = |n: usize, title: String, description: Option<String>, summary| NewEpisode {
+            guid: Some(format!("search-{}-{}", suffix, n)),
+            title,
+            description,
+            summary,
+            ..NewEpisode::default()
+        };
+        // 大量不匹配的剧集，确认只返回命中的行
+        let mut episodes: Vec<NewEpisode> = (0..200)
+            .map(|n| {
+                episode(
+                    n,
+                    format!("Filler {}", n),
+                    Some("haystack".to_string()),
+                    None,
+                )
+            })
+            .collect();
+        episodes.push(episode(200, format!("About {}", word), None, None));
+        episodes.push(episode(
+            201,
+            "Description".to_string(),
+            Some(word.clone()),
+            None,
+        ));
+        episodes.push(episode(
+            202,
+            "Summary".to_string(),
+            None,
+            Some(word.clone()),
+        ));
+        let podcast = NewPodcast {
+            title: format!("search {}", suffix),
+            rss_feed_url: Some(format!("https://example.com/search-{}.xml", suffix)),
+            ..NewPodcast::default()
+        };
+        podcasts.insert_with_episodes(&podcast, &episodes).await?;
+
+        // 标题权重最高，排在最前
+        let (found, total) = repo.search(&word.to_uppercase(), 1, 10).await?;
+        let titles: Vec<&str> = found.iter().map(|e| e.title.as_str()).collect();
+        assert_eq!(total, 3);
+        assert_eq!(titles[0], format!("About {}", word));
+        assert_eq!(titles[1..], ["Description", "Summary"]);
+
+        // 分页与总数
+        let (found, total) = repo.search(&word, 2, 2).await?;
+        assert_eq!((found.len(), total), (1, 3));
+
+        // 空查询不访问数据库
+        assert_eq!(repo.search("  ", 1, 10).await?.1, 0);
+
+        let id = podcasts
+            .get_by_title(&podcast.title)
+            .await?
+            .unwrap()
+            .podcast_id;
+        podcasts.delete_by_id(id).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_count_includes_inserted_episodes() -> AppResult<()> {
+        let state = crate::infrastructure::initialize().await?;
+        let podcasts = &state.repositories.podcast;
+        let repo = &state.repositories.episode;
+        let suffix = Utc::now().timestamp_nanos_opt().unwrap();
+        let episodes: Vec<NewEpisode> = (0..3)
+            .map(|n| NewEpisode {
+                guid: Some(format!("count-{}-{}", suffix, n)),
+                title: format!("Count {}", n),
+                ..NewEpisode::default()
+            })
+            .collect();
+        let podcast = NewPodcast {
+            title: format!("count {}", suffix),
+            rss_feed_url: Some(format!("https://example.com/count-{}.xml", suffix)),
+            ..NewPodcast::default()
+        };
+        podcasts.insert_with_episodes(&podcast, &episodes).await?;
+
+        // 其他测试可能并发写库，只检查下限
+        assert!(repo.count().await? >= 3);
+
+        let id = podcasts
+            .get_by_title(&podcast.title)
+            .await?
+            .unwrap()
+            .podcast_id;
+        podcasts.delete_by_id(id).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,96 @@
+use crate::infrastructure::error::AppResult;
+use crate::infrastructure::persistence::database::DatabaseContext;
+use crate::infrastructure::persistence::models::failed_feed::{FailedFeed, NewFailedFeed};
+use crate::infrastructure::persistence::repositories::PageLimits;
+use crate::schema::failed_feeds;
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use std::sync::Arc;
+
+/// Dead-letter store for feeds that failed every retry
+#[derive(Debug)]
+pub struct FailedFeedRepository {
+    base: Arc<DatabaseContext>,
+    page_limits: PageLimits,
+}
+
+impl FailedFeedRepository {
+    pub fn new(base: Arc<DatabaseContext>) -> Self {
+        Self {
+            base,
+            page_limits: PageLimits::default(),
+        }
+    }
+
+    /// Sets the default and maximum page size of [`list`](Self::list)
+    pub fn with_page_limits(mut self, page_limits: PageLimits) -> Self {
+        self.page_limits = page_limits;
+        self
+    }
+
+    /// Records a failed feed, replacing the previous failure of the same URL
+    pub async fn record(&self, failed_feed: &NewFailedFeed) -> AppResult<()> {
+        let mut conn = self.base.get_connection().await?;
+        diesel::insert_into(failed_feeds::table)
+            .values(failed_feed)
+            .on_conflict(failed_feeds::url)
+            .do_update()
+            .set(failed_feed)
+            .execute(&mut conn)
+            .await?;
+        Ok(())
+    }
+
+    /// Pages through failed feeds, most recent failure first, with the total count
+    pub async fn list(&self, page: i64, per_page: i64) -> AppResult<(Vec<FailedFeed>, i64)> {
+        let mut conn = self.base.get_connection().await?;
+        let total: i64 = failed_feeds::table.count().get_result(&mut conn).await?;
+
+        let (offset, per_page) = self.page_limits.offset_limit(page, per_page);
+        let results = failed_feeds::table
+            .order((failed_feeds::failed_at.desc(), failed_feeds::id.desc()))
+            .limit(per_page)
+            .offset(offset)
+            .load::<FailedFeed>(&mut conn)
+            .await?;
+        Ok((results, total))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    #[tokio::test]
+    async fn test_record_replaces_previous_failure() -> AppResult<()> {
+        let state = crate::infrastructure::initialize().await?;
+        let repo = &state.repositories.failed_feed;
+        let url = format!(
+            "https://example.com/broken-{}.xml",
+            Utc::now().timestamp_nanos_opt().unwrap()
+        );
+        let failure = |error: &str, attempts: i32| NewFailedFeed {
+            url: url.clone(),
+            last_error: Some(error.to_string()),
+            attempts,
+            failed_at: Utc::now(),
+        };
+
+        repo.record(&failure("timeout", 3)).await?;
+        repo.record(&failure("connection refused", 4)).await?;
+
+        // The newest failure is listed first
+        let (failed, total) = repo.list(1, 1).await?;
+        assert!(total >= 1);
+        assert_eq!(failed[0].url, url);
+        assert_eq!(failed[0].last_error.as_deref(), Some("connection refused"));
+        assert_eq!(failed[0].attempts, 4);
+
+        let mut conn = state.database_context.get_connection().await?;
+        diesel::delete(failed_feeds::table.filter(failed_feeds::url.eq(&url)))
+            .execute(&mut conn)
+            .await?;
+        Ok(())
+    }
+}
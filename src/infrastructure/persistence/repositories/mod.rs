@@ -1,7 +1,11 @@
 mod episode_repository;
+mod failed_feed_repository;
+mod pagination;
 mod podcast_rank_repository;
 mod podcast_repository;
 
 pub use episode_repository::EpisodeRepository;
+pub use failed_feed_repository::FailedFeedRepository;
+pub use pagination::PageLimits;
 pub use podcast_rank_repository::PodcastRankRepository;
-pub use podcast_repository::PodcastRepository;
+pub use podcast_repository::{ConflictStrategy, PodcastConflictTarget, PodcastRepository};
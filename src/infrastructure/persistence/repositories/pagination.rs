@@ -0,0 +1,82 @@
+//! Page size limits for the paginated repository queries
+//!
+//! Page numbers and sizes come straight from HTTP requests, so every
+//! paginated query clamps them: a client asking for `per_page=1000000`
+//! gets `max_per_page` rows instead of loading the whole table.
+
+/// Default and maximum page sizes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageLimits {
+    pub default_per_page: i64,
+    pub max_per_page: i64,
+}
+
+impl Default for PageLimits {
+    fn default() -> Self {
+        Self {
+            default_per_page: 10,
+            max_per_page: 200,
+        }
+    }
+}
+
+impl PageLimits {
+    pub fn new(default_per_page: i64, max_per_page: i64) -> Self {
+        Self {
+            default_per_page,
+            max_per_page,
+        }
+    }
+
+    /// Returns `(page, per_page)` within range
+    ///
+    /// Pages start at 1, a `per_page` below 1 uses the default and one above
+    /// the maximum is lowered to it.
+    pub fn clamp(&self, page: i64, per_page: i64) -> (i64, i64) {
+        let per_page = if per_page < 1 {
+            self.default_per_page
+        } else {
+            per_page.min(self.max_per_page)
+        };
+        (page.max(1), per_page)
+    }
+
+    /// Returns the `(OFFSET, LIMIT)` of a clamped page
+    ///
+    /// The offset saturates at `i64::MAX`, so a huge page number yields an
+    /// empty page instead of overflowing.
+    pub fn offset_limit(&self, page: i64, per_page: i64) -> (i64, i64) {
+        let (page, per_page) = self.clamp(page, per_page);
+        ((page - 1).saturating_mul(per_page), per_page)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_per_page_is_clamped_to_max() {
+        let limits = PageLimits::default();
+        assert_eq!(limits.clamp(1, 99999), (1, 200));
+        assert_eq!(limits.offset_limit(3, 99999), (400, 200));
+    }
+
+    #[test]
+    fn test_out_of_range_values_use_defaults() {
+        let limits = PageLimits::new(20, 100);
+        assert_eq!(limits.clamp(0, 0), (1, 20));
+        assert_eq!(limits.clamp(-5, -1), (1, 20));
+        assert_eq!(limits.clamp(2, 50), (2, 50));
+    }
+
+    #[test]
+    fn test_huge_page_saturates_offset() {
+        let limits = PageLimits::default();
+        assert_eq!(limits.offset_limit(i64::MAX, 10), (i64::MAX, 10));
+        assert_eq!(
+            PageLimits::new(10, i64::MAX).offset_limit(i64::MAX, i64::MAX),
+            (i64::MAX, i64::MAX)
+        );
+    }
+}
@@ -1,24 +1,104 @@
 use crate::infrastructure::error::{AppError, AppResult};
 use crate::infrastructure::persistence::database::DatabaseContext;
 use crate::infrastructure::persistence::models::episode::NewEpisode;
-use crate::infrastructure::persistence::models::podcast::{NewPodcast, Podcast, UpdatePodcast};
+use crate::infrastructure::persistence::models::podcast::{
+    CategoryCount, LanguageCount, NewPodcast, Podcast, PodcastWithStats, UpdatePodcast,
+};
 use crate::infrastructure::persistence::models::Episode;
 use crate::infrastructure::persistence::models::UpdateEpisode;
+use crate::infrastructure::persistence::repositories::PageLimits;
+use crate::infrastructure::persistence::retry::with_db_retry;
 use crate::schema::{episodes, podcasts};
+use chrono::{DateTime, Duration, Utc};
 use diesel::prelude::*;
+use diesel::sql_types::Bool;
 use diesel::upsert::*;
 use diesel_async::scoped_futures::ScopedFutureExt;
-use diesel_async::{AsyncConnection, RunQueryDsl};
+use diesel_async::{AsyncConnection, AsyncPgConnection, RunQueryDsl};
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
+diesel::define_sql_function! {
+    /// SQL `lower()`, used to compare language codes case-insensitively
+    fn lower(x: diesel::sql_types::Nullable<diesel::sql_types::Text>) -> diesel::sql_types::Nullable<diesel::sql_types::Text>;
+}
+
+diesel::define_sql_function! {
+    /// SQL `trim()`, used to ignore whitespace around stored language codes
+    fn trim(x: diesel::sql_types::Nullable<diesel::sql_types::Text>) -> diesel::sql_types::Nullable<diesel::sql_types::Text>;
+}
+
+/// Column used to match an incoming podcast against an existing row on upsert
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PodcastConflictTarget {
+    /// One row per feed URL (default)
+    #[default]
+    RssFeedUrl,
+    /// One row per title; podcasts sharing a title are merged
+    Title,
+}
+
+/// What the upsert methods do with a podcast or episode that is already stored
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConflictStrategy {
+    /// Replace the stored row with the incoming data (default)
+    #[default]
+    Overwrite,
+    /// Insert new rows only and leave stored rows untouched
+    SkipExisting,
+    /// Replace the stored row only when the incoming one is newer: a later
+    /// `last_build_date` for podcasts, a later `pub_date` for episodes
+    UpdateIfNewer,
+}
+
 #[derive(Debug)]
 pub struct PodcastRepository {
     base: Arc<DatabaseContext>,
+    conflict_target: PodcastConflictTarget,
+    conflict_strategy: ConflictStrategy,
+    page_limits: PageLimits,
 }
 
 impl PodcastRepository {
     pub fn new(pool: Arc<DatabaseContext>) -> Self {
-        Self { base: pool }
+        Self {
+            base: pool,
+            conflict_target: PodcastConflictTarget::default(),
+            conflict_strategy: ConflictStrategy::default(),
+            page_limits: PageLimits::default(),
+        }
+    }
+
+    /// Sets the default and maximum page size of the paginated methods
+    pub fn with_page_limits(mut self, page_limits: PageLimits) -> Self {
+        self.page_limits = page_limits;
+        self
+    }
+
+    pub fn page_limits(&self) -> PageLimits {
+        self.page_limits
+    }
+
+    /// Sets the column used to detect an existing podcast in the upsert methods
+    pub fn with_conflict_target(mut self, conflict_target: PodcastConflictTarget) -> Self {
+        self.conflict_target = conflict_target;
+        self
+    }
+
+    /// Sets how the upsert methods treat podcasts and episodes that are already stored
+    ///
+    /// `insert_new_episodes` never touches stored episodes; the strategy only
+    /// applies to its podcast row.
+    pub fn with_conflict_strategy(mut self, conflict_strategy: ConflictStrategy) -> Self {
+        self.conflict_strategy = conflict_strategy;
+        self
+    }
+
+    /// Returns the number of stored podcasts
+    pub async fn count(&self) -> AppResult<i64> {
+        let mut conn = self.base.get_connection().await?;
+        let total = podcasts::table.count().get_result(&mut conn).await?;
+        Ok(total)
     }
 
     pub async fn get_by_id(&self, id: i32) -> AppResult<Option<Podcast>> {
@@ -31,6 +111,30 @@ impl PodcastRepository {
         Ok(result)
     }
 
+    /// Loads the podcasts with the given ids in one query, in the order of `ids`
+    ///
+    /// Unknown ids are skipped and repeated ids are returned once. Podcasts
+    /// marked `<itunes:block>` are skipped unless `include_blocked`.
+    pub async fn get_by_ids(&self, ids: &[i32], include_blocked: bool) -> AppResult<Vec<Podcast>> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut conn = self.base.get_connection().await?;
+        let mut query = podcasts::table
+            .filter(podcasts::podcast_id.eq_any(ids))
+            .into_boxed();
+        if !include_blocked {
+            query = query.filter(podcasts::blocked.is_distinct_from(true));
+        }
+        let mut found: HashMap<i32, Podcast> = query
+            .load::<Podcast>(&mut conn)
+            .await?
+            .into_iter()
+            .map(|podcast| (podcast.podcast_id, podcast))
+            .collect();
+        Ok(ids.iter().filter_map(|id| found.remove(id)).collect())
+    }
+
     pub async fn get_by_title(&self, title: &str) -> AppResult<Option<Podcast>> {
         let mut conn = self.base.get_connection().await?;
         let result = podcasts::table
@@ -41,24 +145,55 @@ impl PodcastRepository {
         Ok(result)
     }
 
-    pub async fn search_by_title(&self, query: &str) -> AppResult<Vec<Podcast>> {
+    /// Podcasts whose title contains `query`, ignoring case
+    ///
+    /// Podcasts marked `<itunes:block>` are left out unless `include_blocked`.
+    pub async fn search_by_title(
+        &self,
+        query: &str,
+        include_blocked: bool,
+    ) -> AppResult<Vec<Podcast>> {
         let mut conn = self.base.get_connection().await?;
-        let result = podcasts::table
+        let mut filtered = podcasts::table
             .filter(podcasts::title.ilike(format!("%{}%", query)))
-            .load::<Podcast>(&mut conn)
-            .await?;
+            .into_boxed();
+        if !include_blocked {
+            filtered = filtered.filter(podcasts::blocked.is_distinct_from(true));
+        }
+        let result = filtered.load::<Podcast>(&mut conn).await?;
         Ok(result)
     }
 
-    pub async fn get_all(&self, page: i64, per_page: i64) -> AppResult<(Vec<Podcast>, i64)> {
+    /// Pages through podcasts, optionally leaving out explicit ones
+    ///
+    /// With `exclude_explicit`, podcasts whose `explicit` is NULL are kept.
+    /// Podcasts marked `<itunes:block>` are left out unless `include_blocked`.
+    /// `page` and `per_page` are clamped to the repository's [`PageLimits`].
+    pub async fn get_all(
+        &self,
+        page: i64,
+        per_page: i64,
+        exclude_explicit: bool,
+        include_blocked: bool,
+    ) -> AppResult<(Vec<Podcast>, i64)> {
         let mut conn = self.base.get_connection().await?;
+        let filtered = || {
+            let mut query = podcasts::table.into_boxed();
+            if exclude_explicit {
+                query = query.filter(podcasts::explicit.is_distinct_from(true));
+            }
+            if !include_blocked {
+                query = query.filter(podcasts::blocked.is_distinct_from(true));
+            }
+            query
+        };
 
         // Get total count
-        let total: i64 = podcasts::table.count().get_result(&mut conn).await?;
+        let total: i64 = filtered().count().get_result(&mut conn).await?;
 
         // Get paginated results
-        let offset = (page - 1) * per_page;
-        let podcasts = podcasts::table
+        let (offset, per_page) = self.page_limits.offset_limit(page, per_page);
+        let podcasts = filtered()
             .limit(per_page)
             .offset(offset)
             .load::<Podcast>(&mut conn)
@@ -67,6 +202,340 @@ impl PodcastRepository {
         Ok((podcasts, total))
     }
 
+    /// Keyset pagination over podcasts ordered by `podcast_id`
+    ///
+    /// Returns up to `limit` podcasts with an id greater than `cursor_id` (from
+    /// the start when `None`) and the last id as the cursor for the next page,
+    /// or `None` once a short page shows the end was reached. Unlike the
+    /// OFFSET-based [`get_all`](Self::get_all), rows inserted while a client is
+    /// scrolling never shift the following pages. Podcasts marked
+    /// `<itunes:block>` are left out unless `include_blocked`.
+    pub async fn get_all_after(
+        &self,
+        cursor_id: Option<i32>,
+        limit: i64,
+        include_blocked: bool,
+    ) -> AppResult<(Vec<Podcast>, Option<i32>)> {
+        let (_, limit) = self.page_limits.clamp(1, limit);
+        let mut conn = self.base.get_connection().await?;
+        let mut query = podcasts::table.into_boxed();
+        if let Some(cursor_id) = cursor_id {
+            query = query.filter(podcasts::podcast_id.gt(cursor_id));
+        }
+        if !include_blocked {
+            query = query.filter(podcasts::blocked.is_distinct_from(true));
+        }
+        let podcasts = query
+            .order(podcasts::podcast_id.asc())
+            .limit(limit)
+            .load::<Podcast>(&mut conn)
+            .await?;
+
+        let next_cursor = if podcasts.len() as i64 == limit {
+            podcasts.last().map(|podcast| podcast.podcast_id)
+        } else {
+            None
+        };
+        Ok((podcasts, next_cursor))
+    }
+
+    /// Pages through podcasts whose `category` array contains `category` exactly
+    ///
+    /// Podcasts marked `<itunes:block>` are left out unless `include_blocked`.
+    pub async fn get_by_category(
+        &self,
+        category: &str,
+        page: i64,
+        per_page: i64,
+        include_blocked: bool,
+    ) -> AppResult<(Vec<Podcast>, i64)> {
+        let mut conn = self.base.get_connection().await?;
+        let filtered = || {
+            let mut query = podcasts::table
+                .filter(podcasts::category.contains(vec![Some(category.to_string())]))
+                .into_boxed();
+            if !include_blocked {
+                query = query.filter(podcasts::blocked.is_distinct_from(true));
+            }
+            query
+        };
+
+        let total: i64 = filtered().count().get_result(&mut conn).await?;
+
+        let (offset, per_page) = self.page_limits.offset_limit(page, per_page);
+        let podcasts = filtered()
+            .order(podcasts::podcast_id.asc())
+            .limit(per_page)
+            .offset(offset)
+            .load::<Podcast>(&mut conn)
+            .await?;
+
+        Ok((podcasts, total))
+    }
+
+    /// Pages through podcasts whose `language` matches `language`
+    ///
+    /// Comparison ignores case and surrounding whitespace, so `zh-cn` also
+    /// matches podcasts stored as `zh-CN`. Podcasts marked `<itunes:block>`
+    /// are left out unless `include_blocked`.
+    pub async fn get_by_language(
+        &self,
+        language: &str,
+        page: i64,
+        per_page: i64,
+        include_blocked: bool,
+    ) -> AppResult<(Vec<Podcast>, i64)> {
+        let mut conn = self.base.get_connection().await?;
+        let language = language.trim().to_lowercase();
+        let filtered = || {
+            let mut query = podcasts::table
+                .filter(lower(trim(podcasts::language)).eq(language.clone()))
+                .into_boxed();
+            if !include_blocked {
+                query = query.filter(podcasts::blocked.is_distinct_from(true));
+            }
+            query
+        };
+
+        let total: i64 = filtered().count().get_result(&mut conn).await?;
+
+        let (offset, per_page) = self.page_limits.offset_limit(page, per_page);
+        let podcasts = filtered()
+            .order(podcasts::podcast_id.asc())
+            .limit(per_page)
+            .offset(offset)
+            .load::<Podcast>(&mut conn)
+            .await?;
+
+        Ok((podcasts, total))
+    }
+
+    /// Pages through podcasts that have no episodes stored, ordered by `podcast_id`
+    ///
+    /// Feeds that parse into a podcast without items are usually empty or hit
+    /// a parser bug, so this is meant for quality monitoring. Podcasts marked
+    /// `<itunes:block>` are left out unless `include_blocked`.
+    pub async fn get_without_episodes(
+        &self,
+        page: i64,
+        per_page: i64,
+        include_blocked: bool,
+    ) -> AppResult<(Vec<Podcast>, i64)> {
+        let mut conn = self.base.get_connection().await?;
+        let without_episodes = || {
+            let mut query = podcasts::table
+                .filter(diesel::dsl::not(diesel::dsl::exists(
+                    episodes::table
+                        .filter(episodes::podcast_id.eq(podcasts::podcast_id.nullable())),
+                )))
+                .into_boxed();
+            if !include_blocked {
+                query = query.filter(podcasts::blocked.is_distinct_from(true));
+            }
+            query
+        };
+
+        let total: i64 = without_episodes().count().get_result(&mut conn).await?;
+
+        let (offset, per_page) = self.page_limits.offset_limit(page, per_page);
+        let podcasts = without_episodes()
+            .order(podcasts::podcast_id.asc())
+            .limit(per_page)
+            .offset(offset)
+            .load::<Podcast>(&mut conn)
+            .await?;
+
+        Ok((podcasts, total))
+    }
+
+    /// Pages through podcasts with their episode count and latest `pub_date`
+    ///
+    /// Statistics come from a single LEFT JOIN + GROUP BY query, so podcasts
+    /// without episodes are included with a count of 0. Podcasts marked
+    /// `<itunes:block>` are left out unless `include_blocked`.
+    pub async fn get_all_with_stats(
+        &self,
+        page: i64,
+        per_page: i64,
+        include_blocked: bool,
+    ) -> AppResult<(Vec<PodcastWithStats>, i64)> {
+        let mut conn = self.base.get_connection().await?;
+        // The grouped query can't be boxed, so the flag is bound into the filter
+        let visible = podcasts::blocked
+            .is_distinct_from(true)
+            .or(include_blocked.into_sql::<Bool>());
+
+        let total: i64 = podcasts::table
+            .filter(visible)
+            .count()
+            .get_result(&mut conn)
+            .await?;
+
+        let (offset, per_page) = self.page_limits.offset_limit(page, per_page);
+        let rows = podcasts::table
+            .left_join(episodes::table)
+            .filter(visible)
+            .group_by(podcasts::podcast_id)
+            .select((
+                Podcast::as_select(),
+                diesel::dsl::count(episodes::episode_id.nullable()),
+                diesel::dsl::max(episodes::pub_date.nullable()),
+            ))
+            .order(podcasts::podcast_id.asc())
+            .limit(per_page)
+            .offset(offset)
+            .load::<(Podcast, i64, Option<DateTime<Utc>>)>(&mut conn)
+            .await?;
+
+        let podcasts = rows
+            .into_iter()
+            .map(
+                |(podcast, episode_count, latest_episode_date)| PodcastWithStats {
+                    podcast,
+                    episode_count,
+                    latest_episode_date,
+                },
+            )
+            .collect();
+
+        Ok((podcasts, total))
+    }
+
+    /// Returns podcasts whose `last_build_date` is older than `older_than`
+    ///
+    /// Podcasts without a `last_build_date` count as stale. Only podcasts
+    /// with an `rss_feed_url` are returned, oldest first.
+    pub async fn get_stale(&self, older_than: Duration, limit: i64) -> AppResult<Vec<Podcast>> {
+        let mut conn = self.base.get_connection().await?;
+        let cutoff = Utc::now() - older_than;
+        let result = podcasts::table
+            .filter(podcasts::rss_feed_url.is_not_null())
+            .filter(
+                podcasts::last_build_date
+                    .lt(cutoff)
+                    .or(podcasts::last_build_date.is_null()),
+            )
+            .order(podcasts::last_build_date.asc().nulls_first())
+            .limit(limit)
+            .load::<Podcast>(&mut conn)
+            .await?;
+        Ok(result)
+    }
+
+    /// Returns podcasts last crawled before `older_than`, least recently crawled first
+    ///
+    /// Unlike [`get_stale`](Self::get_stale) this follows `last_fetched_at`,
+    /// which every crawl moves forward, so feeds that were just recrawled drop
+    /// to the back. Podcasts never crawled since the column was added come
+    /// first. Only podcasts with an `rss_feed_url` are returned.
+    pub async fn get_due_for_recrawl(
+        &self,
+        older_than: Duration,
+        limit: i64,
+    ) -> AppResult<Vec<Podcast>> {
+        let mut conn = self.base.get_connection().await?;
+        let cutoff = Utc::now() - older_than;
+        let result = podcasts::table
+            .filter(podcasts::rss_feed_url.is_not_null())
+            .filter(
+                podcasts::last_fetched_at
+                    .lt(cutoff)
+                    .or(podcasts::last_fetched_at.is_null()),
+            )
+            .order(podcasts::last_fetched_at.asc().nulls_first())
+            .limit(limit)
+            .load::<Podcast>(&mut conn)
+            .await?;
+        Ok(result)
+    }
+
+    /// Counts podcasts per category, most common first
+    ///
+    /// Expands the `category` array with `unnest`, skipping NULL entries. A
+    /// podcast listing the same category twice is only counted once. Podcasts
+    /// marked `<itunes:block>` are not counted.
+    pub async fn category_counts(&self) -> AppResult<Vec<CategoryCount>> {
+        let mut conn = self.base.get_connection().await?;
+        let result = diesel::sql_query(
+            "SELECT c.category, COUNT(DISTINCT p.podcast_id) AS podcast_count \
+             FROM podcasts p CROSS JOIN LATERAL unnest(p.category) AS c(category) \
+             WHERE c.category IS NOT NULL AND p.blocked IS DISTINCT FROM true \
+             GROUP BY c.category \
+             ORDER BY podcast_count DESC, c.category ASC",
+        )
+        .load::<CategoryCount>(&mut conn)
+        .await?;
+        Ok(result)
+    }
+
+    /// Counts podcasts per language, most common first
+    ///
+    /// Languages are lowercased and trimmed, so `zh-CN` and `zh-cn` are
+    /// counted together. Podcasts without a language or marked
+    /// `<itunes:block>` are skipped.
+    pub async fn language_counts(&self) -> AppResult<Vec<LanguageCount>> {
+        let mut conn = self.base.get_connection().await?;
+        let result = diesel::sql_query(
+            "SELECT lower(trim(p.language)) AS language, COUNT(*) AS podcast_count \
+             FROM podcasts p \
+             WHERE trim(p.language) <> '' AND p.blocked IS DISTINCT FROM true \
+             GROUP BY lower(trim(p.language)) \
+             ORDER BY podcast_count DESC, language ASC",
+        )
+        .load::<LanguageCount>(&mut conn)
+        .await?;
+        Ok(result)
+    }
+
+    /// Returns the content hash stored by the last crawl of the feed, if any
+    pub async fn get_content_hash(&self, rss_feed_url: &str) -> AppResult<Option<String>> {
+        let mut conn = self.base.get_connection().await?;
+        let result = podcasts::table
+            .filter(podcasts::rss_feed_url.eq(rss_feed_url))
+            .select(podcasts::content_hash)
+            .first::<Option<String>>(&mut conn)
+            .await
+            .optional()?;
+        Ok(result.flatten())
+    }
+
+    /// Sets `last_fetched_at` to now for the podcast stored under `rss_feed_url`
+    ///
+    /// Used when a crawl finds the feed unchanged and skips the upsert.
+    /// Returns `false` when no podcast has `rss_feed_url`.
+    pub async fn touch_last_fetched(&self, rss_feed_url: &str) -> AppResult<bool> {
+        let mut conn = self.base.get_connection().await?;
+        let updated =
+            diesel::update(podcasts::table.filter(podcasts::rss_feed_url.eq(rss_feed_url)))
+                .set(podcasts::last_fetched_at.eq(Some(Utc::now())))
+                .execute(&mut conn)
+                .await?;
+        Ok(updated > 0)
+    }
+
+    /// Moves the podcast stored under `old_url` to `new_url`
+    ///
+    /// Returns `false` when no podcast has `old_url`, or when another podcast
+    /// is already stored under `new_url` and is left to be updated instead.
+    pub async fn update_feed_url(&self, old_url: &str, new_url: &str) -> AppResult<bool> {
+        let mut conn = self.base.get_connection().await?;
+        let taken = podcasts::table
+            .filter(podcasts::rss_feed_url.eq(new_url))
+            .select(podcasts::podcast_id)
+            .first::<i32>(&mut conn)
+            .await
+            .optional()?;
+        if taken.is_some() {
+            return Ok(false);
+        }
+        let rows_affected =
+            diesel::update(podcasts::table.filter(podcasts::rss_feed_url.eq(old_url)))
+                .set(podcasts::rss_feed_url.eq(new_url))
+                .execute(&mut conn)
+                .await?;
+        Ok(rows_affected > 0)
+    }
+
     pub async fn insert(&self, new_podcast: &NewPodcast) -> AppResult<()> {
         let mut conn = self.base.get_connection().await?;
         diesel::insert_into(podcasts::table)
@@ -107,121 +576,108 @@ impl PodcastRepository {
         new_podcast: &NewPodcast,
         new_episodes: &[NewEpisode],
     ) -> AppResult<()> {
-        let mut conn = self.base.get_connection().await?;
+        let (conflict_target, strategy) = (self.conflict_target, self.conflict_strategy);
 
-        conn.transaction::<_, AppError, _>(|conn| {
-            async move {
-                let update_p: UpdatePodcast = new_podcast.into();
-                let inserted_podcast = diesel::insert_into(podcasts::table)
-                    .values(new_podcast)
-                    .on_conflict(podcasts::title)
-                    .do_update()
-                    .set(&update_p)
-                    .get_result::<Podcast>(conn)
-                    .await?;
-
-                let episodes_with_podcast_id: Vec<NewEpisode> = new_episodes
-                    .iter()
-                    .map(|episode| NewEpisode {
-                        podcast_id: Some(inserted_podcast.podcast_id),
-                        episode_image_url: episode.episode_image_url.clone(),
-                        title: episode.title.clone(),
-                        description: episode.description.clone(),
-                        link: episode.link.clone(),
-                        pub_date: episode.pub_date,
-                        guid: episode.guid.clone(),
-                        enclosure_url: episode.enclosure_url.clone(),
-                        enclosure_type: episode.enclosure_type.clone(),
-                        enclosure_length: episode.enclosure_length,
-                        explicit: episode.explicit,
-                        subtitle: episode.subtitle.clone(),
-                        author: episode.author.clone(),
-                        summary: episode.summary.clone(),
-                        keywords: episode.keywords.clone(),
-                        category: episode.category.clone(),
-                        duration: episode.duration.clone(),
-                    })
-                    .collect();
-
-                if !episodes_with_podcast_id.is_empty() {
-                    for episode in &episodes_with_podcast_id {
-                        let update: UpdateEpisode = episode.into();
-                        diesel::insert_into(episodes::table)
-                            .values(episode)
-                            .on_conflict(episodes::title)
-                            .do_update()
-                            .set(update)
-                            .execute(conn)
+        with_db_retry(|| async {
+            let mut conn = self.base.get_connection().await?;
+            conn.transaction::<_, AppError, _>(|conn| {
+                async move {
+                    let inserted_podcast =
+                        upsert_podcast(conn, new_podcast, conflict_target, strategy).await?;
+                    for episode in new_episodes {
+                        upsert_episode(conn, inserted_podcast.podcast_id, episode, strategy)
                             .await?;
                     }
+                    Ok(())
                 }
-
-                Ok(())
-            }
-            .scope_boxed()
+                .scope_boxed()
+            })
+            .await
         })
         .await?;
 
         Ok(())
     }
 
-    pub async fn batch_insert_with_episodes(
+    /// Upserts the podcast and inserts only the episodes it does not have yet
+    ///
+    /// Episodes are matched against the podcast's stored episodes by guid, or by
+    /// title when the incoming episode has no guid. Existing episodes are left
+    /// untouched, except that a stored guid-less copy of an incoming episode
+    /// takes its guid instead of being duplicated. Returns the number of newly
+    /// inserted episodes.
+    pub async fn insert_new_episodes(
         &self,
-        podcasts_with_episodes: &[(NewPodcast, Vec<NewEpisode>)],
-    ) -> AppResult<()> {
-        let mut conn = self.base.get_connection().await?;
+        new_podcast: &NewPodcast,
+        new_episodes: &[NewEpisode],
+    ) -> AppResult<usize> {
+        let (conflict_target, strategy) = (self.conflict_target, self.conflict_strategy);
 
-        conn.transaction::<_, AppError, _>(|conn| {
-            async move {
-                for (new_podcast, new_episodes) in podcasts_with_episodes {
-                    let update_p: UpdatePodcast = new_podcast.into();
-                    let inserted_podcast = diesel::insert_into(podcasts::table)
-                        .values(new_podcast)
-                        .on_conflict(podcasts::rss_feed_url)
-                        .do_update()
-                        .set(&update_p)
-                        .get_result::<Podcast>(conn)
+        let inserted = with_db_retry(|| async {
+            let mut conn = self.base.get_connection().await?;
+            conn.transaction::<_, AppError, _>(|conn| {
+                async move {
+                    let inserted_podcast =
+                        upsert_podcast(conn, new_podcast, conflict_target, strategy).await?;
+
+                    let existing: Vec<(Option<String>, String)> = episodes::table
+                        .filter(episodes::podcast_id.eq(inserted_podcast.podcast_id))
+                        .select((episodes::guid, episodes::title))
+                        .load(conn)
                         .await?;
 
-                    let episodes_with_podcast_id: Vec<NewEpisode> = new_episodes
-                        .iter()
-                        .map(|episode| NewEpisode {
+                    let mut inserted = 0;
+                    for episode in filter_new_episodes(new_episodes, &existing) {
+                        if adopt_guidless_episode(conn, inserted_podcast.podcast_id, episode)
+                            .await?
+                        {
+                            continue;
+                        }
+                        let episode = NewEpisode {
                             podcast_id: Some(inserted_podcast.podcast_id),
-                            episode_image_url: episode.episode_image_url.clone(),
-                            title: episode.title.clone(),
-                            description: episode.description.clone(),
-                            link: episode.link.clone(),
-                            pub_date: episode.pub_date,
-                            guid: episode.guid.clone(),
-                            enclosure_url: episode.enclosure_url.clone(),
-                            enclosure_type: episode.enclosure_type.clone(),
-                            enclosure_length: episode.enclosure_length,
-                            explicit: episode.explicit,
-                            subtitle: episode.subtitle.clone(),
-                            author: episode.author.clone(),
-                            summary: episode.summary.clone(),
-                            keywords: episode.keywords.clone(),
-                            category: episode.category.clone(),
-                            duration: episode.duration.clone(),
-                        })
-                        .collect();
-
-                    if !episodes_with_podcast_id.is_empty() {
-                        for episode in &episodes_with_podcast_id {
-                            let update: UpdateEpisode = episode.into();
-                            diesel::insert_into(episodes::table)
-                                .values(episode)
-                                .on_conflict(episodes::guid)
-                                .do_update()
-                                .set(update)
-                                .execute(conn)
+                            ..episode.clone()
+                        };
+                        inserted += diesel::insert_into(episodes::table)
+                            .values(&episode)
+                            .on_conflict_do_nothing()
+                            .execute(conn)
+                            .await?;
+                    }
+
+                    Ok(inserted)
+                }
+                .scope_boxed()
+            })
+            .await
+        })
+        .await?;
+
+        Ok(inserted)
+    }
+
+    pub async fn batch_insert_with_episodes(
+        &self,
+        podcasts_with_episodes: &[(NewPodcast, Vec<NewEpisode>)],
+    ) -> AppResult<()> {
+        let (conflict_target, strategy) = (self.conflict_target, self.conflict_strategy);
+
+        with_db_retry(|| async {
+            let mut conn = self.base.get_connection().await?;
+            conn.transaction::<_, AppError, _>(|conn| {
+                async move {
+                    for (new_podcast, new_episodes) in podcasts_with_episodes {
+                        let inserted_podcast =
+                            upsert_podcast(conn, new_podcast, conflict_target, strategy).await?;
+                        for episode in new_episodes {
+                            upsert_episode(conn, inserted_podcast.podcast_id, episode, strategy)
                                 .await?;
                         }
                     }
+                    Ok(())
                 }
-                Ok(())
-            }
-            .scope_boxed()
+                .scope_boxed()
+            })
+            .await
         })
         .await?;
 
@@ -229,23 +685,20 @@ impl PodcastRepository {
     }
 
     pub async fn batch_upsert(&self, podcasts: &[NewPodcast]) -> AppResult<()> {
-        let mut conn = self.base.get_connection().await?;
+        let (conflict_target, strategy) = (self.conflict_target, self.conflict_strategy);
 
-        conn.transaction::<_, AppError, _>(|conn| {
-            async move {
-                for podcast in podcasts {
-                    let update: UpdatePodcast = podcast.into();
-                    diesel::insert_into(podcasts::table)
-                        .values(podcast)
-                        .on_conflict(podcasts::rss_feed_url)
-                        .do_update()
-                        .set(&update)
-                        .execute(conn)
-                        .await?;
+        with_db_retry(|| async {
+            let mut conn = self.base.get_connection().await?;
+            conn.transaction::<_, AppError, _>(|conn| {
+                async move {
+                    for podcast in podcasts {
+                        upsert_podcast(conn, podcast, conflict_target, strategy).await?;
+                    }
+                    Ok(())
                 }
-                Ok(())
-            }
-            .scope_boxed()
+                .scope_boxed()
+            })
+            .await
         })
         .await?;
 
@@ -298,7 +751,7 @@ impl PodcastRepository {
                 .await?;
 
             // Get paginated episodes
-            let offset = (page - 1) * per_page;
+            let (offset, per_page) = self.page_limits.offset_limit(page, per_page);
             let episodes = episodes::table
                 .filter(episodes::podcast_id.eq(podcast.podcast_id))
                 .order(episodes::pub_date.desc())
@@ -313,3 +766,900 @@ impl PodcastRepository {
         }
     }
 }
+
+// Insert the podcast or, following `strategy`, update the row matching
+// `conflict_target`, stamping `last_fetched_at` whenever the row is written
+async fn upsert_podcast(
+    conn: &mut AsyncPgConnection,
+    new_podcast: &NewPodcast,
+    conflict_target: PodcastConflictTarget,
+    strategy: ConflictStrategy,
+) -> AppResult<Podcast> {
+    let existing = match strategy {
+        ConflictStrategy::Overwrite => None,
+        _ => find_conflicting_podcast(conn, new_podcast, conflict_target).await?,
+    };
+    if let Some(existing) = existing {
+        let skip = match strategy {
+            ConflictStrategy::UpdateIfNewer => {
+                !is_newer(new_podcast.last_build_date, existing.last_build_date)
+            }
+            _ => true,
+        };
+        if skip {
+            return Ok(existing);
+        }
+    }
+
+    let update: UpdatePodcast = new_podcast.into();
+    let fetched_at = podcasts::last_fetched_at.eq(Some(Utc::now()));
+    let insert = diesel::insert_into(podcasts::table).values((new_podcast, fetched_at));
+    let podcast = match conflict_target {
+        PodcastConflictTarget::RssFeedUrl => {
+            insert
+                .on_conflict(podcasts::rss_feed_url)
+                .do_update()
+                .set((&update, fetched_at))
+                .get_result::<Podcast>(conn)
+                .await?
+        }
+        PodcastConflictTarget::Title => {
+            insert
+                .on_conflict(podcasts::title)
+                .do_update()
+                .set((&update, fetched_at))
+                .get_result::<Podcast>(conn)
+                .await?
+        }
+    };
+    Ok(podcast)
+}
+
+// The stored podcast an upsert of `new_podcast` would conflict with
+async fn find_conflicting_podcast(
+    conn: &mut AsyncPgConnection,
+    new_podcast: &NewPodcast,
+    conflict_target: PodcastConflictTarget,
+) -> AppResult<Option<Podcast>> {
+    let query = podcasts::table.into_boxed();
+    let query = match conflict_target {
+        PodcastConflictTarget::RssFeedUrl => {
+            query.filter(podcasts::rss_feed_url.eq(&new_podcast.rss_feed_url))
+        }
+        PodcastConflictTarget::Title => query.filter(podcasts::title.eq(&new_podcast.title)),
+    };
+    Ok(query.first::<Podcast>(conn).await.optional()?)
+}
+
+// Only a dated incoming row can be newer; an undated stored row is always older
+fn is_newer(incoming: Option<DateTime<Utc>>, stored: Option<DateTime<Utc>>) -> bool {
+    match (incoming, stored) {
+        (Some(incoming), Some(stored)) => incoming > stored,
+        (Some(_), None) => true,
+        (None, _) => false,
+    }
+}
+
+// Insert the episode or, following `strategy`, update the podcast's episode with
+// the same guid, or with the same enclosure URL when the episode has no guid.
+// A stored guid-less copy of an episode with a guid is adopted first.
+async fn upsert_episode(
+    conn: &mut AsyncPgConnection,
+    podcast_id: i32,
+    episode: &NewEpisode,
+    strategy: ConflictStrategy,
+) -> AppResult<()> {
+    let episode = NewEpisode {
+        podcast_id: Some(podcast_id),
+        ..episode.clone()
+    };
+    adopt_guidless_episode(conn, podcast_id, &episode).await?;
+    if strategy != ConflictStrategy::Overwrite {
+        let query = episodes::table
+            .filter(episodes::podcast_id.eq(podcast_id))
+            .select(episodes::pub_date)
+            .into_boxed();
+        let query = match &episode.guid {
+            Some(guid) => query.filter(episodes::guid.eq(guid)),
+            None => query
+                .filter(episodes::guid.is_null())
+                .filter(episodes::enclosure_url.eq(&episode.enclosure_url)),
+        };
+        if let Some(stored_pub_date) = query
+            .first::<Option<DateTime<Utc>>>(conn)
+            .await
+            .optional()?
+        {
+            if strategy == ConflictStrategy::SkipExisting
+                || !is_newer(episode.pub_date, stored_pub_date)
+            {
+                return Ok(());
+            }
+        }
+    }
+
+    let update: UpdateEpisode = (&episode).into();
+    let insert = diesel::insert_into(episodes::table).values(&episode);
+    if episode.guid.is_some() {
+        insert
+            .on_conflict((episodes::podcast_id, episodes::guid))
+            .do_update()
+            .set(update)
+            .execute(conn)
+            .await?;
+    } else {
+        // Matches the partial unique index on guid-less episodes
+        insert
+            .on_conflict((episodes::podcast_id, episodes::enclosure_url))
+            .filter_target(episodes::guid.is_null())
+            .do_update()
+            .set(update)
+            .execute(conn)
+            .await?;
+    }
+    Ok(())
+}
+
+// Give a stored guid-less copy of the episode the incoming guid, so guid conflicts
+// find it. Rows written before guids were synthesized (or while synthesis was off)
+// match by enclosure URL, or by link and pub_date when there is no enclosure.
+// Returns whether a stored row was adopted.
+async fn adopt_guidless_episode(
+    conn: &mut AsyncPgConnection,
+    podcast_id: i32,
+    episode: &NewEpisode,
+) -> AppResult<bool> {
+    let Some(guid) = &episode.guid else {
+        return Ok(false);
+    };
+    let legacy = episodes::table
+        .filter(episodes::podcast_id.eq(podcast_id))
+        .filter(episodes::guid.is_null())
+        .select(episodes::episode_id)
+        .into_boxed();
+    let legacy = match (&episode.enclosure_url, &episode.link) {
+        (Some(enclosure_url), _) => legacy.filter(episodes::enclosure_url.eq(enclosure_url)),
+        (None, Some(link)) => legacy
+            .filter(episodes::enclosure_url.is_null())
+            .filter(episodes::link.eq(link))
+            .filter(episodes::pub_date.is_not_distinct_from(episode.pub_date)),
+        (None, None) => return Ok(false),
+    };
+    let Some(episode_id) = legacy.first::<i32>(conn).await.optional()? else {
+        return Ok(false);
+    };
+    // An episode that already has this guid keeps it; the legacy row stays as is
+    let taken: bool = diesel::select(diesel::dsl::exists(
+        episodes::table
+            .filter(episodes::podcast_id.eq(podcast_id))
+            .filter(episodes::guid.eq(guid)),
+    ))
+    .get_result(conn)
+    .await?;
+    if taken {
+        return Ok(false);
+    }
+    diesel::update(episodes::table.find(episode_id))
+        .set(episodes::guid.eq(guid))
+        .execute(conn)
+        .await?;
+    Ok(true)
+}
+
+// Keep the incoming episodes whose guid (or title, without a guid) is not stored yet
+fn filter_new_episodes<'a>(
+    incoming: &'a [NewEpisode],
+    existing: &[(Option<String>, String)],
+) -> Vec<&'a NewEpisode> {
+    let known_guids: HashSet<&str> = existing
+        .iter()
+        .filter_map(|(guid, _)| guid.as_deref())
+        .collect();
+    let known_titles: HashSet<&str> = existing.iter().map(|(_, title)| title.as_str()).collect();
+
+    incoming
+        .iter()
+        .filter(|episode| match episode.guid.as_deref() {
+            Some(guid) => !known_guids.contains(guid),
+            None => !known_titles.contains(episode.title.as_str()),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn episode(guid: Option<&str>, title: &str) -> NewEpisode {
+        NewEpisode {
+            guid: guid.map(str::to_string),
+            title: title.to_string(),
+            ..NewEpisode::default()
+        }
+    }
+
+    #[test]
+    fn test_filter_new_episodes() {
+        let existing = vec![
+            (Some("guid-1".to_string()), "Episode 1".to_string()),
+            (None, "Episode 2".to_string()),
+        ];
+        let incoming = vec![
+            episode(Some("guid-1"), "Episode 1 (renamed)"),
+            episode(None, "Episode 2"),
+            episode(Some("guid-3"), "Episode 3"),
+            episode(None, "Episode 4"),
+        ];
+
+        let titles: Vec<&str> = filter_new_episodes(&incoming, &existing)
+            .into_iter()
+            .map(|e| e.title.as_str())
+            .collect();
+        assert_eq!(titles, vec!["Episode 3", "Episode 4"]);
+    }
+
+    #[tokio::test]
+    async fn test_get_all_excludes_explicit() -> AppResult<()> {
+        let state = crate::infrastructure::initialize().await?;
+        // Load every podcast in a single page
+        let repo = &PodcastRepository::new(state.database_context.clone())
+            .with_page_limits(PageLimits::new(10, i64::MAX));
+        let suffix = Utc::now().timestamp_nanos_opt().unwrap();
+        let podcast = |name: &str, explicit: Option<bool>| NewPodcast {
+            title: format!("{} {}", name, suffix),
+            rss_feed_url: Some(format!("https://example.com/{}-{}.xml", name, suffix)),
+            explicit,
+            ..NewPodcast::default()
+        };
+        let explicit = podcast("explicit", Some(true));
+        let clean = podcast("clean", Some(false));
+        let unrated = podcast("unrated", None);
+        repo.batch_upsert(&[explicit.clone(), clean.clone(), unrated.clone()])
+            .await?;
+
+        let titles = |podcasts: Vec<Podcast>| -> HashSet<String> {
+            podcasts.into_iter().map(|p| p.title).collect()
+        };
+        let (all, _) = repo.get_all(1, i64::MAX, false, true).await?;
+        let (filtered, total) = repo.get_all(1, i64::MAX, true, true).await?;
+        let (all, filtered) = (titles(all), titles(filtered));
+
+        assert!(all.contains(&explicit.title));
+        assert!(!filtered.contains(&explicit.title));
+        assert!(filtered.contains(&clean.title));
+        // A NULL explicit flag counts as clean
+        assert!(filtered.contains(&unrated.title));
+        assert_eq!(total as usize, filtered.len());
+
+        for podcast in [explicit, clean, unrated] {
+            if let Some(stored) = repo.get_by_title(&podcast.title).await? {
+                repo.delete_by_id(stored.podcast_id).await?;
+            }
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_all_after_pages_by_id() -> AppResult<()> {
+        let state = crate::infrastructure::initialize().await?;
+        let repo = &state.repositories.podcast;
+        let suffix = Utc::now().timestamp_nanos_opt().unwrap();
+        let podcasts: Vec<NewPodcast> = (0..3)
+            .map(|i| NewPodcast {
+                title: format!("cursor {} {}", i, suffix),
+                rss_feed_url: Some(format!("https://example.com/cursor-{}-{}.xml", i, suffix)),
+                ..NewPodcast::default()
+            })
+            .collect();
+        repo.batch_upsert(&podcasts).await?;
+        let mut ids = Vec::new();
+        for podcast in &podcasts {
+            ids.push(repo.get_by_title(&podcast.title).await?.unwrap().podcast_id);
+        }
+        ids.sort();
+
+        let before_first = Some(ids[0] - 1);
+        let (page, cursor) = repo.get_all_after(before_first, 2, false).await?;
+        assert_eq!(
+            page.iter().map(|p| p.podcast_id).collect::<Vec<_>>(),
+            ids[..2]
+        );
+        assert_eq!(cursor, Some(ids[1]));
+
+        // The next page continues right after the cursor
+        let (page, _) = repo.get_all_after(cursor, 1, false).await?;
+        assert_eq!(page[0].podcast_id, ids[2]);
+
+        let (page, cursor) = repo.get_all_after(Some(i32::MAX - 1), 2, false).await?;
+        assert!(page.is_empty());
+        assert_eq!(cursor, None);
+
+        for id in ids {
+            repo.delete_by_id(id).await?;
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_by_ids_keeps_input_order() -> AppResult<()> {
+        let state = crate::infrastructure::initialize().await?;
+        let repo = &state.repositories.podcast;
+        let suffix = Utc::now().timestamp_nanos_opt().unwrap();
+        let mut ids = Vec::new();
+        for name in ["first", "second"] {
+            let podcast = NewPodcast {
+                title: format!("{} {}", name, suffix),
+                rss_feed_url: Some(format!("https://example.com/{}-{}.xml", name, suffix)),
+                ..NewPodcast::default()
+            };
+            repo.insert_with_episodes(&podcast, &[]).await?;
+            ids.push(repo.get_by_title(&podcast.title).await?.unwrap().podcast_id);
+        }
+
+        // Unknown ids are skipped, duplicates returned once
+        let podcasts = repo
+            .get_by_ids(&[ids[1], -1, ids[0], ids[1]], false)
+            .await?;
+        let found: Vec<i32> = podcasts.iter().map(|p| p.podcast_id).collect();
+        assert_eq!(found, vec![ids[1], ids[0]]);
+        assert!(repo.get_by_ids(&[], false).await?.is_empty());
+
+        for id in ids {
+            repo.delete_by_id(id).await?;
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_recrawl_adopts_guidless_episodes() -> AppResult<()> {
+        let state = crate::infrastructure::initialize().await?;
+        let repo = &state.repositories.podcast;
+        let suffix = Utc::now().timestamp_nanos_opt().unwrap();
+        let podcast = NewPodcast {
+            title: format!("guidless {}", suffix),
+            rss_feed_url: Some(format!("https://example.com/guidless-{}.xml", suffix)),
+            ..NewPodcast::default()
+        };
+        // Stored before guids were synthesized
+        let stored = vec![
+            NewEpisode {
+                title: "With enclosure".to_string(),
+                enclosure_url: Some(format!("https://cdn.example.com/{}.mp3", suffix)),
+                ..NewEpisode::default()
+            },
+            NewEpisode {
+                title: "Link only".to_string(),
+                link: Some(format!("https://example.com/{}/2", suffix)),
+                pub_date: Some(Utc::now()),
+                ..NewEpisode::default()
+            },
+        ];
+        repo.insert_with_episodes(&podcast, &stored).await?;
+        let with_guids = |prefix: &str| -> Vec<NewEpisode> {
+            stored
+                .iter()
+                .enumerate()
+                .map(|(n, episode)| NewEpisode {
+                    guid: Some(format!("{}-{}-{}", prefix, suffix, n)),
+                    ..episode.clone()
+                })
+                .collect()
+        };
+        let expected = |episodes: &[NewEpisode]| -> Vec<Option<String>> {
+            let mut guids: Vec<_> = episodes.iter().map(|e| e.guid.clone()).collect();
+            guids.sort();
+            guids
+        };
+        let id = repo.get_by_title(&podcast.title).await?.unwrap().podcast_id;
+        let mut conn = state.database_context.get_connection().await?;
+        let stored_guids = episodes::table
+            .filter(episodes::podcast_id.eq(id))
+            .select(episodes::guid)
+            .order(episodes::guid);
+
+        // Incremental crawl: the stored rows take the guids, nothing is inserted
+        let recrawled = with_guids("incremental");
+        assert_eq!(repo.insert_new_episodes(&podcast, &recrawled).await?, 0);
+        let guids: Vec<Option<String>> = stored_guids.load(&mut conn).await?;
+        assert_eq!(guids, expected(&recrawled));
+
+        // Full upsert of rows that are guid-less again
+        diesel::update(episodes::table.filter(episodes::podcast_id.eq(id)))
+            .set(episodes::guid.eq(None::<String>))
+            .execute(&mut conn)
+            .await?;
+        let recrawled = with_guids("upsert");
+        repo.insert_with_episodes(&podcast, &recrawled).await?;
+        let guids: Vec<Option<String>> = stored_guids.load(&mut conn).await?;
+        assert_eq!(guids, expected(&recrawled));
+
+        repo.delete_by_id(id).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_blocked_excluded_by_default() -> AppResult<()> {
+        use crate::infrastructure::persistence::repositories::EpisodeRepository;
+
+        let state = crate::infrastructure::initialize().await?;
+        // Load every podcast and episode in a single page
+        let limits = PageLimits::new(10, i64::MAX);
+        let repo = &PodcastRepository::new(state.database_context.clone()).with_page_limits(limits);
+        let episode_repo =
+            EpisodeRepository::new(state.database_context.clone()).with_page_limits(limits);
+        let suffix = Utc::now().timestamp_nanos_opt().unwrap();
+        let episode = |name: &str, blocked: Option<bool>| NewEpisode {
+            guid: Some(format!("{}-{}", name, suffix)),
+            title: format!("{} {}", name, suffix),
+            blocked,
+            ..NewEpisode::default()
+        };
+        let mut podcasts = Vec::new();
+        for (name, blocked, episodes) in [
+            (
+                "open",
+                Some(false),
+                vec![episode("visible", None), episode("hidden", Some(true))],
+            ),
+            ("blocked", Some(true), vec![episode("orphaned", None)]),
+        ] {
+            let podcast = NewPodcast {
+                title: format!("{} {}", name, suffix),
+                rss_feed_url: Some(format!("https://example.com/{}-{}.xml", name, suffix)),
+                category: Some(vec![Some(format!("category-{}", suffix))]),
+                language: Some(format!("lang-{}", suffix)),
+                blocked,
+                ..NewPodcast::default()
+            };
+            repo.insert_with_episodes(&podcast, &episodes).await?;
+            podcasts.push(podcast);
+        }
+        let mut ids = Vec::new();
+        for podcast in &podcasts {
+            ids.extend(
+                repo.get_by_title(&podcast.title)
+                    .await?
+                    .map(|p| p.podcast_id),
+            );
+        }
+
+        let titles = |titles: Vec<String>| -> HashSet<String> {
+            titles
+                .into_iter()
+                .filter(|title| title.ends_with(&suffix.to_string()))
+                .collect()
+        };
+        let expected = |names: &[&str]| -> HashSet<String> {
+            names
+                .iter()
+                .map(|name| format!("{} {}", name, suffix))
+                .collect()
+        };
+        let podcast_titles =
+            |podcasts: Vec<Podcast>| titles(podcasts.into_iter().map(|p| p.title).collect());
+        let episode_titles =
+            |episodes: Vec<Episode>| titles(episodes.into_iter().map(|e| e.title).collect());
+
+        for (include_blocked, names) in [(false, &["open"][..]), (true, &["open", "blocked"])] {
+            let (listed, _) = repo.get_all(1, i64::MAX, false, include_blocked).await?;
+            assert_eq!(podcast_titles(listed), expected(names));
+            let (listed, total) = repo
+                .get_by_category(&format!("category-{}", suffix), 1, 10, include_blocked)
+                .await?;
+            assert_eq!(total, names.len() as i64);
+            assert_eq!(podcast_titles(listed), expected(names));
+            let (listed, total) = repo
+                .get_by_language(&format!("lang-{}", suffix), 1, 10, include_blocked)
+                .await?;
+            assert_eq!(total, names.len() as i64);
+            assert_eq!(podcast_titles(listed), expected(names));
+            let (listed, _) = repo
+                .get_all_with_stats(1, i64::MAX, include_blocked)
+                .await?;
+            let listed = listed.into_iter().map(|stats| stats.podcast).collect();
+            assert_eq!(podcast_titles(listed), expected(names));
+            let listed = repo
+                .search_by_title(&suffix.to_string(), include_blocked)
+                .await?;
+            assert_eq!(podcast_titles(listed), expected(names));
+            let listed = repo.get_by_ids(&ids, include_blocked).await?;
+            assert_eq!(podcast_titles(listed), expected(names));
+            let before_first = ids.iter().min().map(|id| id - 1);
+            let (listed, _) = repo
+                .get_all_after(before_first, i64::MAX, include_blocked)
+                .await?;
+            assert_eq!(podcast_titles(listed), expected(names));
+        }
+
+        // Counts only include the open podcast
+        let counts = repo.category_counts().await?;
+        let count = counts
+            .iter()
+            .find(|count| count.category == format!("category-{}", suffix));
+        assert_eq!(count.map(|count| count.podcast_count), Some(1));
+        let counts = repo.language_counts().await?;
+        let count = counts
+            .iter()
+            .find(|count| count.language == format!("lang-{}", suffix));
+        assert_eq!(count.map(|count| count.podcast_count), Some(1));
+
+        // Episodes of a blocked podcast are hidden along with blocked episodes
+        let (recent, _) = episode_repo.get_recent(1, i64::MAX, false, false).await?;
+        assert_eq!(episode_titles(recent), expected(&["visible"]));
+        let (recent, _) = episode_repo.get_recent(1, i64::MAX, false, true).await?;
+        assert_eq!(
+            episode_titles(recent),
+            expected(&["visible", "hidden", "orphaned"])
+        );
+
+        for podcast in podcasts {
+            if let Some(stored) = repo.get_by_title(&podcast.title).await? {
+                repo.delete_by_id(stored.podcast_id).await?;
+            }
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_recrawl_unblocks() -> AppResult<()> {
+        use crate::infrastructure::persistence::repositories::EpisodeRepository;
+
+        let state = crate::infrastructure::initialize().await?;
+        let limits = PageLimits::new(10, i64::MAX);
+        let repo = PodcastRepository::new(state.database_context.clone()).with_page_limits(limits);
+        let episode_repo =
+            EpisodeRepository::new(state.database_context.clone()).with_page_limits(limits);
+        let suffix = Utc::now().timestamp_nanos_opt().unwrap();
+        let crawl = |blocked: bool| {
+            let podcast = NewPodcast {
+                title: format!("Unblocked {}", suffix),
+                rss_feed_url: Some(format!("https://example.com/unblocked-{}.xml", suffix)),
+                blocked: Some(blocked),
+                ..NewPodcast::default()
+            };
+            let episode = NewEpisode {
+                guid: Some(format!("unblocked-{}", suffix)),
+                title: format!("Unblocked episode {}", suffix),
+                blocked: Some(blocked),
+                ..NewEpisode::default()
+            };
+            (podcast, vec![episode])
+        };
+        let listed = || async {
+            let (podcasts, _) = repo.get_all(1, i64::MAX, false, false).await?;
+            let (episodes, _) = episode_repo.get_recent(1, i64::MAX, false, false).await?;
+            Ok::<_, AppError>((
+                podcasts
+                    .iter()
+                    .any(|p| p.title == format!("Unblocked {}", suffix)),
+                episodes
+                    .iter()
+                    .any(|e| e.title == format!("Unblocked episode {}", suffix)),
+            ))
+        };
+
+        let (podcast, episodes) = crawl(true);
+        repo.insert_with_episodes(&podcast, &episodes).await?;
+        let id = repo.get_by_title(&podcast.title).await?.unwrap().podcast_id;
+        assert_eq!(listed().await?, (false, false));
+
+        // The feed dropped <itunes:block>, so the parser reports Some(false)
+        let (podcast, episodes) = crawl(false);
+        repo.insert_with_episodes(&podcast, &episodes).await?;
+        assert_eq!(listed().await?, (true, true));
+
+        repo.delete_by_id(id).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_without_episodes() -> AppResult<()> {
+        let state = crate::infrastructure::initialize().await?;
+        // Load every empty podcast in a single page
+        let repo = &PodcastRepository::new(state.database_context.clone())
+            .with_page_limits(PageLimits::new(10, i64::MAX));
+        let suffix = Utc::now().timestamp_nanos_opt().unwrap();
+        let mut ids = Vec::new();
+        for (name, blocked, episodes) in [
+            ("empty", None, vec![]),
+            ("full", None, vec![episode(Some("ep-1"), "Episode 1")]),
+            ("blocked", Some(true), vec![]),
+        ] {
+            let podcast = NewPodcast {
+                title: format!("{} {}", name, suffix),
+                rss_feed_url: Some(format!("https://example.com/{}-{}.xml", name, suffix)),
+                blocked,
+                ..NewPodcast::default()
+            };
+            repo.insert_with_episodes(&podcast, &episodes).await?;
+            ids.push(repo.get_by_title(&podcast.title).await?.unwrap().podcast_id);
+        }
+
+        let (podcasts, total) = repo.get_without_episodes(1, i64::MAX, false).await?;
+        let found: Vec<i32> = podcasts.iter().map(|p| p.podcast_id).collect();
+        assert_eq!(found.len() as i64, total);
+        assert!(found.contains(&ids[0]));
+        assert!(!found.contains(&ids[1]));
+        assert!(!found.contains(&ids[2]));
+
+        // Blocked podcasts only show up when asked for
+        let (podcasts, _) = repo.get_without_episodes(1, i64::MAX, true).await?;
+        assert!(podcasts.iter().any(|p| p.podcast_id == ids[2]));
+
+        for id in ids {
+            repo.delete_by_id(id).await?;
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_language_filter_ignores_case() -> AppResult<()> {
+        let state = crate::infrastructure::initialize().await?;
+        let repo = &state.repositories.podcast;
+        let suffix = Utc::now().timestamp_nanos_opt().unwrap();
+        let language = format!("x-{}", suffix);
+        let mut ids = Vec::new();
+        for (name, stored) in [
+            ("lower", language.clone()),
+            ("upper", language.to_uppercase()),
+            ("padded", format!("  {}\t", language)),
+        ] {
+            let podcast = NewPodcast {
+                title: format!("{} {}", name, suffix),
+                rss_feed_url: Some(format!("https://example.com/{}-{}.xml", name, suffix)),
+                language: Some(stored),
+                ..NewPodcast::default()
+            };
+            repo.insert_with_episodes(&podcast, &[]).await?;
+            ids.push(repo.get_by_title(&podcast.title).await?.unwrap().podcast_id);
+        }
+
+        let query = format!(" X-{} ", suffix);
+        let (podcasts, total) = repo.get_by_language(&query, 1, 10, false).await?;
+        assert_eq!((podcasts.len(), total), (3, 3));
+        let counts = repo.language_counts().await?;
+        let count = counts.iter().find(|count| count.language == language);
+        assert_eq!(count.map(|count| count.podcast_count), Some(3));
+
+        for id in ids {
+            repo.delete_by_id(id).await?;
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_upsert_sets_last_fetched_at() -> AppResult<()> {
+        let state = crate::infrastructure::initialize().await?;
+        let repo = &state.repositories.podcast;
+        let suffix = Utc::now().timestamp_nanos_opt().unwrap();
+        let podcast = NewPodcast {
+            title: format!("fetched {}", suffix),
+            rss_feed_url: Some(format!("https://example.com/fetched-{}.xml", suffix)),
+            ..NewPodcast::default()
+        };
+
+        repo.insert_with_episodes(&podcast, &[]).await?;
+        let first = repo.get_by_title(&podcast.title).await?.unwrap();
+        let first_fetched = first.last_fetched_at.unwrap();
+        assert!(Utc::now() - first_fetched < Duration::minutes(1));
+
+        // Crawling the feed again moves the timestamp forward
+        repo.insert_with_episodes(&podcast, &[]).await?;
+        let second = repo.get_by_id(first.podcast_id).await?.unwrap();
+        assert!(second.last_fetched_at.unwrap() > first_fetched);
+
+        repo.delete_by_id(first.podcast_id).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_count_includes_inserted_podcasts() -> AppResult<()> {
+        let state = crate::infrastructure::initialize().await?;
+        let repo = &state.repositories.podcast;
+        let suffix = Utc::now().timestamp_nanos_opt().unwrap();
+        let mut ids = Vec::new();
+        for n in 0..2 {
+            let podcast = NewPodcast {
+                title: format!("count {} {}", n, suffix),
+                rss_feed_url: Some(format!("https://example.com/count-{}-{}.xml", n, suffix)),
+                ..NewPodcast::default()
+            };
+            repo.insert_with_episodes(&podcast, &[]).await?;
+            ids.push(repo.get_by_title(&podcast.title).await?.unwrap().podcast_id);
+        }
+
+        // Other tests write concurrently, so only the lower bound is stable
+        assert!(repo.count().await? >= 2);
+
+        for id in ids {
+            repo.delete_by_id(id).await?;
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_due_for_recrawl_skips_just_crawled() -> AppResult<()> {
+        let state = crate::infrastructure::initialize().await?;
+        let repo = &state.repositories.podcast;
+        let suffix = Utc::now().timestamp_nanos_opt().unwrap();
+        let podcast = NewPodcast {
+            title: format!("recrawl {}", suffix),
+            rss_feed_url: Some(format!("https://example.com/recrawl-{}.xml", suffix)),
+            // An old build date must not make the feed due again
+            last_build_date: Some(Utc::now() - Duration::days(365)),
+            ..NewPodcast::default()
+        };
+        repo.insert_with_episodes(&podcast, &[]).await?;
+        let id = repo.get_by_title(&podcast.title).await?.unwrap().podcast_id;
+        let due_ids = |podcasts: Vec<Podcast>| -> Vec<i32> {
+            podcasts.iter().map(|p| p.podcast_id).collect()
+        };
+
+        let due = repo
+            .get_due_for_recrawl(Duration::hours(1), i64::MAX)
+            .await?;
+        assert!(!due_ids(due).contains(&id));
+        let due = repo.get_due_for_recrawl(Duration::zero(), i64::MAX).await?;
+        assert!(due_ids(due).contains(&id));
+
+        repo.delete_by_id(id).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_touch_last_fetched() -> AppResult<()> {
+        let state = crate::infrastructure::initialize().await?;
+        let repo = &state.repositories.podcast;
+        let suffix = Utc::now().timestamp_nanos_opt().unwrap();
+        let url = format!("https://example.com/touched-{}.xml", suffix);
+        let podcast = NewPodcast {
+            title: format!("touched {}", suffix),
+            rss_feed_url: Some(url.clone()),
+            ..NewPodcast::default()
+        };
+
+        repo.insert_with_episodes(&podcast, &[]).await?;
+        let first = repo.get_by_title(&podcast.title).await?.unwrap();
+
+        // An unchanged crawl moves the timestamp forward without an upsert
+        assert!(repo.touch_last_fetched(&url).await?);
+        let touched = repo.get_by_id(first.podcast_id).await?.unwrap();
+        assert!(touched.last_fetched_at.unwrap() > first.last_fetched_at.unwrap());
+        assert!(
+            !repo
+                .touch_last_fetched("https://example.com/missing.xml")
+                .await?
+        );
+
+        repo.delete_by_id(first.podcast_id).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_episode_upsert_keys() -> AppResult<()> {
+        let state = crate::infrastructure::initialize().await?;
+        let repo = &state.repositories.podcast;
+        let suffix = Utc::now().timestamp_nanos_opt().unwrap();
+        let podcast = |name: &str| NewPodcast {
+            title: format!("{} {}", name, suffix),
+            rss_feed_url: Some(format!("https://example.com/{}-{}.xml", name, suffix)),
+            ..NewPodcast::default()
+        };
+        let with_guid = NewEpisode {
+            guid: Some("ep-1".to_string()),
+            ..episode(None, "Episode 1")
+        };
+        let without_guid = |title: &str| NewEpisode {
+            enclosure_url: Some(format!("https://example.com/{}.mp3", suffix)),
+            ..episode(None, title)
+        };
+
+        // Episodes sharing a title in different podcasts are kept apart
+        let (first, second) = (podcast("first"), podcast("second"));
+        repo.insert_with_episodes(&first, &[with_guid.clone(), without_guid("Bonus")])
+            .await?;
+        repo.insert_with_episodes(&second, std::slice::from_ref(&with_guid))
+            .await?;
+        // A guid-less episode is matched again by its enclosure URL
+        repo.insert_with_episodes(&first, &[with_guid, without_guid("Bonus (renamed)")])
+            .await?;
+
+        let mut ids = Vec::new();
+        for (podcast, expected) in [
+            (&first, vec!["Bonus (renamed)", "Episode 1"]),
+            (&second, vec!["Episode 1"]),
+        ] {
+            let stored = repo.get_by_title(&podcast.title).await?.unwrap();
+            let (_, episodes) = repo
+                .get_podcast_with_episodes_by_id(stored.podcast_id)
+                .await?
+                .unwrap();
+            let mut titles: Vec<String> = episodes.into_iter().map(|e| e.title).collect();
+            titles.sort();
+            assert_eq!(titles, expected);
+            ids.push(stored.podcast_id);
+        }
+
+        for id in ids {
+            repo.delete_by_id(id).await?;
+        }
+        Ok(())
+    }
+
+    // Stores a podcast and one episode dated `stored`, crawls them again dated
+    // `incoming` under `strategy`, and returns the descriptions that were kept
+    async fn crawl_twice(
+        strategy: ConflictStrategy,
+        stored: DateTime<Utc>,
+        incoming: DateTime<Utc>,
+    ) -> AppResult<(Option<String>, Option<String>)> {
+        let state = crate::infrastructure::initialize().await?;
+        let repo = &PodcastRepository::new(state.database_context.clone())
+            .with_conflict_strategy(strategy);
+        let suffix = Utc::now().timestamp_nanos_opt().unwrap();
+        let crawl = |description: &str, date: DateTime<Utc>| {
+            let podcast = NewPodcast {
+                title: format!("strategy {}", suffix),
+                rss_feed_url: Some(format!("https://example.com/strategy-{}.xml", suffix)),
+                description: Some(description.to_string()),
+                last_build_date: Some(date),
+                ..NewPodcast::default()
+            };
+            let episode = NewEpisode {
+                description: Some(description.to_string()),
+                pub_date: Some(date),
+                ..episode(Some("ep-1"), "Episode 1")
+            };
+            (podcast, episode)
+        };
+
+        let (podcast, episode) = crawl("stored", stored);
+        repo.insert_with_episodes(&podcast, &[episode]).await?;
+        let (podcast, episode) = crawl("incoming", incoming);
+        repo.insert_with_episodes(&podcast, &[episode]).await?;
+
+        let stored = repo.get_by_title(&podcast.title).await?.unwrap();
+        let (_, episodes) = repo
+            .get_podcast_with_episodes_by_id(stored.podcast_id)
+            .await?
+            .unwrap();
+        repo.delete_by_id(stored.podcast_id).await?;
+        assert_eq!(episodes.len(), 1);
+        Ok((stored.description, episodes[0].description.clone()))
+    }
+
+    fn kept(description: &str) -> (Option<String>, Option<String>) {
+        (Some(description.to_string()), Some(description.to_string()))
+    }
+
+    #[tokio::test]
+    async fn test_conflict_strategy_overwrite() -> AppResult<()> {
+        let now = Utc::now();
+        let result = crawl_twice(ConflictStrategy::Overwrite, now, now - Duration::days(1)).await?;
+        assert_eq!(result, kept("incoming"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_conflict_strategy_skip_existing() -> AppResult<()> {
+        let now = Utc::now();
+        let strategy = ConflictStrategy::SkipExisting;
+        let result = crawl_twice(strategy, now - Duration::days(1), now).await?;
+        assert_eq!(result, kept("stored"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_conflict_strategy_update_if_newer() -> AppResult<()> {
+        let now = Utc::now();
+        let strategy = ConflictStrategy::UpdateIfNewer;
+        let result = crawl_twice(strategy, now - Duration::days(1), now).await?;
+        assert_eq!(result, kept("incoming"));
+        // An older or equally dated crawl leaves the stored rows alone
+        let result = crawl_twice(strategy, now, now - Duration::days(1)).await?;
+        assert_eq!(result, kept("stored"));
+        let result = crawl_twice(strategy, now, now).await?;
+        assert_eq!(result, kept("stored"));
+        Ok(())
+    }
+}
@@ -0,0 +1,165 @@
+//! Retrying of transient database failures.
+//!
+//! Every Diesel and pool error becomes an `InfrastructureErrorKind::Database`
+//! error, which [`AppError::is_retryable`] reports as retryable. Constraint
+//! violations and missing rows will fail the same way on every attempt, so
+//! [`with_db_retry`] additionally inspects the underlying Diesel error and only
+//! retries serialization failures, deadlocks, statement timeouts and lost
+//! connections.
+
+use std::future::Future;
+use std::time::Duration;
+
+use diesel::result::{DatabaseErrorKind, Error as DieselError};
+
+use crate::infrastructure::{AppError, AppResult};
+
+/// Number of attempts made by [`with_db_retry`], including the first one
+pub const DB_RETRY_ATTEMPTS: u32 = 3;
+
+// Delay before the first retry, doubled for every following attempt
+const DB_RETRY_BASE_DELAY: Duration = Duration::from_millis(100);
+
+/// Runs `operation`, retrying transient database errors with exponential backoff
+///
+/// The operation is retried up to [`DB_RETRY_ATTEMPTS`] times in total.
+/// Non-transient errors are returned immediately.
+pub async fn with_db_retry<T, F, Fut>(mut operation: F) -> AppResult<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = AppResult<T>>,
+{
+    let mut attempt = 1;
+    loop {
+        match operation().await {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt < DB_RETRY_ATTEMPTS && is_transient_db_error(&e) => {
+                let delay = DB_RETRY_BASE_DELAY * 2u32.pow(attempt - 1);
+                tracing::warn!(
+                    "⚠️ Transient database error (attempt {}/{}), retrying in {:?}: {}",
+                    attempt,
+                    DB_RETRY_ATTEMPTS,
+                    delay,
+                    e
+                );
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Checks whether a database error may succeed when the operation is repeated
+pub fn is_transient_db_error(err: &AppError) -> bool {
+    let AppError::Infrastructure(e) = err else {
+        return false;
+    };
+    if !e.is_retryable() {
+        return false;
+    }
+    match e
+        .source
+        .as_ref()
+        .and_then(|source| source.downcast_ref::<DieselError>())
+    {
+        Some(DieselError::DatabaseError(kind, info)) => match kind {
+            DatabaseErrorKind::SerializationFailure
+            | DatabaseErrorKind::ClosedConnection
+            | DatabaseErrorKind::UnableToSendCommand => true,
+            DatabaseErrorKind::Unknown => {
+                let message = info.message();
+                message.contains("deadlock detected") || message.contains("statement timeout")
+            }
+            _ => false,
+        },
+        Some(_) => false,
+        // Pool errors (no connection could be checked out) are worth retrying
+        None => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::{InfrastructureError, InfrastructureErrorKind};
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn database_error(kind: DatabaseErrorKind, message: &str) -> AppError {
+        DieselError::DatabaseError(kind, Box::new(message.to_string())).into()
+    }
+
+    #[test]
+    fn test_is_transient_db_error() {
+        assert!(is_transient_db_error(&database_error(
+            DatabaseErrorKind::SerializationFailure,
+            "could not serialize access"
+        )));
+        assert!(is_transient_db_error(&database_error(
+            DatabaseErrorKind::Unknown,
+            "deadlock detected"
+        )));
+        assert!(is_transient_db_error(&AppError::Infrastructure(
+            InfrastructureError::new(
+                InfrastructureErrorKind::Database,
+                "Failed to get database connection: timed out",
+                None,
+            )
+        )));
+
+        assert!(!is_transient_db_error(&database_error(
+            DatabaseErrorKind::UniqueViolation,
+            "duplicate key value"
+        )));
+        assert!(!is_transient_db_error(&DieselError::NotFound.into()));
+        assert!(!is_transient_db_error(&AppError::Infrastructure(
+            InfrastructureError::new(InfrastructureErrorKind::Config, "bad config", None)
+        )));
+    }
+
+    #[tokio::test]
+    async fn test_with_db_retry_retries_transient_errors() {
+        let calls = AtomicU32::new(0);
+        let result = with_db_retry(|| async {
+            if calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                Err(database_error(
+                    DatabaseErrorKind::SerializationFailure,
+                    "could not serialize access",
+                ))
+            } else {
+                Ok(42)
+            }
+        })
+        .await;
+        assert_eq!(result.unwrap(), 42);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_with_db_retry_gives_up() {
+        let calls = AtomicU32::new(0);
+        let result: AppResult<()> = with_db_retry(|| async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(database_error(
+                DatabaseErrorKind::ClosedConnection,
+                "server closed the connection",
+            ))
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), DB_RETRY_ATTEMPTS);
+
+        // Constraint violations fail on the first attempt
+        calls.store(0, Ordering::SeqCst);
+        let result: AppResult<()> = with_db_retry(|| async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(database_error(
+                DatabaseErrorKind::UniqueViolation,
+                "duplicate key value",
+            ))
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+}
@@ -1,16 +1,67 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use tracing::info;
+use clap::{Parser, Subcommand, ValueEnum};
+use tracing::{info, warn};
 
 use podcast_crawler::crawler_refactor::rss_crawler::RssCrawler;
+use podcast_crawler::crawler_refactor::task::{StageStatus, TaskOptions, TaskSummary};
 use podcast_crawler::{
-    infrastructure::{initialize, AppResult, AppState},
+    infrastructure::{config::CrawlerConfig, initialize, AppResult, AppState},
     metrics, try_with_log,
 };
 
 use rand::seq::SliceRandom;
 use rand::thread_rng;
 
+#[derive(Parser)]
+#[command(name = "podcast_crawler", about = "Podcast RSS crawler")]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Start the crawler and HTTP server (default)
+    Serve,
+    /// Crawl a single feed, print the parsed podcast and episodes, and exit
+    ///
+    /// With `--file`, submit every URL in the file to the crawler instead,
+    /// wait for the tasks to finish and print a summary.
+    Crawl {
+        /// RSS feed URL to crawl
+        #[arg(required_unless_present = "file", conflicts_with = "file")]
+        url: Option<String>,
+        /// Newline-delimited file of feed URLs; blank lines and `#` comments are skipped
+        #[arg(long)]
+        file: Option<PathBuf>,
+        /// Output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Json)]
+        format: OutputFormat,
+        /// Seconds `--file` waits for the tasks; unfinished ones are reported as failed
+        #[arg(long, default_value_t = 600)]
+        timeout: u64,
+    },
+}
+
+// How often `crawl --file` checks whether the submitted tasks have finished
+const BATCH_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+// How long the crawler's workers get to stop on shutdown; buffered inserts
+// are flushed even when this runs out
+const CRAWLER_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Clone, Copy, ValueEnum)]
+enum OutputFormat {
+    /// `{"podcast", "episodes", "warnings", "duration_ms"}`, for scripts
+    Json,
+    /// A short human-readable summary
+    Text,
+}
+
 async fn init_app() -> AppResult<Arc<AppState>> {
     metrics::init_metrics();
     let state = Arc::new(initialize().await?);
@@ -35,7 +86,7 @@ async fn run_test_tasks(state: Arc<AppState>) -> AppResult<()> {
     let mut crawler_guard = metrics::CRAWLER.lock().await;
     for url in random_samples {
         if let Some(crawler) = crawler_guard.as_mut() {
-            if let Err(e) = crawler.add_task(&url).await {
+            if let Err(e) = crawler.add_task(&url, TaskOptions::default()).await {
                 eprintln!("Failed to add task for {}: {}", url, e);
             }
         }
@@ -66,32 +117,234 @@ async fn handle_shutdown(metrics_server: actix_web::dev::Server) -> AppResult<()
         }
     });
 
-    let ctrl_c_handle = tokio::spawn(async move {
-        tokio::signal::ctrl_c()
-            .await
-            .expect("Failed to listen for Ctrl+C signal");
-        info!("Ctrl+C received, initiating shutdown");
+    let signal_handle = tokio::spawn(async move {
+        shutdown_signal().await;
         let _ = shutdown_sender.send(());
     });
 
-    let (server_result, ctrl_c_result) = tokio::join!(server_handle, ctrl_c_handle);
+    let (server_result, signal_result) = tokio::join!(server_handle, signal_handle);
 
     if let Err(e) = server_result {
         eprintln!("Metrics server task panicked: {}", e);
     }
-    if let Err(e) = ctrl_c_result {
-        eprintln!("Ctrl+C handler panicked: {}", e);
+    if let Err(e) = signal_result {
+        eprintln!("Shutdown signal handler panicked: {}", e);
     }
 
     info!("Shutting down application...");
+    // Keep the lock so no task is added while the crawler drains, and leave
+    // `CRAWLER` empty once it has stopped
+    let mut crawler_guard = metrics::CRAWLER.lock().await;
+    if let Some(crawler) = crawler_guard.take() {
+        crawler
+            .shutdown_with_timeout(CRAWLER_SHUTDOWN_TIMEOUT)
+            .await;
+        info!("Crawler stopped");
+    }
     Ok(())
 }
 
-#[tokio::main]
-async fn main() -> AppResult<()> {
+// Resolves on Ctrl+C, or on SIGTERM (what container orchestrators send) on unix
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("Failed to listen for Ctrl+C signal");
+        info!("Ctrl+C received, initiating shutdown");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("Failed to listen for SIGTERM signal")
+            .recv()
+            .await;
+        info!("SIGTERM received, initiating shutdown");
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+}
+
+async fn crawl_single_feed(url: &str, format: OutputFormat) -> AppResult<()> {
+    // 只需要爬虫配置，不要求数据库等其他环境变量
+    dotenv::dotenv().ok();
+    let mut config = CrawlerConfig::default();
+    config.set_from_env()?;
+    config.validate()?;
+    let started = Instant::now();
+    let (podcast, episodes, warnings) = RssCrawler::crawl_once(url, &config).await?;
+    let duration_ms = started.elapsed().as_millis();
+    match format {
+        OutputFormat::Json => {
+            let output = serde_json::json!({
+                "podcast": podcast,
+                "episodes": episodes,
+                "warnings": warnings,
+                "duration_ms": duration_ms,
+            });
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&output).expect("parsed feed is serializable")
+            );
+        }
+        OutputFormat::Text => {
+            println!("Podcast: {}", podcast.title);
+            println!("Feed: {}", podcast.rss_feed_url.as_deref().unwrap_or(url));
+            println!("Episodes: {}", episodes.len());
+            for episode in &episodes {
+                let pub_date = episode
+                    .pub_date
+                    .map(|date| date.format("%Y-%m-%d").to_string())
+                    .unwrap_or_else(|| "-".to_string());
+                println!("  {:<10}  {}", pub_date, episode.title);
+            }
+            println!("Warnings: {}", warnings.len());
+            for warning in &warnings {
+                println!("  {}: {}", warning.path, warning.message);
+            }
+            println!("Duration: {} ms", duration_ms);
+        }
+    }
+    Ok(())
+}
+
+// One URL per line; duplicates keep their first position
+fn read_feed_urls(path: &Path) -> AppResult<Vec<String>> {
+    let content = std::fs::read_to_string(path)?;
+    let mut seen = HashSet::new();
+    Ok(content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter(|url| seen.insert(*url))
+        .map(str::to_string)
+        .collect())
+}
+
+async fn crawl_feed_file(path: &Path, format: OutputFormat, timeout: Duration) -> AppResult<()> {
+    let urls = read_feed_urls(path)?;
+    metrics::init_metrics();
+    let state = Arc::new(initialize().await?);
+    let mut crawler = RssCrawler::new(state, 5, 50).await;
+    crawler.start().await;
+
+    let started = Instant::now();
+    let mut task_ids = HashSet::new();
+    let mut rejected = Vec::new();
+    for (url, result) in urls.iter().zip(crawler.add_tasks(&urls).await) {
+        match result {
+            Ok(task_id) => {
+                task_ids.insert(task_id);
+            }
+            Err(e) => rejected.push((url, e.to_string())),
+        }
+    }
+    info!("Submitted {} of {} feeds", task_ids.len(), urls.len());
+
+    let deadline = started + timeout;
+    let tasks = loop {
+        let tasks: Vec<_> = crawler
+            .get_tasks()
+            .await
+            .into_iter()
+            .filter(|task| task_ids.contains(&task.id))
+            .collect();
+        if tasks.iter().all(|task| task.is_finished()) {
+            break tasks;
+        }
+        if Instant::now() >= deadline {
+            warn!("Tasks still running after {:?}, giving up", timeout);
+            break tasks;
+        }
+        tokio::time::sleep(BATCH_POLL_INTERVAL).await;
+    };
+    crawler
+        .shutdown_with_timeout(CRAWLER_SHUTDOWN_TIMEOUT)
+        .await;
+
+    let duration_ms = started.elapsed().as_millis();
+    let completed = tasks.iter().filter(|task| task.is_completed()).count();
+    // Tasks that did not finish in time count as failed
+    let mut failed: Vec<TaskSummary> = tasks
+        .iter()
+        .filter(|task| !task.is_completed())
+        .map(|task| {
+            let mut summary = TaskSummary::from(task);
+            if !task.is_finished() {
+                summary.status = StageStatus::Failed;
+                summary.error_message = Some(format!("did not finish within {:?}", timeout));
+            }
+            summary
+        })
+        .collect();
+    failed.sort_by_key(|task| task.id);
+    match format {
+        OutputFormat::Json => {
+            let output = serde_json::json!({
+                "submitted": task_ids.len(),
+                "completed": completed,
+                "failed": failed,
+                "rejected": rejected
+                    .iter()
+                    .map(|(url, error)| serde_json::json!({ "rss_url": url, "error": error }))
+                    .collect::<Vec<_>>(),
+                "duration_ms": duration_ms,
+            });
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&output).expect("summary is serializable")
+            );
+        }
+        OutputFormat::Text => {
+            println!("Feeds: {}", urls.len());
+            println!("Submitted: {}", task_ids.len());
+            println!("Completed: {}", completed);
+            println!("Failed: {}", failed.len());
+            for task in &failed {
+                println!(
+                    "  {}  {}",
+                    task.url,
+                    task.error_message.as_deref().unwrap_or("-")
+                );
+            }
+            println!("Rejected: {}", rejected.len());
+            for (url, error) in &rejected {
+                println!("  {}  {}", url, error);
+            }
+            println!("Duration: {} ms", duration_ms);
+        }
+    }
+    Ok(())
+}
+
+async fn serve() -> AppResult<()> {
     let state = init_app().await?;
     run_test_tasks(state.clone()).await?;
     let metrics_server = start_http_server(state).await?;
     handle_shutdown(metrics_server).await?;
     Ok(())
 }
+
+#[tokio::main]
+async fn main() -> AppResult<()> {
+    match Cli::parse().command.unwrap_or(Command::Serve) {
+        Command::Serve => serve().await,
+        Command::Crawl {
+            file: Some(file),
+            format,
+            timeout,
+            ..
+        } => crawl_feed_file(&file, format, Duration::from_secs(timeout)).await,
+        Command::Crawl {
+            url: Some(url),
+            format,
+            ..
+        } => crawl_single_feed(&url, format).await,
+        Command::Crawl { .. } => unreachable!("clap requires a URL or --file"),
+    }
+}
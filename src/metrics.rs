@@ -1,21 +1,30 @@
 use actix_web::web::Json;
 use actix_web::{web, HttpResponse, Responder};
+use chrono::{DateTime, Utc};
 use prometheus::{
-    register_histogram_vec, register_int_counter, register_int_gauge, register_int_gauge_vec,
-    Encoder, HistogramVec, IntCounter, IntGauge, IntGaugeVec, TextEncoder,
+    register_histogram_vec, register_int_counter, register_int_counter_vec, register_int_gauge,
+    register_int_gauge_vec, Encoder, HistogramVec, IntCounter, IntCounterVec, IntGauge,
+    IntGaugeVec, TextEncoder,
 };
 use serde::Deserialize;
 use serde_json::{json, to_value, Value};
 use std::sync::Arc;
 use std::sync::Once;
-use tokio::sync::Mutex;
+use tokio::sync::{broadcast, Mutex};
 
+use crate::crawler::url_utils::registrable_host;
 use crate::crawler_refactor::rss_crawler::RssCrawler;
+use crate::crawler_refactor::task::{BasicAuth, TaskOptions, TaskSummary};
+use crate::infrastructure::config::CrawlerConfig;
+use crate::infrastructure::error::{AppError, DomainErrorKind};
 use crate::infrastructure::AppState;
 
 #[derive(Deserialize)]
 struct AddTaskRequest {
     rss_url: String,
+    priority: Option<u8>,
+    auth: Option<BasicAuth>,
+    max_retries: Option<u32>,
 }
 
 lazy_static::lazy_static! {
@@ -28,11 +37,20 @@ pub async fn set_crawler(crawler: RssCrawler) {
 }
 
 async fn add_task_handler(req: Json<AddTaskRequest>) -> impl Responder {
-    let rss_url = &req.rss_url;
+    let req = req.into_inner();
+    let options = TaskOptions {
+        priority: req.priority,
+        auth: req.auth,
+        max_retries: req.max_retries,
+        ..TaskOptions::default()
+    };
     let mut crawler_guard = CRAWLER.lock().await;
     if let Some(crawler) = crawler_guard.as_mut() {
-        match crawler.add_task(rss_url).await {
+        match crawler.add_task(&req.rss_url, options).await {
             Ok(_) => HttpResponse::Ok().body("Task added successfully"),
+            Err(AppError::Domain(e)) if e.kind == DomainErrorKind::Duplicate => {
+                HttpResponse::Conflict().body(format!("Task already in flight: {}", e))
+            }
             Err(e) => {
                 HttpResponse::InternalServerError().body(format!("Failed to add task: {}", e))
             }
@@ -42,8 +60,189 @@ async fn add_task_handler(req: Json<AddTaskRequest>) -> impl Responder {
     }
 }
 
+#[derive(Deserialize)]
+struct ValidateFeedRequest {
+    rss_url: String,
+}
+
+// Dry run of a crawl: the feed is fetched and parsed but nothing is stored
+async fn validate_feed_handler(
+    req: Json<ValidateFeedRequest>,
+    state: web::Data<Arc<AppState>>,
+) -> HttpResponse {
+    let url = req.into_inner().rss_url;
+    HttpResponse::Ok().json(validate_feed(&url, &state.settings.crawler).await)
+}
+
+// Body of the /validate response; fetch and parse errors are reported as invalid
+async fn validate_feed(url: &str, config: &CrawlerConfig) -> Value {
+    match RssCrawler::crawl_once(url, config).await {
+        Ok((podcast, episodes, warnings)) => json!({
+            "valid": true,
+            "podcast_title": podcast.title,
+            "episode_count": episodes.len(),
+            "warnings": warnings,
+            "error": null,
+        }),
+        Err(e) => json!({
+            "valid": false,
+            "podcast_title": null,
+            "episode_count": 0,
+            "warnings": [],
+            "error": e.to_string(),
+        }),
+    }
+}
+
+#[derive(Deserialize)]
+struct AddTasksRequest {
+    rss_urls: Vec<String>,
+}
+
+async fn add_tasks_handler(req: Json<AddTasksRequest>) -> HttpResponse {
+    let urls = req.into_inner().rss_urls;
+    let mut crawler_guard = CRAWLER.lock().await;
+    let Some(crawler) = crawler_guard.as_mut() else {
+        return HttpResponse::InternalServerError().body("Crawler not initialized");
+    };
+
+    let results: Vec<Value> = urls
+        .iter()
+        .zip(crawler.add_tasks(&urls).await)
+        .map(|(url, result)| match result {
+            Ok(task_id) => json!({ "rss_url": url, "task_id": task_id }),
+            Err(e) => json!({ "rss_url": url, "error": e.to_string() }),
+        })
+        .collect();
+    let accepted = results
+        .iter()
+        .filter(|result| result.get("task_id").is_some())
+        .count();
+    HttpResponse::Ok().json(json!({
+        "accepted": accepted,
+        "rejected": results.len() - accepted,
+        "results": results,
+    }))
+}
+
+async fn get_tasks_handler() -> HttpResponse {
+    let crawler_guard = CRAWLER.lock().await;
+    if let Some(crawler) = crawler_guard.as_ref() {
+        let mut tasks: Vec<TaskSummary> = crawler
+            .get_tasks()
+            .await
+            .iter()
+            .map(TaskSummary::from)
+            .collect();
+        tasks.sort_by_key(|task| task.id);
+        HttpResponse::Ok().json(tasks)
+    } else {
+        HttpResponse::InternalServerError().body("Crawler not initialized")
+    }
+}
+
+// Comment line sent when no task changed for a while, so disconnected clients
+// are noticed and their subscription dropped
+const TASK_STREAM_KEEP_ALIVE: std::time::Duration = std::time::Duration::from_secs(15);
+
+/// Server-Sent Events stream of task progress
+///
+/// Each event is a `TaskSummary` in JSON, sent when a task is created and
+/// whenever its stage or status changes.
+async fn task_stream_handler() -> HttpResponse {
+    let crawler_guard = CRAWLER.lock().await;
+    let Some(crawler) = crawler_guard.as_ref() else {
+        return HttpResponse::InternalServerError().body("Crawler not initialized");
+    };
+    let events = crawler.subscribe_task_events();
+    drop(crawler_guard);
+
+    let stream = futures::stream::unfold(events, |mut events| async move {
+        loop {
+            let chunk = match tokio::time::timeout(TASK_STREAM_KEEP_ALIVE, events.recv()).await {
+                Ok(Ok(summary)) => match serde_json::to_string(&summary) {
+                    Ok(data) => format!("event: task\ndata: {}\n\n", data),
+                    Err(_) => continue,
+                },
+                // A slow client misses the oldest events but keeps the stream
+                Ok(Err(broadcast::error::RecvError::Lagged(_))) => continue,
+                Ok(Err(broadcast::error::RecvError::Closed)) => return None,
+                Err(_) => ": keep-alive\n\n".to_string(),
+            };
+            return Some((Ok::<_, actix_web::Error>(web::Bytes::from(chunk)), events));
+        }
+    });
+    HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header(("Cache-Control", "no-cache"))
+        .streaming(stream)
+}
+
+async fn cancel_task_handler(path: web::Path<u64>) -> HttpResponse {
+    let id = path.into_inner();
+    let crawler_guard = CRAWLER.lock().await;
+    let Some(crawler) = crawler_guard.as_ref() else {
+        return HttpResponse::InternalServerError().body("Crawler not initialized");
+    };
+    match crawler.cancel_task(id).await {
+        Ok(()) => HttpResponse::Ok().json(json!({ "task_id": id, "cancelled": true })),
+        Err(AppError::Domain(e)) if e.kind == DomainErrorKind::NotFound => {
+            HttpResponse::NotFound().body(e.to_string())
+        }
+        Err(AppError::Domain(e)) if e.kind == DomainErrorKind::InvalidState => {
+            HttpResponse::Conflict().body(e.to_string())
+        }
+        Err(e) => HttpResponse::InternalServerError().body(format!("Failed to cancel task: {}", e)),
+    }
+}
+
+async fn pause_crawler_handler() -> HttpResponse {
+    set_crawler_paused(true).await
+}
+
+async fn resume_crawler_handler() -> HttpResponse {
+    set_crawler_paused(false).await
+}
+
+async fn set_crawler_paused(paused: bool) -> HttpResponse {
+    let crawler_guard = CRAWLER.lock().await;
+    let Some(crawler) = crawler_guard.as_ref() else {
+        return HttpResponse::InternalServerError().body("Crawler not initialized");
+    };
+    if paused {
+        crawler.pause();
+    } else {
+        crawler.resume();
+    }
+    HttpResponse::Ok().json(json!({ "paused": crawler.is_paused() }))
+}
+
+#[derive(Deserialize)]
+struct FailedFeedsQuery {
+    page: Option<i64>,
+    per_page: Option<i64>,
+}
+
+async fn get_failed_feeds_handler(
+    query: web::Query<FailedFeedsQuery>,
+    state: web::Data<Arc<AppState>>,
+) -> HttpResponse {
+    let page = query.page.unwrap_or(1);
+    let per_page = query
+        .per_page
+        .unwrap_or(state.settings.server.default_per_page);
+    match state.repositories.failed_feed.list(page, per_page).await {
+        Ok((failed_feeds, total)) => HttpResponse::Ok().json((failed_feeds, total)),
+        Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
+    }
+}
+
 static INIT: Once = Once::new();
 
+// Label values of the `task_status` gauge
+const TASK_STAGES: [&str; 4] = ["distribution", "fetching", "parsing", "inserting"];
+const TASK_STATUSES: [&str; 4] = ["pending", "in_progress", "completed", "failed"];
+
 lazy_static::lazy_static! {
     pub static ref ACTIVE_WORKERS: IntGauge = register_int_gauge!(
         "active_workers",
@@ -85,37 +284,140 @@ lazy_static::lazy_static! {
         }
     ).unwrap();
 
+    pub static ref FEED_FETCH_BYTES: HistogramVec = register_histogram_vec!(
+        "feed_fetch_bytes",
+        "Size of successfully downloaded feeds in bytes",
+        &["host"],
+        prometheus::exponential_buckets(1024.0, 4.0, 10).unwrap()
+    ).unwrap();
+
+    pub static ref FEED_FETCH_DURATION: HistogramVec = register_histogram_vec!(
+        "feed_fetch_duration_seconds",
+        "Time taken to download a feed",
+        &["host"],
+        vec![0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0]
+    ).unwrap();
+
+    pub static ref PARSE_FIELD_COVERAGE: HistogramVec = register_histogram_vec!(
+        "parse_field_coverage",
+        "Fraction of key podcast/episode fields that were populated by a parse",
+        &["host", "scope"],
+        vec![0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8, 0.9, 1.0]
+    ).unwrap();
+
+    pub static ref CIRCUIT_BREAKER_OPENED: IntCounterVec = register_int_counter_vec!(
+        "circuit_breaker_opened",
+        "Times a host's circuit opened after repeated fetch failures",
+        &["host"]
+    ).unwrap();
+
+    pub static ref WORKER_LAGGED_MESSAGES: IntCounter = register_int_counter!(
+        "worker_lagged_messages",
+        "Broadcast messages skipped by workers whose task channel lagged"
+    ).unwrap();
+
+    pub static ref LOST_TASKS: IntCounter = register_int_counter!(
+        "lost_tasks",
+        "Tasks dropped before their target worker received them (also counted in failed_tasks)"
+    ).unwrap();
+
+    pub static ref UNCHANGED_TASKS: IntCounter = register_int_counter!(
+        "unchanged_tasks",
+        "Tasks skipped because the feed content hash matched the last crawl (also counted in processed_tasks)"
+    ).unwrap();
+
     pub static ref SUBMITTED_TASKS: IntCounter = register_int_counter!(
         "submitted_tasks",
         "Total number of submitted tasks"
     ).unwrap();
+
+    pub static ref DB_POOL_CONNECTIONS_IDLE: IntGauge = register_int_gauge!(
+        "db_pool_connections_idle",
+        "Number of idle connections in the database pool"
+    ).unwrap();
+
+    pub static ref DB_POOL_CONNECTIONS_IN_USE: IntGauge = register_int_gauge!(
+        "db_pool_connections_in_use",
+        "Number of database pool connections currently checked out"
+    ).unwrap();
+
+    pub static ref DB_POOL_SIZE: IntGauge = register_int_gauge!(
+        "db_pool_size",
+        "Number of connections currently managed by the database pool"
+    ).unwrap();
+}
+
+/// Records size and download time of a successfully fetched feed
+///
+/// The label is the registrable host of `url` so that per-feed paths and
+/// subdomains do not blow up label cardinality.
+pub fn record_feed_fetch(url: &str, bytes: usize, duration: std::time::Duration) {
+    let host = registrable_host(url).unwrap_or_else(|| "unknown".to_string());
+    FEED_FETCH_BYTES
+        .with_label_values(&[&host])
+        .observe(bytes as f64);
+    FEED_FETCH_DURATION
+        .with_label_values(&[&host])
+        .observe(duration.as_secs_f64());
+}
+
+/// Counts a circuit opening for the registrable host of `url`
+pub fn record_circuit_opened(url: &str) {
+    let host = registrable_host(url).unwrap_or_else(|| "unknown".to_string());
+    CIRCUIT_BREAKER_OPENED.with_label_values(&[&host]).inc();
+}
+
+/// Records how complete a parse was for `scope` (`podcast` or `episode`)
+pub fn record_parse_field_coverage(url: &str, scope: &str, coverage: f64) {
+    let host = registrable_host(url).unwrap_or_else(|| "unknown".to_string());
+    PARSE_FIELD_COVERAGE
+        .with_label_values(&[&host, scope])
+        .observe(coverage);
+}
+
+// Refresh the database pool gauges from the current bb8 pool state
+fn update_db_pool_metrics(state: &AppState) {
+    let pool_state = state.database_context.pool().state();
+    let size = pool_state.connections as i64;
+    let idle = pool_state.idle_connections as i64;
+    DB_POOL_SIZE.set(size);
+    DB_POOL_CONNECTIONS_IDLE.set(idle);
+    DB_POOL_CONNECTIONS_IN_USE.set(size - idle);
 }
 
 pub fn init_metrics() {
-    INIT.call_once(|| {
-        // Initialize metrics
-        ACTIVE_WORKERS.set(0);
-        PROCESSED_TASKS.reset();
-        FAILED_TASKS.reset();
-        TASK_RETRIES.reset();
-        TASK_STATUS.reset();
-        TASK_STAGE_DURATION.reset();
-        SUBMITTED_TASKS.reset();
-        // Initialize all possible status counts to 0
-        let stages = vec!["distribution", "fetching", "parsing", "inserting"];
-        let statuses = vec!["pending", "in_progress", "completed", "failed"];
-
-        for stage in stages {
-            for status in &statuses {
-                crate::metrics::TASK_STATUS
-                    .with_label_values(&[stage, status])
-                    .set(0);
-            }
+    INIT.call_once(reset_metrics);
+}
+
+/// Zeroes every task and feed metric, as on startup
+///
+/// Exposed through `POST /metrics/reset` in test mode so integration tests can
+/// make deterministic assertions about counters without restarting.
+pub fn reset_metrics() {
+    ACTIVE_WORKERS.set(0);
+    PROCESSED_TASKS.reset();
+    FAILED_TASKS.reset();
+    TASK_RETRIES.reset();
+    TASK_STATUS.reset();
+    TASK_STAGE_DURATION.reset();
+    SUBMITTED_TASKS.reset();
+    WORKER_LAGGED_MESSAGES.reset();
+    LOST_TASKS.reset();
+    UNCHANGED_TASKS.reset();
+    FEED_FETCH_BYTES.reset();
+    FEED_FETCH_DURATION.reset();
+    PARSE_FIELD_COVERAGE.reset();
+    CIRCUIT_BREAKER_OPENED.reset();
+    // Initialize all possible status counts to 0
+    for stage in TASK_STAGES {
+        for status in TASK_STATUSES {
+            TASK_STATUS.with_label_values(&[stage, status]).set(0);
         }
-    });
+    }
 }
 
-pub async fn metrics_handler() -> impl Responder {
+pub async fn metrics_handler(state: web::Data<Arc<AppState>>) -> impl Responder {
+    update_db_pool_metrics(&state);
     let encoder = TextEncoder::new();
     let mut buffer = vec![];
     let metric_families = prometheus::gather();
@@ -125,21 +427,94 @@ pub async fn metrics_handler() -> impl Responder {
         .body(buffer)
 }
 
+/// Builds a JSON rollup of the task counters and per-stage status gauges
+pub fn stats_snapshot() -> Value {
+    let per_stage: serde_json::Map<String, Value> = TASK_STAGES
+        .iter()
+        .map(|stage| {
+            let statuses: serde_json::Map<String, Value> = TASK_STATUSES
+                .iter()
+                .map(|status| {
+                    let value = TASK_STATUS.with_label_values(&[stage, status]).get();
+                    (status.to_string(), json!(value))
+                })
+                .collect();
+            (stage.to_string(), Value::Object(statuses))
+        })
+        .collect();
+    json!({
+        "submitted": SUBMITTED_TASKS.get(),
+        "processed": PROCESSED_TASKS.get(),
+        "failed": FAILED_TASKS.get(),
+        "retries": TASK_RETRIES.get(),
+        "active_workers": ACTIVE_WORKERS.get(),
+        "per_stage": per_stage,
+    })
+}
+
+async fn reset_metrics_handler(state: web::Data<Arc<AppState>>) -> HttpResponse {
+    if !state.settings.is_test {
+        return HttpResponse::Forbidden().body("Metrics reset is only available in test mode");
+    }
+    reset_metrics();
+    HttpResponse::Ok().json(json!({ "reset": true }))
+}
+
+async fn stats_handler() -> HttpResponse {
+    HttpResponse::Ok().json(stats_snapshot())
+}
+
+async fn stats_counts_handler(state: web::Data<Arc<AppState>>) -> HttpResponse {
+    let repositories = &state.repositories;
+    match tokio::try_join!(repositories.podcast.count(), repositories.episode.count()) {
+        Ok((podcasts, episodes)) => HttpResponse::Ok().json(json!({
+            "podcasts": podcasts,
+            "episodes": episodes,
+        })),
+        Err(_) => HttpResponse::InternalServerError().body("Failed to count podcasts and episodes"),
+    }
+}
+
+async fn health_handler(state: web::Data<Arc<AppState>>) -> HttpResponse {
+    match state.liveness_check().await {
+        Ok(_) => HttpResponse::Ok().json(json!({ "status": "ok" })),
+        Err(e) => HttpResponse::ServiceUnavailable().json(json!({
+            "status": "unavailable",
+            "component": "database",
+            "error_code": e.error_code(),
+        })),
+    }
+}
+
 #[derive(Deserialize)]
 struct SearchPodcastsQuery {
     q: String,
+    include_blocked: Option<bool>,
 }
 
 #[derive(Deserialize)]
 struct GetPodcastsQuery {
     include_episodes: Option<bool>,
+    with_stats: Option<bool>,
+    category: Option<String>,
+    language: Option<String>,
+    page: Option<i64>,
+    per_page: Option<i64>,
+    exclude_explicit: Option<bool>,
+    include_blocked: Option<bool>,
 }
 
 async fn search_podcasts_handler(
     query: web::Query<SearchPodcastsQuery>,
     state: web::Data<Arc<AppState>>,
 ) -> HttpResponse {
-    match state.repositories.podcast.search_by_title(&query.q).await {
+    let include_blocked = query.include_blocked.unwrap_or(false);
+    match state
+        .repositories
+        .podcast
+        .search_by_title(&query.q, include_blocked)
+        .await
+    {
         Ok(podcasts) => HttpResponse::Ok().json(podcasts),
         Err(_) => HttpResponse::InternalServerError().body("Failed to search podcasts"),
     }
@@ -150,7 +525,51 @@ async fn get_podcasts_handler(
     state: web::Data<Arc<AppState>>,
 ) -> impl Responder {
     let include_episodes = query.include_episodes.unwrap_or(false);
-    match state.repositories.podcast.get_all(1, 10).await {
+    let page = query.page.unwrap_or(1);
+    let per_page = query
+        .per_page
+        .unwrap_or(state.settings.server.default_per_page);
+    let include_blocked = query.include_blocked.unwrap_or(false);
+    if let Some(category) = &query.category {
+        return match state
+            .repositories
+            .podcast
+            .get_by_category(category, page, per_page, include_blocked)
+            .await
+        {
+            Ok((podcasts, total)) => HttpResponse::Ok().json((podcasts, total)),
+            Err(_) => HttpResponse::InternalServerError().body("Failed to fetch podcasts"),
+        };
+    }
+    if let Some(language) = &query.language {
+        return match state
+            .repositories
+            .podcast
+            .get_by_language(language, page, per_page, include_blocked)
+            .await
+        {
+            Ok((podcasts, total)) => HttpResponse::Ok().json((podcasts, total)),
+            Err(_) => HttpResponse::InternalServerError().body("Failed to fetch podcasts"),
+        };
+    }
+    if query.with_stats.unwrap_or(false) {
+        return match state
+            .repositories
+            .podcast
+            .get_all_with_stats(page, per_page, include_blocked)
+            .await
+        {
+            Ok((podcasts, _total)) => HttpResponse::Ok().json(podcasts),
+            Err(_) => HttpResponse::InternalServerError().body("Failed to fetch podcasts"),
+        };
+    }
+    let exclude_explicit = query.exclude_explicit.unwrap_or(false);
+    match state
+        .repositories
+        .podcast
+        .get_all(page, per_page, exclude_explicit, include_blocked)
+        .await
+    {
         Ok((podcasts, _total)) => {
             if include_episodes {
                 let mut podcasts_with_episodes = Vec::new();
@@ -173,17 +592,60 @@ async fn get_podcasts_handler(
     }
 }
 
+#[derive(Deserialize)]
+struct PaginatedPodcastsQuery {
+    include_blocked: Option<bool>,
+}
+
 async fn get_podcasts_paginated_handler(
     state: web::Data<Arc<AppState>>,
     path: web::Path<(i64, i64)>,
+    query: web::Query<PaginatedPodcastsQuery>,
 ) -> impl Responder {
     let (page, per_page) = path.into_inner();
-    match state.repositories.podcast.get_all(page, per_page).await {
+    let include_blocked = query.include_blocked.unwrap_or(false);
+    match state
+        .repositories
+        .podcast
+        .get_all(page, per_page, false, include_blocked)
+        .await
+    {
         Ok((podcasts, total)) => HttpResponse::Ok().json((podcasts, total)),
         Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
     }
 }
 
+#[derive(Deserialize)]
+struct PodcastsCursorQuery {
+    after: Option<i32>,
+    limit: Option<i64>,
+    include_blocked: Option<bool>,
+}
+
+async fn get_podcasts_cursor_handler(
+    query: web::Query<PodcastsCursorQuery>,
+    state: web::Data<Arc<AppState>>,
+) -> impl Responder {
+    let limit = query
+        .limit
+        .unwrap_or(state.settings.server.default_per_page);
+    if limit <= 0 {
+        return HttpResponse::BadRequest().body("limit must be positive");
+    }
+    match state
+        .repositories
+        .podcast
+        .get_all_after(query.after, limit, query.include_blocked.unwrap_or(false))
+        .await
+    {
+        Ok((podcasts, next_cursor)) => HttpResponse::Ok().json(json!({
+            "podcasts": podcasts,
+            "next_cursor": next_cursor,
+        })),
+        Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
+    }
+}
+
 #[derive(Deserialize)]
 struct PodcastPathParams {
     id: i32,
@@ -194,12 +656,44 @@ struct PodcastPathParams {
 async fn get_podcast_handler(
     path: web::Path<PodcastPathParams>,
     state: web::Data<Arc<AppState>>,
-) -> impl Responder {
+) -> HttpResponse {
     let params = path.into_inner();
+    podcast_with_episodes(&state, params.id, params.page, params.per_page).await
+}
+
+#[derive(Deserialize)]
+struct PodcastEpisodesQuery {
+    page: Option<i64>,
+    per_page: Option<i64>,
+}
+
+async fn get_podcast_episodes_handler(
+    path: web::Path<i32>,
+    query: web::Query<PodcastEpisodesQuery>,
+    state: web::Data<Arc<AppState>>,
+) -> HttpResponse {
+    let page = query.page.unwrap_or(1);
+    let per_page = query.per_page.unwrap_or(50);
+    podcast_with_episodes(&state, path.into_inner(), page, per_page).await
+}
+
+// The podcast with one page of its episodes, shared by the path and query variants
+async fn podcast_with_episodes(
+    state: &AppState,
+    id: i32,
+    page: i64,
+    per_page: i64,
+) -> HttpResponse {
+    // Echo the page actually returned
+    let (page, per_page) = state
+        .repositories
+        .podcast
+        .page_limits()
+        .clamp(page, per_page);
     match state
         .repositories
         .podcast
-        .get_podcast_with_paginated_episodes(params.id, params.page, params.per_page)
+        .get_podcast_with_paginated_episodes(id, page, per_page)
         .await
     {
         Ok(Some((podcast, episodes, total_episodes))) => {
@@ -207,8 +701,8 @@ async fn get_podcast_handler(
             if let Value::Object(obj) = &mut podcast_json {
                 obj.insert("episodes".to_string(), json!(episodes));
                 obj.insert("total_episodes".to_string(), json!(total_episodes));
-                obj.insert("current_page".to_string(), json!(params.page));
-                obj.insert("per_page".to_string(), json!(params.per_page));
+                obj.insert("current_page".to_string(), json!(page));
+                obj.insert("per_page".to_string(), json!(per_page));
             }
             HttpResponse::Ok().json(podcast_json)
         }
@@ -217,6 +711,87 @@ async fn get_podcast_handler(
     }
 }
 
+async fn get_categories_handler(state: web::Data<Arc<AppState>>) -> HttpResponse {
+    match state.repositories.podcast.category_counts().await {
+        Ok(categories) => HttpResponse::Ok().json(categories),
+        Err(_) => HttpResponse::InternalServerError().body("Failed to fetch categories"),
+    }
+}
+
+async fn get_languages_handler(state: web::Data<Arc<AppState>>) -> HttpResponse {
+    match state.repositories.podcast.language_counts().await {
+        Ok(languages) => HttpResponse::Ok().json(languages),
+        Err(_) => HttpResponse::InternalServerError().body("Failed to fetch languages"),
+    }
+}
+
+async fn get_podcast_by_id_handler(
+    path: web::Path<i32>,
+    state: web::Data<Arc<AppState>>,
+) -> HttpResponse {
+    let id = path.into_inner();
+    match state.repositories.podcast.get_by_id(id).await {
+        Ok(Some(podcast)) => HttpResponse::Ok().json(podcast),
+        Ok(None) => HttpResponse::NotFound().body("Podcast not found"),
+        Err(_) => HttpResponse::InternalServerError().body("Failed to fetch podcast"),
+    }
+}
+
+#[derive(Deserialize)]
+struct PodcastsByIdsRequest {
+    ids: Vec<i32>,
+    include_blocked: Option<bool>,
+}
+
+async fn get_podcasts_by_ids_handler(
+    req: Json<PodcastsByIdsRequest>,
+    state: web::Data<Arc<AppState>>,
+) -> HttpResponse {
+    let PodcastsByIdsRequest {
+        ids,
+        include_blocked,
+    } = req.into_inner();
+    let max_ids = state.settings.server.max_per_page;
+    if ids.len() as i64 > max_ids {
+        return HttpResponse::BadRequest().body(format!("At most {} ids per request", max_ids));
+    }
+    match state
+        .repositories
+        .podcast
+        .get_by_ids(&ids, include_blocked.unwrap_or(false))
+        .await
+    {
+        Ok(podcasts) => HttpResponse::Ok().json(podcasts),
+        Err(_) => HttpResponse::InternalServerError().body("Failed to fetch podcasts"),
+    }
+}
+
+#[derive(Deserialize)]
+struct EmptyPodcastsQuery {
+    page: Option<i64>,
+    per_page: Option<i64>,
+    include_blocked: Option<bool>,
+}
+
+async fn get_empty_podcasts_handler(
+    query: web::Query<EmptyPodcastsQuery>,
+    state: web::Data<Arc<AppState>>,
+) -> HttpResponse {
+    let page = query.page.unwrap_or(1);
+    let per_page = query
+        .per_page
+        .unwrap_or(state.settings.server.default_per_page);
+    match state
+        .repositories
+        .podcast
+        .get_without_episodes(page, per_page, query.include_blocked.unwrap_or(false))
+        .await
+    {
+        Ok((podcasts, total)) => HttpResponse::Ok().json((podcasts, total)),
+        Err(_) => HttpResponse::InternalServerError().body("Failed to fetch podcasts"),
+    }
+}
+
 async fn get_podcast_by_title_handler(
     path: web::Path<String>,
     state: web::Data<Arc<AppState>>,
@@ -229,15 +804,120 @@ async fn get_podcast_by_title_handler(
     }
 }
 
+#[derive(Deserialize)]
+struct EpisodesByDateQuery {
+    from: String,
+    to: String,
+    page: Option<i64>,
+    per_page: Option<i64>,
+}
+
+async fn get_episodes_by_date_handler(
+    query: web::Query<EpisodesByDateQuery>,
+    state: web::Data<Arc<AppState>>,
+) -> HttpResponse {
+    let parse =
+        |value: &str| DateTime::parse_from_rfc3339(value).map(|date| date.with_timezone(&Utc));
+    let (start, end) = match (parse(&query.from), parse(&query.to)) {
+        (Ok(start), Ok(end)) => (start, end),
+        _ => return HttpResponse::BadRequest().body("`from` and `to` must be RFC3339 timestamps"),
+    };
+    let page = query.page.unwrap_or(1);
+    let per_page = query
+        .per_page
+        .unwrap_or(state.settings.server.default_per_page);
+
+    match state
+        .repositories
+        .episode
+        .get_by_pub_date_range(start, end, page, per_page)
+        .await
+    {
+        Ok((episodes, total)) => HttpResponse::Ok().json((episodes, total)),
+        Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
+    }
+}
+
+async fn refresh_podcast_handler(
+    path: web::Path<i32>,
+    state: web::Data<Arc<AppState>>,
+) -> HttpResponse {
+    let id = path.into_inner();
+    let rss_url = match state.repositories.podcast.get_by_id(id).await {
+        Ok(Some(podcast)) => match podcast.rss_feed_url {
+            Some(url) => url,
+            None => return HttpResponse::Conflict().body("Podcast has no rss_feed_url"),
+        },
+        Ok(None) => return HttpResponse::NotFound().body("Podcast not found"),
+        Err(_) => return HttpResponse::InternalServerError().body("Failed to fetch podcast"),
+    };
+
+    let mut crawler_guard = CRAWLER.lock().await;
+    if let Some(crawler) = crawler_guard.as_mut() {
+        match crawler.add_task(&rss_url, TaskOptions::default()).await {
+            Ok(task_id) => HttpResponse::Ok().json(json!({ "task_id": task_id })),
+            Err(AppError::Domain(e)) if e.kind == DomainErrorKind::Duplicate => {
+                HttpResponse::Conflict().body(format!("Task already in flight: {}", e))
+            }
+            Err(e) => {
+                HttpResponse::InternalServerError().body(format!("Failed to add task: {}", e))
+            }
+        }
+    } else {
+        HttpResponse::InternalServerError().body("Crawler not initialized")
+    }
+}
+
+#[derive(Deserialize)]
+struct RecentEpisodesQuery {
+    exclude_explicit: Option<bool>,
+    include_blocked: Option<bool>,
+}
+
+async fn get_recent_episodes_handler(
+    state: web::Data<Arc<AppState>>,
+    path: web::Path<(i64, i64)>,
+    query: web::Query<RecentEpisodesQuery>,
+) -> impl Responder {
+    let (page, per_page) = path.into_inner();
+    let exclude_explicit = query.exclude_explicit.unwrap_or(false);
+    let include_blocked = query.include_blocked.unwrap_or(false);
+    match state
+        .repositories
+        .episode
+        .get_recent(page, per_page, exclude_explicit, include_blocked)
+        .await
+    {
+        Ok((episodes, total)) => HttpResponse::Ok().json((episodes, total)),
+        Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
+    }
+}
+
 pub fn start_metrics_server(state: Arc<AppState>) -> actix_web::dev::Server {
     actix_web::HttpServer::new(move || {
         actix_web::App::new()
             .wrap(actix_cors::Cors::permissive())
             .app_data(web::Data::new(state.clone()))
+            .route("/health", web::get().to(health_handler))
             .route("/metrics", web::get().to(metrics_handler))
+            .route("/metrics/reset", web::post().to(reset_metrics_handler))
+            .route("/stats", web::get().to(stats_handler))
+            .route("/stats/counts", web::get().to(stats_counts_handler))
             .route("/add_task", web::post().to(add_task_handler))
+            .route("/add_tasks", web::post().to(add_tasks_handler))
+            .route("/validate", web::post().to(validate_feed_handler))
+            .route("/tasks", web::get().to(get_tasks_handler))
+            .route("/tasks/stream", web::get().to(task_stream_handler))
+            .route("/tasks/{id}", web::delete().to(cancel_task_handler))
+            .route("/crawler/pause", web::post().to(pause_crawler_handler))
+            .route("/crawler/resume", web::post().to(resume_crawler_handler))
+            .route("/failed", web::get().to(get_failed_feeds_handler))
             .route("/podcasts/search", web::get().to(search_podcasts_handler))
             .route("/podcasts", web::get().to(get_podcasts_handler))
+            .route(
+                "/podcasts/cursor",
+                web::get().to(get_podcasts_cursor_handler),
+            )
             .route(
                 "/podcasts/page/{page}/{per_page}",
                 web::get().to(get_podcasts_paginated_handler),
@@ -246,12 +926,92 @@ pub fn start_metrics_server(state: Arc<AppState>) -> actix_web::dev::Server {
                 "/podcasts/by-title/{title}",
                 web::get().to(get_podcast_by_title_handler),
             )
+            .route(
+                "/podcasts/batch",
+                web::post().to(get_podcasts_by_ids_handler),
+            )
+            .route("/podcasts/empty", web::get().to(get_empty_podcasts_handler))
+            .route("/podcasts/{id}", web::get().to(get_podcast_by_id_handler))
+            .route(
+                "/podcasts/{id}/refresh",
+                web::post().to(refresh_podcast_handler),
+            )
+            .route(
+                "/podcasts/{id}/episodes",
+                web::get().to(get_podcast_episodes_handler),
+            )
             .route(
                 "/podcasts/{id}/episodes/{page}/{per_page}",
                 web::get().to(get_podcast_handler),
             )
+            .route("/categories", web::get().to(get_categories_handler))
+            .route("/languages", web::get().to(get_languages_handler))
+            .route("/episodes", web::get().to(get_episodes_by_date_handler))
+            .route(
+                "/episodes/recent/{page}/{per_page}",
+                web::get().to(get_recent_episodes_handler),
+            )
     })
     .bind("127.0.0.1:8080")
     .expect("Failed to bind metrics server")
     .run()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_validate_feed_reports_parse_result() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/feed.xml"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<rss version="2.0"><channel><title>Validated</title>
+                <item><guid>1</guid><title>One</title></item>
+                <item><guid>2</guid><title>Two</title></item>
+                </channel></rss>"#,
+            ))
+            .mount(&mock_server)
+            .await;
+
+        let url = format!("{}/feed.xml", mock_server.uri());
+        let result = validate_feed(&url, &CrawlerConfig::default()).await;
+        assert_eq!(result["valid"], true);
+        assert_eq!(result["podcast_title"], "Validated");
+        assert_eq!(result["episode_count"], 2);
+        assert_eq!(result["error"], Value::Null);
+        // The channel has no itunes:image
+        let fields: Vec<&str> = result["warnings"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|warning| warning["field"].as_str())
+            .collect();
+        assert!(fields.contains(&"image_url"), "{:?}", result["warnings"]);
+    }
+
+    #[tokio::test]
+    async fn test_validate_feed_reports_error() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/page.html"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_string("<html><body>Not a feed</body></html>"),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let url = format!("{}/page.html", mock_server.uri());
+        let result = validate_feed(&url, &CrawlerConfig::default()).await;
+        assert_eq!(result["valid"], false);
+        assert_eq!(result["podcast_title"], Value::Null);
+        assert_eq!(result["episode_count"], 0);
+        assert_eq!(result["warnings"], json!([]));
+        assert!(result["error"]
+            .as_str()
+            .is_some_and(|error| !error.is_empty()));
+    }
+}
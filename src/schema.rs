@@ -48,6 +48,24 @@ diesel::table! {
         category -> Nullable<Array<Nullable<Text>>>,
         #[max_length = 255]
         duration -> Nullable<Varchar>,
+        #[max_length = 1024]
+        transcript_url -> Nullable<Varchar>,
+        #[max_length = 100]
+        transcript_type -> Nullable<Varchar>,
+        feed_position -> Nullable<Int4>,
+        extra -> Nullable<Jsonb>,
+        blocked -> Nullable<Bool>,
+    }
+}
+
+diesel::table! {
+    failed_feeds (id) {
+        id -> Int4,
+        #[max_length = 1024]
+        url -> Varchar,
+        last_error -> Nullable<Text>,
+        attempts -> Int4,
+        failed_at -> Timestamptz,
     }
 }
 
@@ -102,9 +120,24 @@ diesel::table! {
         explicit -> Nullable<Bool>,
         summary -> Nullable<Text>,
         subtitle -> Nullable<Text>,
+        #[max_length = 64]
+        content_hash -> Nullable<Varchar>,
+        #[max_length = 1024]
+        funding_url -> Nullable<Varchar>,
+        funding_text -> Nullable<Text>,
+        persons -> Nullable<Jsonb>,
+        extra -> Nullable<Jsonb>,
+        last_fetched_at -> Nullable<Timestamptz>,
+        blocked -> Nullable<Bool>,
     }
 }
 
 diesel::joinable!(episodes -> podcasts (podcast_id));
 
-diesel::allow_tables_to_appear_in_same_query!(episode_rank, episodes, podcast_rank, podcasts,);
+diesel::allow_tables_to_appear_in_same_query!(
+    episode_rank,
+    episodes,
+    failed_feeds,
+    podcast_rank,
+    podcasts,
+);
@@ -1,4 +1,10 @@
-use podcast_crawler::crawler::{rss::RssFeedParser, HttpCrawler};
+use podcast_crawler::crawler::{rss::RssFeedParser, Crawler, HttpCrawler};
+use podcast_crawler::infrastructure::config::CrawlerConfig;
+use podcast_crawler::infrastructure::error::{AppError, NetworkErrorKind};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
 use wiremock::matchers::{method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
@@ -153,3 +159,194 @@ async fn integration_test_crawler_with_rss_parser() {
         );
     }
 }
+
+#[tokio::test]
+async fn test_crawler_returns_final_url_after_redirect() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/old-feed"))
+        .respond_with(ResponseTemplate::new(301).append_header(
+            "Location",
+            format!("{}/new-feed", mock_server.uri()).as_str(),
+        ))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/new-feed"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(include_str!("../tests/data/complex_feed.xml")),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let crawler = HttpCrawler::new(RssFeedParser::new(), 1);
+    let (content, final_url) = crawler
+        .fetch_with_final_url(&format!("{}/old-feed", mock_server.uri()))
+        .await
+        .expect("redirected fetch should succeed");
+
+    assert!(!content.is_empty());
+    assert_eq!(final_url, format!("{}/new-feed", mock_server.uri()));
+}
+
+#[tokio::test]
+async fn test_crawler_redirect_loop_is_too_many_redirects() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/loop"))
+        .respond_with(
+            ResponseTemplate::new(302)
+                .append_header("Location", format!("{}/loop", mock_server.uri()).as_str()),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let config = CrawlerConfig {
+        max_concurrent_tasks: 1,
+        max_redirects: 3,
+        ..CrawlerConfig::default()
+    };
+    let crawler = HttpCrawler::with_config(RssFeedParser::new(), &config);
+    let result = crawler.fetch(&format!("{}/loop", mock_server.uri())).await;
+
+    match result {
+        Err(AppError::Network(e)) => assert_eq!(e.kind, NetworkErrorKind::TooManyRedirects),
+        other => panic!(
+            "expected TooManyRedirects, got {:?}",
+            other.map(|c| c.len())
+        ),
+    }
+}
+
+#[tokio::test]
+async fn test_crawler_follows_exactly_max_redirects() {
+    let mock_server = MockServer::start().await;
+
+    // /hop/0 -> /hop/1 -> /hop/2 -> /feed.xml is three redirects
+    for hop in 0..3 {
+        let next = match hop {
+            2 => format!("{}/feed.xml", mock_server.uri()),
+            hop => format!("{}/hop/{}", mock_server.uri(), hop + 1),
+        };
+        Mock::given(method("GET"))
+            .and(path(format!("/hop/{}", hop)))
+            .respond_with(ResponseTemplate::new(302).append_header("Location", next.as_str()))
+            .mount(&mock_server)
+            .await;
+    }
+    Mock::given(method("GET"))
+        .and(path("/feed.xml"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("<rss/>"))
+        .mount(&mock_server)
+        .await;
+
+    let url = format!("{}/hop/0", mock_server.uri());
+    let crawler = |max_redirects| {
+        let config = CrawlerConfig {
+            max_concurrent_tasks: 1,
+            max_redirects,
+            ..CrawlerConfig::default()
+        };
+        HttpCrawler::with_config(RssFeedParser::new(), &config)
+    };
+
+    assert_eq!(crawler(3).fetch(&url).await.unwrap(), b"<rss/>");
+    match crawler(2).fetch(&url).await {
+        Err(AppError::Network(e)) => assert_eq!(e.kind, NetworkErrorKind::TooManyRedirects),
+        other => panic!(
+            "expected TooManyRedirects, got {:?}",
+            other.map(|c| c.len())
+        ),
+    }
+}
+
+#[tokio::test]
+async fn test_crawler_rejects_oversized_feed() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/huge-feed"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("x".repeat(4096)))
+        .mount(&mock_server)
+        .await;
+
+    let config = CrawlerConfig {
+        max_concurrent_tasks: 1,
+        max_feed_bytes: 1024,
+        ..CrawlerConfig::default()
+    };
+    let crawler = HttpCrawler::with_config(RssFeedParser::new(), &config);
+    let result = crawler
+        .fetch(&format!("{}/huge-feed", mock_server.uri()))
+        .await;
+
+    match result {
+        Err(AppError::Network(e)) => assert_eq!(e.kind, NetworkErrorKind::InvalidResponse),
+        other => panic!("expected InvalidResponse, got {:?}", other.map(|c| c.len())),
+    }
+}
+
+// Minimal HTTP/1.1 server answering every request with `body`, counting accepted TCP connections
+async fn start_counting_server(body: &'static str) -> (String, Arc<AtomicUsize>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let connections = Arc::new(AtomicUsize::new(0));
+    let counter = connections.clone();
+    tokio::spawn(async move {
+        loop {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            counter.fetch_add(1, Ordering::SeqCst);
+            tokio::spawn(async move {
+                let mut buf = vec![0u8; 4096];
+                // The small GET requests of this test each arrive in a single read
+                while let Ok(n) = socket.read(&mut buf).await {
+                    if n == 0 {
+                        break;
+                    }
+                    let response = format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: application/rss+xml\r\nContent-Length: {}\r\n\r\n{}",
+                        body.len(),
+                        body
+                    );
+                    if socket.write_all(response.as_bytes()).await.is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+    });
+    (format!("http://{}", addr), connections)
+}
+
+/// Every fetch without keep-alive opens a new connection, paying a TCP
+/// handshake (one round trip) plus a TLS handshake (one or two more) for
+/// HTTPS feeds. Against a remote CDN that is typically 50-300 ms per feed;
+/// on localhost the difference is too small to assert on, so the test counts
+/// connections instead.
+#[tokio::test]
+async fn test_crawler_reuses_connections() {
+    let (uri, connections) = start_counting_server("<rss/>").await;
+    let fetch_three_times = |pool_max_idle_per_host| {
+        let uri = uri.clone();
+        async move {
+            let config = CrawlerConfig {
+                max_concurrent_tasks: 1,
+                pool_max_idle_per_host,
+                ..CrawlerConfig::default()
+            };
+            let crawler = HttpCrawler::with_config(RssFeedParser::new(), &config);
+            for i in 0..3 {
+                let content = crawler.fetch(&format!("{}/feed{}", uri, i)).await.unwrap();
+                assert_eq!(content, b"<rss/>");
+            }
+        }
+    };
+
+    fetch_three_times(0).await;
+    assert_eq!(connections.swap(0, Ordering::SeqCst), 3);
+    fetch_three_times(8).await;
+    assert_eq!(connections.load(Ordering::SeqCst), 1);
+}
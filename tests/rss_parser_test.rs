@@ -1,6 +1,6 @@
 use chrono::Datelike;
 use podcast_crawler::crawler::rss::{
-    clean_html, parse_bool, parse_date, validate_url, RssFeedParser,
+    clean_html, detect_language, parse_bool, parse_date, validate_url, ParserConfig, RssFeedParser,
 };
 
 use podcast_crawler::crawler::traits::FeedParser;
@@ -250,3 +250,137 @@ fn test_validate_url() {
     println!("{:?}", url::Url::parse("a:////invalid"));
     assert!(validate_url("a:////invalid").is_err());
 }
+
+#[tokio::test]
+async fn test_parse_podcast_transcript() {
+    let parser = RssFeedParser::new();
+    let (_podcast, episodes) = parser
+        .parse(
+            include_bytes!("data/transcript_feed.xml"),
+            "https://example.com/transcript_feed.xml",
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(episodes.len(), 3);
+
+    assert_eq!(
+        episodes[0].transcript_url.as_deref(),
+        Some("https://example.com/transcripts/1.vtt")
+    );
+    assert_eq!(episodes[0].transcript_type.as_deref(), Some("text/vtt"));
+
+    assert_eq!(
+        episodes[1].transcript_url.as_deref(),
+        Some("https://example.com/transcripts/2.html")
+    );
+    assert_eq!(episodes[1].transcript_type.as_deref(), Some("text/html"));
+
+    assert_eq!(episodes[2].transcript_url, None);
+    assert_eq!(episodes[2].transcript_type, None);
+}
+
+#[tokio::test]
+async fn test_detect_language_for_undeclared_feed() {
+    let parser = RssFeedParser::with_config(ParserConfig::default().with_language_detection(true));
+    let (podcast, _episodes) = parser
+        .parse(
+            include_bytes!("data/undeclared_language_feed.xml"),
+            "https://example.com/garden.xml",
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(podcast.language.as_deref(), Some("en"));
+}
+
+#[test]
+fn test_detect_language_short_text_is_unreliable() {
+    assert_eq!(detect_language("ok"), None);
+}
+
+#[tokio::test]
+async fn test_multiple_enclosures_prefer_audio() {
+    let parser = RssFeedParser::new();
+    let (_podcast, episodes) = parser
+        .parse(
+            include_bytes!("data/multi_enclosure_feed.xml"),
+            "https://example.com/multi.xml",
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        episodes[0].enclosure_url.as_deref(),
+        Some("https://example.com/audio/multi-1.mp3")
+    );
+    assert_eq!(episodes[0].enclosure_length, Some(1000));
+    assert_eq!(
+        episodes[1].enclosure_url.as_deref(),
+        Some("https://example.com/audio/multi-2.m4a")
+    );
+    assert_eq!(episodes[1].enclosure_type.as_deref(), Some("audio/x-m4a"));
+}
+
+#[tokio::test]
+async fn test_require_audio_enclosure_rejects_html() {
+    let rss = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <rss version="2.0">
+            <channel>
+                <title>Broken Enclosures</title>
+                <item>
+                    <title>Landing Page</title>
+                    <enclosure url="https://example.com/episodes/1.html" type="text/html" length="100"/>
+                </item>
+            </channel>
+        </rss>"#;
+    let url = "https://example.com/feed.xml";
+
+    let strict =
+        RssFeedParser::with_config(ParserConfig::default().with_audio_enclosure_check(true));
+    assert!(strict.parse(rss.as_bytes(), url).await.is_err());
+
+    let lenient = RssFeedParser::with_config(
+        ParserConfig::default()
+            .with_audio_enclosure_check(true)
+            .with_strict_mode(false),
+    );
+    let (_podcast, episodes) = lenient.parse(rss.as_bytes(), url).await.unwrap();
+    assert_eq!(episodes.len(), 1);
+    assert_eq!(episodes[0].enclosure_url, None);
+}
+
+#[tokio::test]
+async fn test_parse_feed_with_leading_bom() {
+    let content = format!(
+        "\u{FEFF}{}",
+        include_str!("data/undeclared_language_feed.xml")
+    );
+    let parser = RssFeedParser::new();
+    let (podcast, episodes) = parser
+        .parse(content.as_bytes(), "https://example.com/garden.xml")
+        .await
+        .unwrap();
+
+    assert_eq!(podcast.title, "The Weekly Garden Hour");
+    assert_eq!(episodes.len(), 1);
+}
+
+#[tokio::test]
+async fn test_parse_gb2312_encoded_feed() {
+    let parser = RssFeedParser::new();
+    let (podcast, episodes) = parser
+        .parse(
+            include_bytes!("data/gb2312_feed.xml"),
+            "https://example.com/gb2312.xml",
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(podcast.title, "中文播客测试");
+    assert_eq!(
+        podcast.description.as_deref(),
+        Some("这是一个使用 GB2312 编码的播客")
+    );
+    assert_eq!(episodes[0].title, "第一期：你好世界");
+}